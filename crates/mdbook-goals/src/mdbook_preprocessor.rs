@@ -238,8 +238,13 @@ impl<'c> GoalPreprocessorWithContext<'c> {
 
             goals_with_status.sort_by_key(|g| &g.metadata.title);
 
+            // If goals have categories, they are listed (and numbered) category by category.
+            if let Some(groups) = goal::by_category(&goals_with_status) {
+                goals_with_status = groups.into_iter().flat_map(|(_, goals)| goals).collect();
+            }
+
             // Format the list of goals and replace the `<!-- -->` comment with that.
-            let output = goal::format_goal_table(&goals_with_status)?;
+            let output = goal::format_goal_tables_by_category(&goals_with_status)?;
             chapter.content.replace_range(range, &output);
 
             // Populate with children if this is not README
@@ -323,7 +328,8 @@ impl<'c> GoalPreprocessorWithContext<'c> {
             return Ok(goals.clone());
         }
 
-        let goal_documents = goal::goals_in_dir(&self.ctx.config.book.src.join(milestone_path))?;
+        let goal_documents =
+            goal::goals_in_dir(&self.ctx.config.book.src.join(milestone_path), false)?;
         let goals = Arc::new(goal_documents);
         self.goal_document_map
             .insert(milestone_path.to_path_buf(), goals.clone());
@@ -482,7 +488,13 @@ impl<'c> GoalPreprocessorWithContext<'c> {
             );
         };
 
-        let replacement = op(goal).join(", ");
+        // Goals without team asks (or task owners) render "None" rather than an empty cell.
+        let values = op(goal);
+        let replacement = if values.is_empty() {
+            "None".to_string()
+        } else {
+            values.join(", ")
+        };
         chapter.content.replace_range(range, &replacement);
 
         Ok(())
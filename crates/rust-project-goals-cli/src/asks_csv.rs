@@ -0,0 +1,109 @@
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+use rust_project_goals::goal;
+
+const HEADER: [&str; 7] = [
+    "goal", "slug", "team", "owners", "required", "task", "complete",
+];
+
+/// Write a CSV with one row per team ask (per team) of the goals in `path`, for planning in a
+/// spreadsheet. Whether an ask is required is guessed from its notes, see [`is_optional`].
+pub(super) fn asks_csv(path: &Path, output: &Option<PathBuf>, strict: bool) -> anyhow::Result<()> {
+    let goal_documents = goal::goals_in_dir(path, strict)?;
+
+    let mut text = String::new();
+    writeln!(text, "{}", csv_row(HEADER))?;
+    for goal_document in &goal_documents {
+        let slug = goal_document
+            .link_path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .unwrap_or_default();
+        let owners = goal_document.metadata.owner_usernames().join(" ");
+
+        for entry in goal_document.team_ask_entries() {
+            let required = if is_optional(&entry.notes) {
+                "optional"
+            } else {
+                "required"
+            };
+            let complete = if entry.is_complete { "yes" } else { "no" };
+            writeln!(
+                text,
+                "{}",
+                csv_row([
+                    &goal_document.metadata.title,
+                    slug,
+                    entry.team.as_str(),
+                    &owners,
+                    required,
+                    &entry.ask_description,
+                    complete,
+                ])
+            )?;
+        }
+    }
+
+    if let Some(output) = output {
+        if let Some(parent) = output.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(output, text)?;
+    } else {
+        print!("{text}");
+    }
+
+    Ok(())
+}
+
+/// Goal documents have no structured way to mark a team ask as optional, so this is a heuristic:
+/// the ask is optional if its notes contain the word "optional", unless it is negated as in
+/// "not optional" or "non-optional".
+fn is_optional(notes: &str) -> bool {
+    let notes = notes.to_lowercase();
+    notes.match_indices("optional").any(|(index, _)| {
+        let before = notes[..index].trim_end_matches(['-', ' ']);
+        !(before.ends_with("not") || before.ends_with("non"))
+    })
+}
+
+fn csv_row<'a>(fields: impl IntoIterator<Item = &'a str>) -> String {
+    fields
+        .into_iter()
+        .map(csv_field)
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Quote `field` if it contains a comma, quote, or newline, doubling any quotes (RFC 4180).
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fields_are_escaped() {
+        assert_eq!(
+            csv_row(["plain", "a, b", "say \"hi\"", "two\nlines"]),
+            "plain,\"a, b\",\"say \"\"hi\"\"\",\"two\nlines\""
+        );
+    }
+
+    #[test]
+    fn optional_asks_are_guessed_from_notes() {
+        assert!(is_optional("Optional, if time permits"));
+        assert!(is_optional("nice to have (optional)"));
+        assert!(!is_optional(""));
+        assert!(!is_optional("Not optional: blocks stabilization"));
+        assert!(!is_optional("non-optional"));
+        assert!(is_optional("not urgent, and optional"));
+    }
+}
@@ -0,0 +1,99 @@
+use std::path::{Path, PathBuf};
+
+use rust_project_goals::goal;
+use serde::Serialize;
+
+use rust_project_goals::sync::TaskCounts;
+
+/// The JSON read by a shields.io endpoint badge, see <https://shields.io/badges/endpoint-badge>.
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+struct EndpointBadge {
+    schema_version: u32,
+    label: String,
+    message: String,
+    color: String,
+}
+
+/// Write the shields.io endpoint JSON for a badge showing how many tasks of the goal
+/// `goal_slug` (found in the directory `path`) are complete.
+pub(super) fn badge_json(
+    path: &Path,
+    goal_slug: &str,
+    label: &str,
+    output: &Option<PathBuf>,
+    strict: bool,
+) -> anyhow::Result<()> {
+    let goal_documents = goal::goals_in_dir(path, strict)?;
+    let Some(goal_document) = goal_documents.iter().find(|doc| doc.slug() == goal_slug) else {
+        anyhow::bail!("no goal named `{goal_slug}` found in `{}`", path.display());
+    };
+
+    let json = serde_json::to_string(&badge(label, TaskCounts::of(goal_document)))?;
+    if let Some(output) = output {
+        if let Some(parent) = output.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(output, json)?;
+    } else {
+        println!("{json}");
+    }
+
+    Ok(())
+}
+
+fn badge(label: &str, counts: TaskCounts) -> EndpointBadge {
+    let (message, color) = if counts.total == 0 {
+        ("no tasks".to_string(), "lightgrey".to_string())
+    } else {
+        let completion = f64::from(counts.completed) / f64::from(counts.total);
+        (
+            format!("{:.0}%", completion * 100.0),
+            completion_color(completion),
+        )
+    };
+    EndpointBadge {
+        schema_version: 1,
+        label: label.to_string(),
+        message,
+        color,
+    }
+}
+
+/// A hex color going from red (nothing complete) through yellow to green (all complete).
+fn completion_color(completion: f64) -> String {
+    // The hue goes from 0° (red) to 120° (green), at full saturation and 40% lightness.
+    let hue = 120.0 * completion.clamp(0.0, 1.0);
+    let lightness = 0.4;
+    let chroma = 1.0 - (2.0 * lightness - 1.0_f64).abs();
+    let x = chroma * (1.0 - ((hue / 60.0) % 2.0 - 1.0).abs());
+    let (r, g) = if hue < 60.0 { (chroma, x) } else { (x, chroma) };
+    let m = lightness - chroma / 2.0;
+    let channel = |c: f64| ((c + m) * 255.0).round() as u8;
+    format!("{:02x}{:02x}{:02x}", channel(r), channel(g), channel(0.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn badge_scales_from_red_to_green() {
+        let badge = |completed, total| {
+            let counts = TaskCounts {
+                completed,
+                total,
+                ..TaskCounts::default()
+            };
+            badge("progress", counts)
+        };
+        assert_eq!(
+            serde_json::to_string(&badge(3, 5)).unwrap(),
+            r#"{"schemaVersion":1,"label":"progress","message":"60%","color":"a3cc00"}"#
+        );
+        assert_eq!(badge(0, 4).color, "cc0000");
+        assert_eq!(badge(2, 4).color, "cccc00");
+        assert_eq!(badge(4, 4).color, "00cc00");
+        assert_eq!(badge(0, 0).message, "no tasks");
+    }
+}
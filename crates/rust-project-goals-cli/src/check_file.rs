@@ -0,0 +1,129 @@
+use std::io::Read;
+use std::path::Path;
+
+use anyhow::Context;
+use rust_project_goals::{
+    gh::issue_id::Repository,
+    goal::{self, GoalDocument},
+    re, sync,
+    util::Warnings,
+};
+
+use crate::{
+    error::{ClassifiedError, ErrorKind},
+    rfc,
+};
+
+/// Name under which a document read from stdin is reported.
+const STDIN: &str = "<stdin>";
+
+/// Check the single goal document at `path` (or read from stdin if `None`), without loading
+/// the rest of its directory, then print the body its tracking issue would get.
+/// Meant for editors to lint a goal on save.
+pub(super) fn check_file(
+    path: Option<&Path>,
+    timeframe: Option<&str>,
+    repository: &Repository,
+) -> anyhow::Result<()> {
+    let (path, text) = match path {
+        Some(path) => (
+            path,
+            std::fs::read_to_string(path)
+                .with_context(|| format!("reading `{}`", path.display()))?,
+        ),
+        None => {
+            let mut text = String::new();
+            std::io::stdin()
+                .read_to_string(&mut text)
+                .context("reading stdin")?;
+            (Path::new(STDIN), text)
+        }
+    };
+
+    let problems = goal::validate_metadata_in_text(path, &text, Some(repository))?;
+    for problem in &problems {
+        eprintln!("{problem}");
+    }
+    if !problems.is_empty() {
+        return Err(ClassifiedError::new(
+            ErrorKind::Validation,
+            format!("found {} metadata problem(s)", problems.len()),
+        )
+        .into());
+    }
+
+    let link_path = Path::new(path.file_name().unwrap_or(path.as_os_str()));
+    let document = match GoalDocument::from_text(path, link_path, &text) {
+        Ok(Some(document)) => document,
+        Ok(None) => anyhow::bail!(
+            "`{}` is not a goal document (its first section has no `Metadata` table)",
+            path.display()
+        ),
+        Err(e) => {
+            return Err(ClassifiedError::new(
+                ErrorKind::Validation,
+                format!("loading goal from `{}`: {e:#}", path.display()),
+            )
+            .into())
+        }
+    };
+    rfc::warn_if_lacking_tasks(&document, &mut Warnings::default());
+
+    let timeframe = document_timeframe(&document, timeframe)?;
+    print!("{}", sync::issue_preview(&timeframe, &document)?);
+    Ok(())
+}
+
+/// The timeframe of `document`: the one given with `--timeframe`, or else its `Timeframe`
+/// row, or else the name of its directory.
+fn document_timeframe(document: &GoalDocument, timeframe: Option<&str>) -> anyhow::Result<String> {
+    if let Some(timeframe) = timeframe {
+        return Ok(timeframe.to_string());
+    }
+
+    let row = document
+        .metadata
+        .table
+        .rows
+        .iter()
+        .find(|row| row[0] == goal::TIMEFRAME_ROW);
+    if let Some(row) = row {
+        return Ok(row[1].trim().to_string());
+    }
+
+    let directory = document
+        .path
+        .parent()
+        .and_then(|parent| parent.file_name())
+        .and_then(|name| name.to_str());
+    match directory {
+        Some(name) if re::TIMEFRAME.is_match(name) => Ok(name.to_string()),
+        _ => anyhow::bail!(
+            "cannot infer the timeframe of `{}`; pass `--timeframe`",
+            document.path.display()
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_project_goals::goal::test_support::GoalText;
+
+    use super::*;
+
+    #[test]
+    fn timeframe_comes_from_the_option_row_or_directory() {
+        let in_dir = GoalText::new("Goal").document("src/2025h1/goal.md");
+        assert_eq!(document_timeframe(&in_dir, None).unwrap(), "2025h1");
+        assert_eq!(
+            document_timeframe(&in_dir, Some("2025h2")).unwrap(),
+            "2025h2"
+        );
+
+        let declared = GoalText::new("Goal")
+            .row("Timeframe", "2024h2")
+            .document(STDIN);
+        assert_eq!(document_timeframe(&declared, None).unwrap(), "2024h2");
+        assert!(document_timeframe(&GoalText::new("Goal").document(STDIN), None).is_err());
+    }
+}
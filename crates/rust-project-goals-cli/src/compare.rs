@@ -0,0 +1,131 @@
+use std::fmt::Write;
+use std::path::Path;
+
+use rust_project_goals::goal::{self, GoalDocument};
+
+use rust_project_goals::sync::TaskCounts;
+
+/// How a goal of the new timeframe relates to the old one.
+#[derive(Debug)]
+enum Comparison<'doc> {
+    /// The goal is in both timeframes (same slug or same title).
+    Continued {
+        old: &'doc GoalDocument,
+        new: &'doc GoalDocument,
+    },
+
+    /// The goal is only in the new timeframe.
+    New(&'doc GoalDocument),
+
+    /// The goal is only in the old timeframe.
+    Dropped(&'doc GoalDocument),
+}
+
+/// Print which goals of `new_path` continue from `old_path` (with their task completion
+/// in both), which are new, and which were dropped.
+pub(super) fn compare(old_path: &Path, new_path: &Path, strict: bool) -> anyhow::Result<()> {
+    let old_goals = goal::goals_in_dir(old_path, strict)?;
+    let new_goals = goal::goals_in_dir(new_path, strict)?;
+    let comparisons = compare_goals(&old_goals, &new_goals);
+
+    let mut continued = String::new();
+    let mut new = String::new();
+    let mut dropped = String::new();
+    for comparison in &comparisons {
+        match comparison {
+            Comparison::Continued { old, new } => {
+                let before = TaskCounts::of(old);
+                let after = TaskCounts::of(new);
+                writeln!(
+                    continued,
+                    "* {}: {} of {} tasks complete, now {} of {}",
+                    new.metadata.title,
+                    before.completed,
+                    before.total,
+                    after.completed,
+                    after.total,
+                )?;
+            }
+            Comparison::New(goal) => writeln!(new, "* {}", goal.metadata.title)?,
+            Comparison::Dropped(goal) => writeln!(dropped, "* {}", goal.metadata.title)?,
+        }
+    }
+
+    for (header, list) in [
+        ("Continued goals", continued),
+        ("New goals", new),
+        ("Dropped goals", dropped),
+    ] {
+        if !list.is_empty() {
+            println!("## {header}\n\n{list}");
+        }
+    }
+
+    Ok(())
+}
+
+/// Match the goals of the two timeframes by slug, or else by title.
+fn compare_goals<'doc>(
+    old_goals: &'doc [GoalDocument],
+    new_goals: &'doc [GoalDocument],
+) -> Vec<Comparison<'doc>> {
+    let same_goal = |old: &GoalDocument, new: &GoalDocument| {
+        old.slug() == new.slug() || old.metadata.title == new.metadata.title
+    };
+
+    let mut comparisons: Vec<_> = new_goals
+        .iter()
+        .map(
+            |new| match old_goals.iter().find(|old| same_goal(old, new)) {
+                Some(old) => Comparison::Continued { old, new },
+                None => Comparison::New(new),
+            },
+        )
+        .collect();
+    comparisons.extend(
+        old_goals
+            .iter()
+            .filter(|old| !new_goals.iter().any(|new| same_goal(old, new)))
+            .map(Comparison::Dropped),
+    );
+    comparisons
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_project_goals::goal::test_support::GoalText;
+
+    use super::*;
+
+    fn goal(timeframe: &str, slug: &str, title: &str) -> GoalDocument {
+        GoalText::new(title).document(&format!("src/{timeframe}/{slug}.md"))
+    }
+
+    #[test]
+    fn goals_are_matched_by_slug_or_title() {
+        let old = [
+            goal("2025h1", "async", "Async"),
+            goal("2025h1", "old-name", "Parallel frontend"),
+            goal("2025h1", "gone", "Gone"),
+        ];
+        let new = [
+            goal("2025h2", "async", "Async closures"),
+            goal("2025h2", "new-name", "Parallel frontend"),
+            goal("2025h2", "fresh", "Fresh"),
+        ];
+        let summary: Vec<String> = compare_goals(&old, &new)
+            .iter()
+            .map(|comparison| match comparison {
+                Comparison::Continued { old, new } => {
+                    format!("{} -> {}", old.slug(), new.slug())
+                }
+                Comparison::New(goal) => format!("+{}", goal.slug()),
+                Comparison::Dropped(goal) => format!("-{}", goal.slug()),
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            ["async -> async", "old-name -> new-name", "+fresh", "-gone"]
+        );
+    }
+}
@@ -0,0 +1,174 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::PathBuf;
+
+use rust_project_goals::{
+    gh::{
+        backend::GithubBackend,
+        issue_id::Repository,
+        issues::{ExistingGithubIssue, DUPLICATE_GOAL_PREFIX},
+    },
+    goal,
+    sync::{self, GithubAction, LabelOptions},
+};
+use rust_project_goals_json::GithubIssueState;
+
+use crate::rfc::{self, IssuesOptions};
+
+/// Find open tracking issues in `milestone` that have the same (normalized) title and close
+/// all but one of them, leaving a comment that points to the one we keep.
+///
+/// The issue we keep is the one referenced by a goal document in `src/<milestone>`, if any,
+/// or else the one with the lowest number.
+pub(crate) fn dedup_milestone(
+    github: &dyn GithubBackend,
+    repository: &Repository,
+    milestone: &str,
+    label_options: &LabelOptions,
+    options: &IssuesOptions,
+    strict: bool,
+) -> anyhow::Result<()> {
+    let goals_dir = PathBuf::from("src").join(milestone);
+    let referenced: BTreeSet<u64> = if goals_dir.is_dir() {
+        goal::goals_in_dir(&goals_dir, strict)?
+            .iter()
+            .filter_map(|doc| doc.metadata.tracking_issue.as_ref())
+            .map(|issue_id| issue_id.number)
+            .collect()
+    } else {
+        eprintln!(
+            "warning: `{}` not found, keeping the lowest numbered issues",
+            goals_dir.display()
+        );
+        BTreeSet::new()
+    };
+
+    let issues = github.list_issues_in_milestone(repository, milestone)?;
+    let actions = dedup_actions(&issues, &referenced, &label_options.tracking_issue_label());
+
+    if actions.is_empty() {
+        eprintln!("No duplicate tracking issues in milestone {milestone}.");
+        Ok(())
+    } else if options.commit {
+        rfc::check_expected_repository(repository, options)?;
+        rfc::execute_actions(github, repository, actions, options)
+    } else {
+        rfc::print_actions(&actions, options)
+    }
+}
+
+/// The actions closing the duplicates among the open `issues` that have the
+/// `tracking_issue_label`.
+fn dedup_actions(
+    issues: &[ExistingGithubIssue],
+    referenced: &BTreeSet<u64>,
+    tracking_issue_label: &str,
+) -> BTreeSet<GithubAction<'static>> {
+    let mut by_title: BTreeMap<String, Vec<&ExistingGithubIssue>> = BTreeMap::new();
+    for issue in issues {
+        if issue.has_label(tracking_issue_label) && issue.state == GithubIssueState::Open {
+            by_title
+                .entry(normalized_title(&issue.title))
+                .or_default()
+                .push(issue);
+        }
+    }
+
+    let mut actions = BTreeSet::new();
+    for (_, mut group) in by_title {
+        if group.len() < 2 {
+            continue;
+        }
+
+        group.sort_by_key(|issue| (!referenced.contains(&issue.number), issue.number));
+        let canonical = group[0].number;
+        if group
+            .iter()
+            .filter(|issue| referenced.contains(&issue.number))
+            .count()
+            > 1
+        {
+            eprintln!(
+                "warning: several goal documents reference duplicates of #{canonical}, \
+                 their links need to be fixed by hand"
+            );
+        }
+
+        for duplicate in &group[1..] {
+            actions.insert(GithubAction::Comment {
+                number: duplicate.number,
+                body: format!("{DUPLICATE_GOAL_PREFIX} #{canonical}."),
+            });
+            actions.insert(GithubAction::CloseIssue {
+                number: duplicate.number,
+            });
+        }
+    }
+    actions
+}
+
+/// Titles that only differ in case, whitespace or Unicode normal form are considered the same.
+fn normalized_title(title: &str) -> String {
+    sync::normalize_title(title)
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_project_goals::gh::{issues::TRACKING_ISSUE_LABEL, labels::GhLabel};
+
+    use super::*;
+
+    fn tracking_issue(number: u64, title: &str) -> ExistingGithubIssue {
+        ExistingGithubIssue {
+            number,
+            title: title.to_string(),
+            assignees: BTreeSet::new(),
+            comments: vec![],
+            body: String::new(),
+            state: GithubIssueState::Open,
+            labels: vec![GhLabel {
+                name: TRACKING_ISSUE_LABEL.to_string(),
+                color: String::new(),
+            }],
+            milestone: None,
+        }
+    }
+
+    #[test]
+    fn duplicates_are_closed_in_favor_of_the_referenced_issue() {
+        let issues = [
+            tracking_issue(3, "Do it ourselves"),
+            tracking_issue(5, "do it  ourselves "),
+            tracking_issue(7, "Do it ourselves"),
+            tracking_issue(8, "Something else"),
+        ];
+
+        let actions = dedup_actions(&issues, &BTreeSet::from([5]), TRACKING_ISSUE_LABEL);
+        assert_eq!(
+            actions,
+            BTreeSet::from([
+                GithubAction::Comment {
+                    number: 3,
+                    body: format!("{DUPLICATE_GOAL_PREFIX} #5."),
+                },
+                GithubAction::CloseIssue { number: 3 },
+                GithubAction::Comment {
+                    number: 7,
+                    body: format!("{DUPLICATE_GOAL_PREFIX} #5."),
+                },
+                GithubAction::CloseIssue { number: 7 },
+            ])
+        );
+
+        // Without a reference, the lowest number wins.
+        let actions = dedup_actions(&issues, &BTreeSet::new(), TRACKING_ISSUE_LABEL);
+        assert!(actions.contains(&GithubAction::CloseIssue { number: 5 }));
+        assert!(!actions.contains(&GithubAction::CloseIssue { number: 3 }));
+
+        // Issues without the tracking label in use are left alone.
+        assert!(dedup_actions(&issues, &BTreeSet::new(), "tracking-issue").is_empty());
+    }
+}
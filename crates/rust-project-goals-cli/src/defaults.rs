@@ -0,0 +1,182 @@
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+use serde::Deserialize;
+
+/// The configuration file that is read from the current directory when `--config` is not given.
+const DEFAULT_CONFIG_FILE: &str = "rust-project-goals.toml";
+
+/// The part of the configuration file we care about. Other tables (e.g., `team_asks`)
+/// are read by the library.
+#[derive(Deserialize)]
+struct ConfigFile {
+    /// Default values for command-line options, keyed by their long name (e.g., `team-label-color`).
+    #[serde(default)]
+    cli: toml::Table,
+}
+
+/// Make the values in the `[cli]` table of the configuration file the defaults of the
+/// corresponding options of `command` and its subcommands, so that flags given on the
+/// command line still take precedence.
+///
+/// The file is the one given by `--config` in `args`, or else `rust-project-goals.toml`
+/// in the current directory, if there is one.
+pub(crate) fn apply_config_defaults(
+    command: clap::Command,
+    args: &[OsString],
+) -> anyhow::Result<clap::Command> {
+    let path = match config_arg(args) {
+        Some(path) => path,
+        None if Path::new(DEFAULT_CONFIG_FILE).exists() => PathBuf::from(DEFAULT_CONFIG_FILE),
+        None => return Ok(command),
+    };
+
+    let text = std::fs::read_to_string(&path)
+        .with_context(|| format!("reading configuration from `{}`", path.display()))?;
+    let config: ConfigFile = toml::from_str(&text)
+        .with_context(|| format!("parsing configuration from `{}`", path.display()))?;
+    with_defaults(command, &config.cli)
+        .with_context(|| format!("applying `[cli]` defaults from `{}`", path.display()))
+}
+
+/// The value of `--config` in `args`, if any. This has to be found before the arguments
+/// are parsed, since the configuration changes how they are parsed.
+fn config_arg(args: &[OsString]) -> Option<PathBuf> {
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        if arg == "--config" {
+            return args.next().map(PathBuf::from);
+        }
+        if let Some(path) = arg.to_str().and_then(|a| a.strip_prefix("--config=")) {
+            return Some(PathBuf::from(path));
+        }
+    }
+    None
+}
+
+fn with_defaults(
+    mut command: clap::Command,
+    defaults: &toml::Table,
+) -> anyhow::Result<clap::Command> {
+    for (name, value) in defaults {
+        let value = match value {
+            toml::Value::String(s) => s.clone(),
+            toml::Value::Integer(i) => i.to_string(),
+            _ => anyhow::bail!("`{name}` must be a string or an integer"),
+        };
+
+        let mut found = false;
+        command = set_default(command, name, &value, &mut found)?;
+        if !found {
+            anyhow::bail!("no command has an option `--{name}`");
+        }
+    }
+    Ok(command)
+}
+
+/// Set the default of the option `--{name}` of `command` and of all its subcommands.
+fn set_default(
+    mut command: clap::Command,
+    name: &str,
+    value: &str,
+    found: &mut bool,
+) -> anyhow::Result<clap::Command> {
+    let arg = command
+        .get_arguments()
+        .find(|arg| arg.get_long() == Some(name))
+        .map(|arg| (arg.get_id().clone(), arg.get_action().takes_values()));
+    if let Some((id, takes_values)) = arg {
+        if !takes_values {
+            anyhow::bail!("`--{name}` is a flag, it cannot be given a default");
+        }
+        let value = value.to_string();
+        command = command.mut_arg(id, |arg| arg.default_value(value));
+        *found = true;
+    }
+
+    let subcommands: Vec<String> = command
+        .get_subcommands()
+        .map(|subcommand| subcommand.get_name().to_string())
+        .collect();
+    for subcommand in subcommands {
+        let mut result = Ok(());
+        command = command.mut_subcommand(subcommand, |subcommand| {
+            match set_default(subcommand.clone(), name, value, found) {
+                Ok(subcommand) => subcommand,
+                Err(e) => {
+                    result = Err(e);
+                    subcommand
+                }
+            }
+        });
+        result?;
+    }
+    Ok(command)
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::{CommandFactory, FromArgMatches};
+
+    use super::*;
+
+    #[derive(clap::Parser, Debug)]
+    struct Opt {
+        #[arg(long)]
+        repository: Option<String>,
+
+        #[command(subcommand)]
+        cmd: Command,
+    }
+
+    #[derive(clap::Subcommand, Debug)]
+    enum Command {
+        Issues {
+            #[arg(long, default_value = "500")]
+            sleep: u64,
+
+            #[arg(long)]
+            commit: bool,
+        },
+    }
+
+    fn parse(config: &str, args: &[&str]) -> anyhow::Result<Opt> {
+        let command = with_defaults(Opt::command(), &toml::from_str(config)?)?;
+        let matches =
+            command.try_get_matches_from(std::iter::once("rpg").chain(args.iter().copied()))?;
+        Ok(Opt::from_arg_matches(&matches)?)
+    }
+
+    #[test]
+    fn command_line_overrides_config() {
+        let config = "repository = \"o/r\"\nsleep = 10\n";
+        let opt = parse(config, &["issues"]).unwrap();
+        assert_eq!(opt.repository.as_deref(), Some("o/r"));
+        assert!(matches!(opt.cmd, Command::Issues { sleep: 10, .. }));
+
+        let opt = parse(config, &["--repository", "a/b", "issues", "--sleep", "1"]).unwrap();
+        assert_eq!(opt.repository.as_deref(), Some("a/b"));
+        assert!(matches!(opt.cmd, Command::Issues { sleep: 1, .. }));
+    }
+
+    #[test]
+    fn unknown_options_and_flags_are_rejected() {
+        assert!(parse("slep = 10", &["issues"]).is_err());
+        assert!(parse("commit = \"true\"", &["issues"]).is_err());
+    }
+
+    #[test]
+    fn config_path_is_found_before_parsing() {
+        let args = |args: &[&str]| args.iter().map(OsString::from).collect::<Vec<_>>();
+        assert_eq!(config_arg(&args(&["rpg", "issues"])), None);
+        assert_eq!(
+            config_arg(&args(&["rpg", "--config", "ci.toml", "issues"])),
+            Some(PathBuf::from("ci.toml"))
+        );
+        assert_eq!(
+            config_arg(&args(&["rpg", "issues", "--config=ci.toml"])),
+            Some(PathBuf::from("ci.toml"))
+        );
+    }
+}
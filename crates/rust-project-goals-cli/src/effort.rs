@@ -0,0 +1,136 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::path::Path;
+
+use rust_project_goals::{
+    goal::{self, GoalDocument, ParsedOwners, PlanItem, WorkDays},
+    team::TeamName,
+};
+
+/// Effort summed over a set of plan items.
+#[derive(Debug, Default, PartialEq)]
+struct EffortTotals {
+    estimated: WorkDays,
+    /// Only counts the items whose actual effort was recorded.
+    actual: WorkDays,
+    /// Items without an effort annotation.
+    unestimated: usize,
+}
+
+impl EffortTotals {
+    fn add(&mut self, plan_item: &PlanItem) -> anyhow::Result<()> {
+        match plan_item.effort {
+            Some(effort) => {
+                self.estimated = self.estimated.checked_add(effort.estimated)?;
+                self.actual = self.actual.checked_add(effort.actual.unwrap_or_default())?;
+            }
+            None => self.unestimated += 1,
+        }
+        Ok(())
+    }
+}
+
+/// Print the estimated and actual effort (from annotations like `(est: 2w, actual: 3w)`
+/// in the owners of plan items) of each goal in `path`, and of the asks of each team.
+pub(super) fn effort(path: &Path, strict: bool) -> anyhow::Result<()> {
+    let goal_documents = goal::goals_in_dir(path, strict)?;
+
+    let mut text = String::new();
+    writeln!(text, "## Effort by goal\n")?;
+    writeln!(
+        text,
+        "| Goal | Estimated | Actual | Tasks without estimate |"
+    )?;
+    writeln!(text, "| --- | --- | --- | --- |")?;
+    for (title, totals) in effort_by_goal(&goal_documents)? {
+        write_row(&mut text, title, &totals)?;
+    }
+
+    writeln!(text, "\n## Effort by team\n")?;
+    writeln!(
+        text,
+        "| Team | Estimated | Actual | Asks without estimate |"
+    )?;
+    writeln!(text, "| --- | --- | --- | --- |")?;
+    for (team, totals) in effort_by_team(&goal_documents)? {
+        write_row(&mut text, &team.name(), &totals)?;
+    }
+    print!("{text}");
+
+    Ok(())
+}
+
+fn write_row(text: &mut String, name: &str, totals: &EffortTotals) -> anyhow::Result<()> {
+    let EffortTotals {
+        estimated,
+        actual,
+        unestimated,
+    } = totals;
+    writeln!(text, "| {name} | {estimated} | {actual} | {unestimated} |")?;
+    Ok(())
+}
+
+fn effort_by_goal(goal_documents: &[GoalDocument]) -> anyhow::Result<Vec<(&str, EffortTotals)>> {
+    goal_documents
+        .iter()
+        .map(|goal_document| {
+            let mut totals = EffortTotals::default();
+            for plan_item in plan_items(goal_document) {
+                totals.add(plan_item)?;
+            }
+            Ok((goal_document.metadata.title.as_str(), totals))
+        })
+        .collect()
+}
+
+/// The effort of the team asks, counted in full for each team being asked.
+fn effort_by_team(
+    goal_documents: &[GoalDocument],
+) -> anyhow::Result<BTreeMap<&'static TeamName, EffortTotals>> {
+    let mut by_team: BTreeMap<&'static TeamName, EffortTotals> = BTreeMap::new();
+    for plan_item in goal_documents.iter().flat_map(plan_items) {
+        if let Some(ParsedOwners::TeamAsks(teams)) = plan_item.parse_owners()? {
+            for team in teams {
+                by_team.entry(team).or_default().add(plan_item)?;
+            }
+        }
+    }
+    Ok(by_team)
+}
+
+fn plan_items(goal_document: &GoalDocument) -> impl Iterator<Item = &PlanItem> {
+    goal_document
+        .goal_plans
+        .iter()
+        .flat_map(|goal_plan| &goal_plan.plan_items)
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_project_goals::goal::test_support::GoalText;
+
+    use super::*;
+
+    #[test]
+    fn effort_is_summed_per_goal() {
+        let goal_document = GoalText::new("Goal")
+            .row("Point of contact", "@alice")
+            .task("Design", "@alice (est: 3d, actual: 1w)", "")
+            .task("Implementation", "@alice, @bob (est: 2w)", "")
+            .task("Documentation", "@bob", "")
+            .document("src/2025h1/goal.md");
+        let goals = [goal_document];
+        assert_eq!(
+            effort_by_goal(&goals).unwrap(),
+            [(
+                "Goal",
+                EffortTotals {
+                    estimated: WorkDays(13),
+                    actual: WorkDays(5),
+                    unestimated: 1,
+                }
+            )]
+        );
+        assert_eq!(goals[0].task_owners.len(), 2);
+    }
+}
@@ -0,0 +1,93 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use serde::Serialize;
+
+/// Set by `--json-errors`: report errors as JSON objects on stderr instead of text.
+static JSON_ERRORS: AtomicBool = AtomicBool::new(false);
+
+pub(crate) fn set_json_errors(enabled: bool) {
+    JSON_ERRORS.store(enabled, Ordering::Relaxed);
+}
+
+pub(crate) fn json_errors() -> bool {
+    JSON_ERRORS.load(Ordering::Relaxed)
+}
+
+#[derive(Copy, Clone, Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) enum ErrorKind {
+    /// The goal documents (or the command line) are not valid.
+    Validation,
+
+    /// A `gh` command failed.
+    Github,
+
+    /// Reading or writing a file failed.
+    Io,
+
+    Other,
+}
+
+/// An error that knows what kind of failure it is, for `--json-errors`.
+#[derive(Debug)]
+pub(crate) struct ClassifiedError {
+    pub kind: ErrorKind,
+    pub message: String,
+    pub action: Option<String>,
+    pub issue: Option<u64>,
+}
+
+impl ClassifiedError {
+    pub(crate) fn new(kind: ErrorKind, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            message: message.into(),
+            action: None,
+            issue: None,
+        }
+    }
+}
+
+impl std::fmt::Display for ClassifiedError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for ClassifiedError {}
+
+/// The JSON object printed for an error with `--json-errors`.
+#[derive(Debug, Serialize)]
+pub(crate) struct JsonError<'a> {
+    pub kind: ErrorKind,
+    pub action: Option<&'a str>,
+    pub issue: Option<u64>,
+    pub message: String,
+}
+
+impl<'a> JsonError<'a> {
+    /// Describe `error`, using the details of a [`ClassifiedError`] in its chain if there is one.
+    pub(crate) fn from_anyhow(error: &'a anyhow::Error) -> Self {
+        let classified = error
+            .chain()
+            .find_map(|e| e.downcast_ref::<ClassifiedError>());
+        let kind = match classified {
+            Some(classified) => classified.kind,
+            None if error.chain().any(|e| e.is::<std::io::Error>()) => ErrorKind::Io,
+            None => ErrorKind::Other,
+        };
+        JsonError {
+            kind,
+            action: classified.and_then(|c| c.action.as_deref()),
+            issue: classified.and_then(|c| c.issue),
+            message: format!("{error:#}"),
+        }
+    }
+
+    pub(crate) fn print(&self) {
+        match serde_json::to_string(self) {
+            Ok(json) => eprintln!("{json}"),
+            Err(e) => eprintln!("{}: {e}", self.message),
+        }
+    }
+}
@@ -0,0 +1,250 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+use rust_project_goals::{
+    action::Action,
+    gh::{backend::GithubBackend, issue_id::Repository},
+    sync::{self, GithubAction, LabelOptions, SyncOptions},
+};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::rfc::{self, IssuesOptions};
+
+/// The actions planned by `cargo rpg issues`, split so that they can be executed by parallel jobs.
+struct MatrixPlan {
+    /// Actions on the repository itself (milestones and labels), which must come first.
+    setup: Vec<Action>,
+
+    /// Actions on existing issues, each issue's actions all in the same chunk.
+    chunks: Vec<BTreeMap<u64, Vec<Action>>>,
+
+    /// Actions left for `cargo rpg issues`: creating issues and editing goal documents.
+    remaining: Vec<Action>,
+}
+
+/// Where `export-matrix` writes the actions, and for how many jobs.
+#[derive(clap::Args, Debug)]
+pub(crate) struct MatrixOptions {
+    /// Maximum number of parallel jobs (chunks) to split the issues into.
+    #[arg(long, default_value = "8")]
+    pub jobs: usize,
+
+    /// Directory for `setup.json` and the `chunk-<n>.json` files.
+    #[arg(long, default_value = "actions")]
+    pub output_dir: PathBuf,
+}
+
+/// A file of actions written by `export-matrix` and read by `execute-actions`.
+#[derive(Serialize, Deserialize)]
+struct ActionsFile {
+    repository: String,
+    actions: Vec<Action>,
+}
+
+/// Plan the actions for the goals in `paths` like `cargo rpg issues`, and write them to the
+/// output directory as `setup.json` and one `chunk-<n>.json` per job. The matrix listing
+/// the chunks is printed on stdout, for the `strategy.matrix` of a workflow.
+pub(crate) fn export_matrix(
+    github: &dyn GithubBackend,
+    repository: &Repository,
+    paths: &[PathBuf],
+    timeframe: Option<&str>,
+    label_options: &LabelOptions,
+    sync_options: SyncOptions<'_>,
+    matrix_options: &MatrixOptions,
+) -> anyhow::Result<()> {
+    let MatrixOptions { jobs, output_dir } = matrix_options;
+    let actions = sync::plan_actions(
+        github,
+        repository,
+        paths,
+        timeframe,
+        label_options,
+        sync_options,
+    )?;
+    let plan = split_actions(actions, *jobs);
+
+    std::fs::create_dir_all(output_dir)
+        .with_context(|| format!("creating directory `{}`", output_dir.display()))?;
+    write_actions(
+        &output_dir.join("setup.json"),
+        repository,
+        plan.setup.iter(),
+    )?;
+
+    let mut include = vec![];
+    for (index, chunk) in plan.chunks.iter().enumerate() {
+        let path = output_dir.join(format!("chunk-{}.json", index + 1));
+        write_actions(&path, repository, chunk.values().flatten())?;
+        include.push(json!({
+            "chunk": index + 1,
+            "actions": path.display().to_string(),
+            "issues": chunk.keys().collect::<Vec<_>>(),
+        }));
+    }
+    println!("{}", json!({ "include": include }));
+
+    eprintln!(
+        "{} setup action(s) and {} chunk(s) written to `{}`.",
+        plan.setup.len(),
+        plan.chunks.len(),
+        output_dir.display()
+    );
+    if !plan.remaining.is_empty() {
+        eprintln!(
+            "{} action(s) create issues or edit goal documents; run `cargo rpg issues` \
+             once the chunks are done to execute them:",
+            plan.remaining.len()
+        );
+        for action in &plan.remaining {
+            eprintln!("* {action}");
+        }
+    }
+
+    Ok(())
+}
+
+/// Execute (or, without `--commit`, print) the actions in a file written by [`export_matrix`].
+pub(crate) fn execute_actions_file(
+    github: &dyn GithubBackend,
+    repository: &Repository,
+    path: &Path,
+    options: &IssuesOptions,
+) -> anyhow::Result<()> {
+    let text =
+        std::fs::read_to_string(path).with_context(|| format!("reading `{}`", path.display()))?;
+    let file: ActionsFile =
+        serde_json::from_str(&text).with_context(|| format!("parsing `{}`", path.display()))?;
+    if file.repository != repository.to_string() {
+        anyhow::bail!(
+            "`{}` has actions for `{}`, not `{repository}`",
+            path.display(),
+            file.repository
+        );
+    }
+
+    let actions = file
+        .actions
+        .into_iter()
+        .map(GithubAction::from_action)
+        .collect::<anyhow::Result<BTreeSet<_>>>()
+        .with_context(|| format!("reading the actions in `{}`", path.display()))?;
+    if actions.is_empty() {
+        eprintln!("No actions in `{}`, nothing to do.", path.display());
+        return Ok(());
+    }
+
+    if options.commit {
+        rfc::check_expected_repository(repository, options)?;
+        rfc::execute_actions(github, repository, actions, options)?;
+    } else {
+        rfc::print_actions(&actions, options)?;
+    }
+
+    Ok(())
+}
+
+fn write_actions<'a>(
+    path: &Path,
+    repository: &Repository,
+    actions: impl Iterator<Item = &'a Action>,
+) -> anyhow::Result<()> {
+    let file = ActionsFile {
+        repository: repository.to_string(),
+        actions: actions.cloned().collect(),
+    };
+    std::fs::write(path, serde_json::to_string_pretty(&file)?)
+        .with_context(|| format!("writing `{}`", path.display()))
+}
+
+/// Split `actions` into the setup, at most `jobs` chunks of issues, and the remaining actions.
+/// Issues are spread so that the chunks have about as many actions each, the issues with the
+/// most actions first; the actions of each issue keep their order.
+fn split_actions(actions: Vec<Action>, jobs: usize) -> MatrixPlan {
+    let mut setup = vec![];
+    let mut remaining = vec![];
+    let mut by_issue: BTreeMap<u64, Vec<Action>> = BTreeMap::new();
+    for action in actions {
+        match (&action, action.issue_number()) {
+            (
+                Action::CreateMilestone { .. }
+                | Action::SetMilestoneDue { .. }
+                | Action::CreateLabel { .. }
+                | Action::UpdateLabelColor { .. },
+                _,
+            ) => setup.push(action),
+            (Action::CreateIssue { .. }, _) | (_, None) => remaining.push(action),
+            _ if action.goal_document().is_some() => remaining.push(action),
+            (_, Some(number)) => by_issue.entry(number).or_default().push(action),
+        }
+    }
+
+    let mut issues: Vec<_> = by_issue.into_iter().collect();
+    issues.sort_by_key(|(number, actions)| (std::cmp::Reverse(actions.len()), *number));
+
+    let mut chunks: Vec<BTreeMap<u64, Vec<Action>>> = vec![];
+    for (number, actions) in issues {
+        if chunks.len() < jobs.max(1) {
+            chunks.push(BTreeMap::from([(number, actions)]));
+            continue;
+        }
+        let smallest = chunks
+            .iter_mut()
+            .min_by_key(|chunk| chunk.values().map(Vec::len).sum::<usize>())
+            .unwrap();
+        smallest.insert(number, actions);
+    }
+
+    MatrixPlan {
+        setup,
+        chunks,
+        remaining,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_project_goals::gh::labels::GhLabel;
+
+    use super::*;
+
+    #[test]
+    fn issues_are_spread_over_the_chunks() {
+        let lock = |number| Action::LockIssue { number };
+        let comment = |number| Action::Comment {
+            number,
+            body: "Hello".to_string(),
+        };
+        let actions = vec![
+            Action::SetMilestoneDue {
+                title: "2025h1".to_string(),
+                due_on: "2025-06-30".to_string(),
+                previous: None,
+            },
+            Action::CreateLabel {
+                label: GhLabel {
+                    name: "T-lang".to_string(),
+                    color: "f5f1fd".to_string(),
+                },
+            },
+            comment(1),
+            lock(1),
+            comment(2),
+            comment(3),
+            lock(4),
+        ];
+
+        let plan = split_actions(actions, 2);
+        assert_eq!(plan.setup.len(), 2);
+        assert!(plan.remaining.is_empty());
+        let issues: Vec<Vec<u64>> = plan
+            .chunks
+            .iter()
+            .map(|chunk| chunk.keys().copied().collect())
+            .collect();
+        assert_eq!(issues, [vec![1, 4], vec![2, 3]]);
+        assert_eq!(plan.chunks[0][&1], [comment(1), lock(1)]);
+    }
+}
@@ -1,15 +1,42 @@
 use anyhow::Context;
-use clap::Parser;
+use clap::{CommandFactory, FromArgMatches};
 use regex::Regex;
-use rust_project_goals::gh::issue_id::Repository;
+use rust_project_goals::action::PeriodBy;
+use rust_project_goals::gh::{backend::GhCli, issue_id::Repository};
+use rust_project_goals::sync;
+use rust_project_goals::util::Warnings;
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::IsTerminal;
 use std::path::PathBuf;
 use walkdir::WalkDir;
 
+mod asks_csv;
+mod badge_json;
 mod cfp;
+mod check_file;
+mod compare;
+mod dedup;
+mod defaults;
+mod effort;
+mod error;
+mod export_matrix;
 mod generate_json;
+mod migrate_milestone;
+mod milestone_due;
+mod mine;
+mod owners_export;
+mod preflight;
+mod reassign;
 mod rfc;
+mod self_test;
+mod sync_labels;
 mod team_repo;
+mod teams;
+#[cfg(test)]
+mod test_support;
 mod updates;
+mod validate_links;
+mod watch;
 
 #[derive(clap::Parser, Debug)]
 #[structopt(about = "Project goal preprocessor")]
@@ -17,19 +44,102 @@ struct Opt {
     #[command(subcommand)]
     cmd: Command,
 
-    /// Repository to use if applicable
-    #[arg(long, default_value = "rust-lang/rust-project-goals")]
-    repository: Repository,
+    /// Repository to use if applicable (defaults to the repository of the `origin` git remote)
+    #[arg(long)]
+    repository: Option<Repository>,
+
+    /// On failure, print a JSON object to stderr (with the error kind, the failing action
+    /// and issue number if any, and the message) instead of plain text.
+    #[arg(long, global = true)]
+    json_errors: bool,
+
+    /// Treat markdown files in goal directories that are not goal documents as errors,
+    /// rather than skipping them (set `RUST_LOG=debug` to see which files are skipped).
+    #[arg(long, global = true)]
+    strict: bool,
+
+    /// Configuration file whose `[cli]` table gives defaults for command-line options
+    /// (defaults to `rust-project-goals.toml` in the current directory, if it exists).
+    #[arg(long, global = true)]
+    config: Option<PathBuf>,
+
+    /// Log every `gh` command that is run, with its exit status (and its output if it failed),
+    /// and why each action on a tracking issue was planned. `RUST_LOG` can be used for finer control.
+    #[arg(short, long, global = true)]
+    verbose: bool,
+}
+
+impl Opt {
+    /// The repository given with `--repository`, or else the one of the `origin` git remote.
+    fn repository(&self) -> anyhow::Result<Repository> {
+        match &self.repository {
+            Some(repository) => Ok(repository.clone()),
+            None => Repository::from_git_origin(),
+        }
+    }
 }
 
 #[derive(clap::Subcommand, Debug)]
-#[allow(dead_code)]
 enum Command {
     /// Print the comment required to initiate FCP
-    FCP { path: PathBuf },
+    FCP {
+        path: PathBuf,
 
-    /// Print the RFC text to stdout
-    RFC { path: PathBuf },
+        /// Timeframe of the goals (e.g., `2024h2`), if it can't be inferred from the path.
+        #[arg(long)]
+        timeframe: Option<String>,
+
+        /// Post the comment on this issue (e.g., the RFC thread) instead of printing it.
+        /// If an earlier run posted it there, that comment is updated instead.
+        #[arg(long)]
+        post_to: Option<u64>,
+
+        /// Check the box of (and mark with ✅) every team member who reacted with a
+        /// thumbs-up to the checklist already posted with `--post-to`.
+        #[arg(long, requires = "post_to")]
+        acknowledge_reactions: bool,
+
+        /// Like `--acknowledge-reactions`, but read the reactions from the checklist posted
+        /// on this issue, without posting anything.
+        #[arg(long, conflicts_with = "acknowledge_reactions")]
+        reactions_from: Option<u64>,
+
+        /// Name leads as `` `@username` `` rather than `@username` in the posted comment too,
+        /// so that updating it does not notify them. Printed comments never mention anyone.
+        #[arg(long)]
+        no_mentions: bool,
+    },
+
+    /// Print the RFC text to stdout (or to a file with `--output`)
+    RFC {
+        path: PathBuf,
+
+        /// Write the RFC text to the given file instead of stdout.
+        #[arg(long)]
+        output: Option<PathBuf>,
+
+        /// Additionally write one file per section into a directory named after
+        /// the output file (e.g., `rfc.md` is split into `rfc/*.md`).
+        #[arg(long, requires = "output")]
+        split: bool,
+
+        /// The `mdbook` executable used to build the book.
+        #[arg(long, default_value = "mdbook")]
+        mdbook_binary: PathBuf,
+
+        /// Do not run `mdbook build`; reuse the existing output in `book/markdown`
+        /// (which must be newer than the sources).
+        #[arg(long)]
+        no_build: bool,
+
+        /// Timeframe of the goals (e.g., `2024h2`), if it can't be inferred from the path.
+        #[arg(long)]
+        timeframe: Option<String>,
+
+        /// Start with a table of contents linking to each heading of the RFC.
+        #[arg(long)]
+        toc: bool,
+    },
 
     /// Set up a new Call For Proposals (CFP) period
     CFP {
@@ -47,16 +157,202 @@ enum Command {
 
     /// Use `gh` CLI tool to create issues on the rust-lang/rust-project-goals repository
     Issues {
+        #[command(flatten)]
+        args: Box<IssuesArgs>,
+    },
+
+    /// Check that `gh` is installed and authenticated with write access to the repository,
+    /// and that the milestones for the given timeframe folders exist.
+    Preflight {
+        /// One or more timeframe folders (e.g., `src/2025h1`).
+        #[arg(required = true)]
+        paths: Vec<PathBuf>,
+
+        /// Timeframe of the goals (e.g., `2024h2`), if it can't be inferred from the path.
+        /// Only allowed with a single path.
+        #[arg(long)]
+        timeframe: Option<String>,
+    },
+
+    /// Close the tracking issues of a finished milestone, labeling them as completed
+    /// and leaving a closing comment.
+    Archive {
+        /// Milestone to archive (e.g., `2024h2`).
+        milestone: String,
+
+        /// Only archive goals whose tasks are all complete (according to `src/<milestone>`).
+        #[arg(long)]
+        only_complete: bool,
+
+        #[command(flatten)]
+        label_options: sync::LabelOptions,
+
+        #[command(flatten)]
+        options: rfc::IssuesOptions,
+    },
+
+    /// Regenerate the body of the tracking issue of every goal in `src/<milestone>` (e.g., after
+    /// changing the body template), without changing anything else about the issues.
+    RegenerateAllBodies {
+        /// Milestone of the goals (e.g., `2025h1`).
+        milestone: String,
+
+        /// Handlebars template for the body of the tracking issues
+        /// (defaults to `templates/issue_body.hbs`).
+        #[arg(long)]
+        body_template: Option<PathBuf>,
+
+        /// Maximum number of assignees per tracking issue, as given to `issues`
+        /// (owners beyond it are named in the body).
+        #[arg(long, default_value_t = sync::MAX_ASSIGNEES, value_parser = sync::parse_assignee_limit)]
+        assignee_limit: usize,
+
+        /// How the goal period of a tracking issue is recorded, as given to `issues`.
+        #[arg(long, value_enum, default_value_t = PeriodBy::Milestone)]
+        period_by: PeriodBy,
+
+        #[command(flatten)]
+        options: rfc::IssuesOptions,
+    },
+
+    /// Plan the actions of `issues` and split them into chunks of issues for parallel
+    /// GitHub Actions jobs, printing the matrix of chunks (see `execute-actions`).
+    ExportMatrix {
+        /// One or more timeframe folders (e.g., `src/2025h1`) whose goals should be synced.
+        #[arg(required = true)]
+        paths: Vec<PathBuf>,
+
+        /// Timeframe of the goals (e.g., `2024h2`), if it can't be inferred from the path.
+        /// Only allowed with a single path.
+        #[arg(long)]
+        timeframe: Option<String>,
+
+        #[command(flatten)]
+        label_options: sync::LabelOptions,
+
+        /// Never lock tracking issues, as given to `issues`.
+        #[arg(long)]
+        no_lock: bool,
+
+        /// Maximum number of assignees per tracking issue, as given to `issues`.
+        #[arg(long, default_value_t = sync::MAX_ASSIGNEES, value_parser = sync::parse_assignee_limit)]
+        assignee_limit: usize,
+
+        /// How the goal period of a tracking issue is recorded, as given to `issues`.
+        #[arg(long, value_enum, default_value_t = PeriodBy::Milestone)]
+        period_by: PeriodBy,
+
+        #[command(flatten)]
+        matrix_options: export_matrix::MatrixOptions,
+    },
+
+    /// Execute the actions in a file written by `export-matrix` (`setup.json` or a chunk).
+    ExecuteActions {
+        /// The file of actions.
+        file: PathBuf,
+
+        #[command(flatten)]
+        options: rfc::IssuesOptions,
+    },
+
+    /// Create the labels used by tracking issues (including the `T-<team>` labels of the teams
+    /// with asks in `paths`) in several repositories, fixing the color of existing ones.
+    SyncLabels {
+        /// One or more timeframe folders (e.g., `src/2025h1`) whose teams need labels.
+        #[arg(required = true)]
+        paths: Vec<PathBuf>,
+
+        /// Repositories to sync (e.g., `rust-lang/rust-project-goals,rust-lang/compiler-team`).
+        #[arg(long, value_delimiter = ',', required = true)]
+        repos: Vec<Repository>,
+
+        #[command(flatten)]
+        label_options: sync::LabelOptions,
+
+        #[command(flatten)]
+        options: rfc::IssuesOptions,
+    },
+
+    /// Close duplicate tracking issues (with the same title) in a milestone, keeping the one
+    /// referenced by the goal document (or else the oldest).
+    Dedup {
+        /// Milestone to check (e.g., `2024h2`).
+        milestone: String,
+
+        #[command(flatten)]
+        label_options: sync::LabelOptions,
+
+        #[command(flatten)]
+        options: rfc::IssuesOptions,
+    },
+
+    /// Move the open tracking issues of a milestone to another (e.g., when renaming `2024h2` to
+    /// `2024-h2`), posting the continuation comment on each. The new milestone is created if needed.
+    MigrateMilestone {
+        /// Milestone to move the issues out of (e.g., `2024h2`).
+        #[arg(long)]
+        from: String,
+
+        /// Milestone to move the issues into (e.g., `2024-h2`).
+        #[arg(long)]
+        to: String,
+
+        /// Also move the closed tracking issues (by default they stay in `--from`).
+        #[arg(long)]
+        include_closed: bool,
+
+        #[command(flatten)]
+        label_options: sync::LabelOptions,
+
+        #[command(flatten)]
+        options: rfc::IssuesOptions,
+    },
+
+    /// Check that the github credentials allow syncing issues on a repository, by creating a
+    /// labeled `[rpg-selftest]` issue, commenting on it, locking it, and closing it.
+    SelfTest {
+        /// Repository to check (e.g., `rust-lang/rust-project-goals`).
+        #[arg(long)]
+        repository: Repository,
+
+        /// Also delete the issue once closed (this requires admin rights on the repository).
+        #[arg(long)]
+        delete: bool,
+    },
+
+    /// Set the due date of a milestone, creating the milestone if it does not exist.
+    /// Only prints the planned changes unless `--commit` is given.
+    SetMilestoneDue {
+        /// Milestone to update (e.g., `2025h1`).
+        milestone: String,
+
+        /// New due date, as `YYYY-MM-DD`.
+        #[arg(value_parser = parse_date)]
+        date: chrono::NaiveDate,
+
+        #[command(flatten)]
+        options: rfc::IssuesOptions,
+    },
+
+    /// Move a goal from one owner to another, updating both the goal document
+    /// and the assignees of its tracking issue.
+    Reassign {
+        /// Path to the directory containing the goal (e.g., `src/2024h2`)
         path: PathBuf,
 
-        /// Number of milliseconds to pause between github commands
-        /// to avoid rate limiting
-        #[arg(long, default_value = "500")]
-        sleep: u64,
+        /// Name of the goal document without the `.md` extension (e.g., `async`)
+        goal_slug: String,
 
-        /// Without this option, no action is taken.
+        /// Current owner to remove (e.g., `@ghost`)
         #[arg(long)]
-        commit: bool,
+        from: String,
+
+        /// New owner to add (e.g., `@octocat`)
+        #[arg(long)]
+        to: String,
+
+        #[command(flatten)]
+        options: rfc::IssuesOptions,
     },
 
     /// Generate the project-goal-owners team based on the owners found in `paths`.
@@ -71,7 +367,44 @@ enum Command {
     },
 
     /// Checks that the goal documents are well-formed, intended for use within CI
-    Check {},
+    Check {
+        /// Also fail if an owner of a goal is not a member of any Rust team.
+        #[arg(long)]
+        require_team_members: bool,
+
+        /// Fail if any warning was reported (e.g., a goal without tasks), not only on errors.
+        #[arg(long)]
+        fail_on_warning: bool,
+    },
+
+    /// Check a single goal document (e.g., when saving it in an editor) and print
+    /// the body of its tracking issue.
+    CheckFile {
+        /// The goal document (e.g., `src/2025h1/async.md`).
+        #[arg(required_unless_present = "stdin")]
+        path: Option<PathBuf>,
+
+        /// Read the goal document from stdin instead.
+        #[arg(long, conflicts_with = "path")]
+        stdin: bool,
+
+        /// Timeframe of the goal (e.g., `2024h2`), if it has no `Timeframe` row
+        /// and can't be inferred from the path.
+        #[arg(long)]
+        timeframe: Option<String>,
+    },
+
+    /// Check that relative links to other `.md` files in the goal documents point at files that exist.
+    ValidateLinks {
+        /// Directories containing the goals (e.g., `src/2025h1`)
+        #[arg(required = true)]
+        paths: Vec<PathBuf>,
+
+        /// Check the links against the book built by `mdbook build` (in `book/html`) instead:
+        /// `.md` links, and `.html` links into the book, must point at a generated page.
+        #[arg(long)]
+        published: bool,
+    },
 
     /// Generate json file with status from tracking issues.
     /// This is intended for storing alongside the book for consumption by external tools.
@@ -85,6 +418,76 @@ enum Command {
         json_path: Option<PathBuf>,
     },
 
+    /// Compare the goals of two timeframes, listing the goals that continue (with their
+    /// task completion in both), the new goals, and the dropped ones.
+    Compare {
+        /// Directory with the goals of the earlier timeframe (e.g., `src/2025h1`).
+        old: PathBuf,
+
+        /// Directory with the goals of the later timeframe (e.g., `src/2025h2`).
+        new: PathBuf,
+    },
+
+    /// List the goals owned by a user, with their tracking issue and how many tasks are complete.
+    Mine {
+        /// Directory containing the goals (e.g., `src/2025h1`).
+        path: PathBuf,
+
+        /// GitHub username (e.g., `@octocat`); defaults to the user `gh` is authenticated as.
+        #[arg(long)]
+        user: Option<String>,
+    },
+
+    /// Sum the effort annotations (like `(est: 2w)`) of the plan items of each goal,
+    /// and of the asks of each team.
+    Effort {
+        /// Directory containing the goals (e.g., `src/2025h1`).
+        path: PathBuf,
+    },
+
+    /// Generate a CODEOWNERS-like list mapping each goal document to its owners.
+    OwnersExport {
+        /// Directory containing the goals (e.g., `src/2025h1`).
+        path: PathBuf,
+
+        /// If specified, write the output into the given file.
+        #[arg(long)]
+        output_file: Option<PathBuf>,
+    },
+
+    /// List the teams that the goals in a folder ask for, with the goals asking each of them.
+    Teams {
+        /// Directory containing the goals (e.g., `src/2025h1`).
+        path: PathBuf,
+    },
+
+    /// Generate a CSV of the team asks (one row per goal and team) for spreadsheet-based planning.
+    AsksCsv {
+        /// Directory containing the goals (e.g., `src/2025h1`).
+        path: PathBuf,
+
+        /// If specified, write the output into the given file.
+        #[arg(long)]
+        output_file: Option<PathBuf>,
+    },
+
+    /// Generate shields.io endpoint JSON for a badge showing the task completion of a goal.
+    BadgeJson {
+        /// Directory containing the goal (e.g., `src/2025h1`).
+        path: PathBuf,
+
+        /// Name of the goal document without the `.md` extension (e.g., `async`).
+        goal_slug: String,
+
+        /// Text on the left side of the badge.
+        #[arg(long, default_value = "progress")]
+        label: String,
+
+        /// If specified, write the output into the given file.
+        #[arg(long)]
+        output_file: Option<PathBuf>,
+    },
+
     /// Generate markdown with the list of updates for each tracking issue.
     /// Collects goal updates.
     Updates {
@@ -109,12 +512,197 @@ enum Command {
     },
 }
 
+/// The arguments of `issues`, boxed in [`Command`] as there are so many of them.
+#[derive(clap::Args, Debug)]
+struct IssuesArgs {
+    /// One or more timeframe folders (e.g., `src/2025h1`) whose goals should be synced.
+    #[arg(required = true)]
+    paths: Vec<PathBuf>,
+
+    /// Timeframe of the goals (e.g., `2024h2`), if it can't be inferred from the path.
+    /// Only allowed with a single path.
+    #[arg(long)]
+    timeframe: Option<String>,
+
+    #[command(flatten)]
+    label_options: sync::LabelOptions,
+
+    /// Never lock tracking issues (goals can also opt out individually
+    /// with a `Lockable | false` metadata row).
+    #[arg(long)]
+    no_lock: bool,
+
+    /// Reopen the tracking issues of accepted goals that were closed.
+    /// Without this, we only warn about them.
+    #[arg(long)]
+    reopen_closed: bool,
+
+    /// Delete the older lock and continuation comments on each tracking issue, keeping
+    /// the most recent of each. Only comments by `--bot-account` are ever deleted.
+    #[arg(long, requires = "bot_account")]
+    prune_comments: bool,
+
+    /// The account (e.g., `@rust-goals-bot`) that posts our comments, see `--prune-comments`.
+    #[arg(long)]
+    bot_account: Option<String>,
+
+    /// Add each tracking issue to this project board (by number) of the repository's owner,
+    /// unless it is already on it.
+    #[arg(long)]
+    project: Option<u64>,
+
+    /// Value of the `Status` field for the issues added to `--project`.
+    #[arg(long, requires = "project")]
+    project_status: Option<String>,
+
+    /// Refuse to create or update issues if an owner of a goal is not a member
+    /// of any Rust team.
+    #[arg(long)]
+    require_team_members: bool,
+
+    /// Fail, listing every failure, if an owner cannot be looked up in the Rust team data
+    /// (e.g., because it could not be fetched). Without this, their username is assigned as is.
+    #[arg(long)]
+    owners_strict: bool,
+
+    /// Where owners are maintained: with `markdown`, the assignees of each tracking issue
+    /// are set to the owners of its goal; with `github`, the assignees of the issue are
+    /// written back to the `Additional owners` row of the goal instead.
+    #[arg(long, value_enum, default_value_t = sync::AssigneeSource::Markdown)]
+    assignee_source: sync::AssigneeSource,
+
+    /// Where titles are maintained: with `markdown`, the title of each tracking issue is
+    /// set to the title of its goal; with `github`, a title changed on the issue since the
+    /// last sync is written back to the goal document instead (conflicts are reported).
+    #[arg(long, value_enum, default_value_t = sync::TitleSource::Markdown)]
+    title_source: sync::TitleSource,
+
+    /// How assignees are synced with `--assignee-source markdown`: `reconcile` makes them
+    /// exactly the owners of the goal, while `additive` only assigns missing owners and
+    /// never unassigns anyone (e.g., volunteers who assigned themselves).
+    #[arg(long, value_enum, default_value_t = sync::AssigneeMode::Reconcile)]
+    assignees: sync::AssigneeMode,
+
+    /// Github username (e.g., `@steward`) to assign, and name as the point of contact,
+    /// on the tracking issues of goals that list no owners.
+    #[arg(long)]
+    default_owner: Option<String>,
+
+    /// Issue type (e.g., `Task`) to give every tracking issue, on repositories that use
+    /// issue types. New issues get it on the sync after they are created. Ignored (with a
+    /// warning) if the repository has no issue types.
+    #[arg(long)]
+    issue_type: Option<String>,
+
+    /// File listing actions to never execute, one per line, as the kind of action and the
+    /// issue it applies to (e.g., `ChangeTitle #123`). Lines starting with `#` are comments.
+    #[arg(long)]
+    ignore_file: Option<PathBuf>,
+
+    /// When the tasks of a goal make its tracking issue body longer than github allows,
+    /// list them in a comment on the issue (pointed to from the body) instead of failing.
+    #[arg(long)]
+    truncate_body: bool,
+
+    /// Plan again (without ever committing) whenever a goal document in `paths` changes,
+    /// for quick feedback while editing.
+    #[arg(long, conflicts_with = "commit")]
+    watch: bool,
+
+    /// When no existing issue has the exact title of a goal, look for issues with a close
+    /// title. On a terminal, we ask whether to use one of them as the tracking issue;
+    /// otherwise, we only list them.
+    #[arg(long)]
+    fuzzy_match: bool,
+
+    /// Handlebars template for the body of the tracking issues
+    /// (defaults to `templates/issue_body.hbs`).
+    #[arg(long)]
+    body_template: Option<PathBuf>,
+
+    /// Directory with Handlebars templates for the comment posted when a goal continues
+    /// into a new milestone (`continuing_goal.hbs`) and when a tracking issue is locked
+    /// (`lock_issue.hbs`). Missing ones default to those in `templates/`.
+    #[arg(long)]
+    comment_template: Option<PathBuf>,
+
+    /// Maximum number of assignees per tracking issue. Further owners (additional owners
+    /// are dropped first) are named in the issue body instead.
+    #[arg(long, default_value_t = sync::MAX_ASSIGNEES, value_parser = sync::parse_assignee_limit)]
+    assignee_limit: usize,
+
+    /// How the goal period of a tracking issue is recorded: as its github `milestone`,
+    /// or as a `label` named after the period (e.g., `2025h1`).
+    #[arg(long, value_enum, default_value_t = PeriodBy::Milestone)]
+    period_by: PeriodBy,
+
+    /// Regenerate the body of every existing tracking issue, even if it looks up to date.
+    /// Text that was not generated by us is preserved at the bottom of the issue.
+    #[arg(long)]
+    force_update_body: bool,
+
+    #[command(flatten)]
+    options: rfc::IssuesOptions,
+}
+
+fn parse_date(s: &str) -> Result<chrono::NaiveDate, String> {
+    chrono::NaiveDate::parse_from_str(s, "%Y-%m-%d")
+        .map_err(|e| format!("expected a date like `2025-06-30` ({e})"))
+}
+
 fn main() -> anyhow::Result<()> {
-    let opt: Opt = Opt::parse();
+    let args: Vec<_> = std::env::args_os().collect();
+    let command = defaults::apply_config_defaults(Opt::command(), &args)?;
+    let opt = Opt::from_arg_matches(&command.get_matches_from(&args)).unwrap_or_else(|e| e.exit());
+    let mut logger = env_logger::Builder::from_default_env();
+    if opt.verbose {
+        logger.filter_module("rust_project_goals", log::LevelFilter::Debug);
+    }
+    logger.init();
+
+    error::set_json_errors(opt.json_errors);
+
+    let result = run(&opt);
+    if let Err(e) = &result {
+        if opt.json_errors {
+            error::JsonError::from_anyhow(e).print();
+            std::process::exit(1);
+        }
+    }
+    result
+}
 
+fn run(opt: &Opt) -> anyhow::Result<()> {
     match &opt.cmd {
-        Command::FCP { path } => {
-            rfc::generate_comment(&path)?;
+        Command::FCP {
+            path,
+            timeframe,
+            post_to,
+            acknowledge_reactions,
+            reactions_from,
+            no_mentions,
+        } => {
+            let reactions_from = match post_to {
+                Some(number) if *acknowledge_reactions => Some(*number),
+                _ => *reactions_from,
+            };
+            let acknowledged = match reactions_from {
+                Some(number) => rfc::fcp_acknowledgements(&GhCli, &opt.repository()?, number)?,
+                None => BTreeSet::new(),
+            };
+            let comment = rfc::generate_comment(
+                &path,
+                timeframe.as_deref(),
+                &acknowledged,
+                post_to.is_some() && !no_mentions,
+                opt.strict,
+            )?;
+            match post_to {
+                Some(number) => {
+                    rfc::post_fcp_comment(&GhCli, &opt.repository()?, *number, &comment)?
+                }
+                None => print!("{comment}"),
+            }
         }
 
         Command::CFP {
@@ -125,36 +713,340 @@ fn main() -> anyhow::Result<()> {
             cfp::create_cfp(timeframe, *force, *dry_run)?;
         }
 
-        Command::Check {} => {
-            check()?;
+        Command::Check {
+            require_team_members,
+            fail_on_warning,
+        } => {
+            check(
+                &opt.repository()?,
+                *require_team_members,
+                *fail_on_warning,
+                opt.strict,
+            )?;
+        }
+
+        Command::CheckFile {
+            path,
+            stdin: _,
+            timeframe,
+        } => {
+            check_file::check_file(path.as_deref(), timeframe.as_deref(), &opt.repository()?)?;
         }
 
-        Command::RFC { path } => {
-            rfc::generate_rfc(&path)?;
+        Command::ValidateLinks { paths, published } => {
+            validate_links::validate_links(paths, *published)?;
         }
 
-        Command::Issues {
+        Command::RFC {
             path,
-            commit,
-            sleep,
+            output,
+            split,
+            mdbook_binary,
+            no_build,
+            timeframe,
+            toc,
         } => {
-            rfc::generate_issues(&opt.repository, path, *commit, *sleep)
-                .with_context(|| format!("failed to adjust issues; rerun command to resume"))?;
+            rfc::generate_rfc(
+                &path,
+                output.as_deref(),
+                *split,
+                (!no_build).then_some(mdbook_binary.as_path()),
+                timeframe.as_deref(),
+                *toc,
+                opt.strict,
+            )?;
+        }
+
+        Command::Issues { args } => {
+            let IssuesArgs {
+                paths,
+                timeframe,
+                label_options,
+                no_lock,
+                reopen_closed,
+                prune_comments,
+                bot_account,
+                project,
+                project_status,
+                require_team_members,
+                owners_strict,
+                assignee_source,
+                title_source,
+                assignees,
+                default_owner,
+                issue_type,
+                ignore_file,
+                truncate_body,
+                watch,
+                fuzzy_match,
+                body_template,
+                comment_template,
+                force_update_body,
+                assignee_limit,
+                period_by,
+                options,
+            } = &**args;
+            if let Some(body_template) = body_template {
+                sync::set_body_template(body_template)?;
+            }
+            if let Some(comment_template) = comment_template {
+                sync::set_comment_templates(comment_template)?;
+            }
+            let repository = opt.repository()?;
+            let ignored = ignore_file
+                .as_deref()
+                .map(sync::IgnoredActions::load)
+                .transpose()?;
+            let sync_options = sync::SyncOptions {
+                lock: !no_lock,
+                force_update_body: *force_update_body,
+                assignee_limit: *assignee_limit,
+                period_by: *period_by,
+                reopen_closed: *reopen_closed,
+                prune_comments_by: bot_account.as_deref().filter(|_| *prune_comments),
+                project: project.map(|number| sync::Project {
+                    number,
+                    status: project_status.as_deref(),
+                }),
+                fuzzy_match: fuzzy_match.then(|| {
+                    if std::io::stdin().is_terminal() {
+                        sync::FuzzyMatch::Ask
+                    } else {
+                        sync::FuzzyMatch::Report
+                    }
+                }),
+                require_team_members: *require_team_members,
+                owners_strict: *owners_strict,
+                assignee_source: *assignee_source,
+                title_source: *title_source,
+                assignees: *assignees,
+                default_owner: default_owner
+                    .as_deref()
+                    .map(|owner| owner.trim_start_matches('@')),
+                issue_type: issue_type.as_deref(),
+                ignored: ignored.as_ref(),
+                truncate_body: *truncate_body,
+                strict: opt.strict,
+            };
+            let generate_issues = || {
+                rfc::generate_issues(
+                    &GhCli,
+                    &repository,
+                    paths,
+                    timeframe.as_deref(),
+                    label_options,
+                    sync_options,
+                    options,
+                )
+            };
+            if *watch {
+                watch::watch(paths, generate_issues)?;
+            } else {
+                generate_issues()
+                    .with_context(|| format!("failed to adjust issues; rerun command to resume"))?;
+            }
+        }
+
+        Command::Preflight { paths, timeframe } => {
+            preflight::preflight(&opt.repository()?, paths, timeframe.as_deref())?;
+        }
+
+        Command::RegenerateAllBodies {
+            milestone,
+            body_template,
+            assignee_limit,
+            period_by,
+            options,
+        } => {
+            if let Some(body_template) = body_template {
+                sync::set_body_template(body_template)?;
+            }
+            rfc::regenerate_all_bodies(
+                &GhCli,
+                &opt.repository()?,
+                milestone,
+                *period_by,
+                *assignee_limit,
+                options,
+                opt.strict,
+            )?;
+        }
+
+        Command::Archive {
+            milestone,
+            only_complete,
+            label_options,
+            options,
+        } => {
+            rfc::archive_milestone(
+                &GhCli,
+                &opt.repository()?,
+                milestone,
+                *only_complete,
+                label_options,
+                options,
+                opt.strict,
+            )?;
+        }
+
+        Command::ExportMatrix {
+            paths,
+            timeframe,
+            label_options,
+            no_lock,
+            assignee_limit,
+            period_by,
+            matrix_options,
+        } => {
+            if timeframe.is_some() && paths.len() > 1 {
+                anyhow::bail!("`--timeframe` can only be used with a single path");
+            }
+            let sync_options = sync::SyncOptions {
+                lock: !no_lock,
+                assignee_limit: *assignee_limit,
+                period_by: *period_by,
+                strict: opt.strict,
+                ..Default::default()
+            };
+            export_matrix::export_matrix(
+                &GhCli,
+                &opt.repository()?,
+                paths,
+                timeframe.as_deref(),
+                label_options,
+                sync_options,
+                matrix_options,
+            )?;
+        }
+
+        Command::ExecuteActions { file, options } => {
+            export_matrix::execute_actions_file(&GhCli, &opt.repository()?, file, options)?;
+        }
+
+        Command::SyncLabels {
+            paths,
+            repos,
+            label_options,
+            options,
+        } => {
+            sync_labels::sync_labels(&GhCli, paths, repos, label_options, options, opt.strict)?;
+        }
+
+        Command::Dedup {
+            milestone,
+            label_options,
+            options,
+        } => {
+            dedup::dedup_milestone(
+                &GhCli,
+                &opt.repository()?,
+                milestone,
+                label_options,
+                options,
+                opt.strict,
+            )?;
+        }
+
+        Command::MigrateMilestone {
+            from,
+            to,
+            include_closed,
+            label_options,
+            options,
+        } => {
+            migrate_milestone::migrate_milestone(
+                &GhCli,
+                &opt.repository()?,
+                from,
+                to,
+                label_options,
+                *include_closed,
+                options,
+            )?;
+        }
+
+        Command::SelfTest { repository, delete } => {
+            self_test::self_test(&GhCli, repository, *delete)?;
+        }
+
+        Command::SetMilestoneDue {
+            milestone,
+            date,
+            options,
+        } => {
+            milestone_due::set_milestone_due(
+                &GhCli,
+                &opt.repository()?,
+                milestone,
+                *date,
+                options,
+            )?;
+        }
+
+        Command::Reassign {
+            path,
+            goal_slug,
+            from,
+            to,
+            options,
+        } => {
+            let goal_document = reassign::find_goal(path, goal_slug, opt.strict)?;
+            reassign::reassign(
+                &GhCli,
+                &opt.repository()?,
+                &goal_document,
+                from,
+                to,
+                options,
+            )?;
         }
 
         Command::TeamRepo {
             path,
             team_repo_path,
         } => {
-            team_repo::generate_team_repo(&path, team_repo_path)?;
+            team_repo::generate_team_repo(&path, team_repo_path, opt.strict)?;
         }
 
         Command::Json {
             milestone,
             json_path,
         } => {
-            generate_json::generate_json(&opt.repository, &milestone, json_path)?;
+            generate_json::generate_json(&opt.repository()?, &milestone, json_path)?;
+        }
+        Command::Compare { old, new } => {
+            compare::compare(old, new, opt.strict)?;
+        }
+
+        Command::Mine { path, user } => {
+            mine::mine(path, user.as_deref(), opt.strict)?;
+        }
+
+        Command::Effort { path } => {
+            effort::effort(path, opt.strict)?;
+        }
+
+        Command::OwnersExport { path, output_file } => {
+            owners_export::owners_export(path, output_file, opt.strict)?;
+        }
+
+        Command::Teams { path } => {
+            teams::teams(path, opt.strict)?;
+        }
+
+        Command::AsksCsv { path, output_file } => {
+            asks_csv::asks_csv(path, output_file, opt.strict)?;
+        }
+
+        Command::BadgeJson {
+            path,
+            goal_slug,
+            label,
+            output_file,
+        } => {
+            badge_json::badge_json(path, goal_slug, label, output_file, opt.strict)?;
         }
+
         Command::Updates {
             milestone,
             vscode,
@@ -162,7 +1054,7 @@ fn main() -> anyhow::Result<()> {
             start_date,
             end_date,
         } => updates::generate_updates(
-            &opt.repository,
+            &opt.repository()?,
             milestone,
             output_file.as_deref(),
             start_date,
@@ -174,10 +1066,18 @@ fn main() -> anyhow::Result<()> {
     Ok(())
 }
 
-fn check() -> anyhow::Result<()> {
+fn check(
+    repository: &Repository,
+    require_team_members: bool,
+    fail_on_warning: bool,
+    strict: bool,
+) -> anyhow::Result<()> {
     // Look for all directories like `2024h2` or `2025h1` and load goals from those directories.
     let regex = Regex::new(r"\d\d\d\dh[12]")?;
 
+    // Keep going after an error, so that a single run reports every problem.
+    let mut errors: BTreeMap<&str, usize> = BTreeMap::new();
+    let mut warnings = Warnings::default();
     for entry in WalkDir::new("src") {
         let entry = entry?;
 
@@ -193,8 +1093,66 @@ fn check() -> anyhow::Result<()> {
             continue;
         }
 
-        let _goals = rust_project_goals::goal::goals_in_dir(entry.path())?;
+        // Validate the metadata first so that we can report every problem at once,
+        // rather than failing on the first malformed document.
+        let problems = rust_project_goals::goal::validate_metadata_in_dir(
+            entry.path(),
+            Some(repository),
+            strict,
+        )?;
+        for problem in &problems {
+            eprintln!("{problem}");
+        }
+        if !problems.is_empty() {
+            *errors.entry("metadata").or_default() += problems.len();
+            continue;
+        }
+
+        let goals = match rust_project_goals::goal::goals_in_dir(entry.path(), strict) {
+            Ok(goals) => goals,
+            Err(e) => {
+                eprintln!("error: {e:#}");
+                *errors.entry("goal document").or_default() += 1;
+                continue;
+            }
+        };
+        for goal in &goals {
+            rfc::warn_if_lacking_tasks(goal, &mut warnings);
+            rfc::warn_if_foreign_tracking_issue(goal, &mut warnings);
+        }
+        if require_team_members {
+            if let Err(e) = rfc::check_team_members(&goals) {
+                eprintln!("error: {e:#}");
+                *errors.entry("team membership").or_default() += 1;
+            }
+        }
+    }
+
+    let warnings = warnings.counts();
+    let error_count: usize = errors.values().sum();
+    let warning_count: usize = warnings.values().sum();
+    eprintln!();
+    eprintln!("{error_count} error(s), {warning_count} warning(s)");
+    for (category, count) in &errors {
+        eprintln!("  error: {category}: {count}");
+    }
+    for (category, count) in warnings {
+        eprintln!("  warning: {category}: {count}");
     }
 
+    if error_count > 0 {
+        return Err(error::ClassifiedError::new(
+            error::ErrorKind::Validation,
+            format!("found {error_count} error(s)"),
+        )
+        .into());
+    }
+    if fail_on_warning && warning_count > 0 {
+        return Err(error::ClassifiedError::new(
+            error::ErrorKind::Validation,
+            format!("found {warning_count} warning(s) with `--fail-on-warning`"),
+        )
+        .into());
+    }
     Ok(())
 }
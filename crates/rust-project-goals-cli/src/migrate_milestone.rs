@@ -0,0 +1,208 @@
+use std::collections::BTreeSet;
+
+use rust_project_goals::{
+    action::PeriodBy,
+    gh::{backend::GithubBackend, issue_id::Repository},
+    sync::{CommentTemplates, GithubAction, GoalComment, LabelOptions},
+    util::commas,
+};
+use rust_project_goals_json::GithubIssueState;
+
+use crate::rfc::{self, IssuesOptions};
+
+/// Move every tracking issue in the milestone `from` to the milestone `to` (e.g., when
+/// renaming `2024h2` to `2024-h2`), posting the continuation comment on each, and creating
+/// `to` if it does not exist yet. Closed issues stay in `from` unless `include_closed` is set.
+pub(crate) fn migrate_milestone(
+    github: &dyn GithubBackend,
+    repository: &Repository,
+    from: &str,
+    to: &str,
+    label_options: &LabelOptions,
+    include_closed: bool,
+    options: &IssuesOptions,
+) -> anyhow::Result<()> {
+    if from == to {
+        anyhow::bail!("the milestones to migrate from and to are both `{from}`");
+    }
+
+    let tracking_issue_label = label_options.tracking_issue_label();
+    let actions = plan_migration(
+        github,
+        repository,
+        from,
+        to,
+        &tracking_issue_label,
+        include_closed,
+    )?;
+    if actions.is_empty() {
+        eprintln!("No tracking issues to move in milestone `{from}`, nothing to do.");
+        return Ok(());
+    }
+
+    if options.commit {
+        rfc::check_expected_repository(repository, options)?;
+        rfc::execute_actions(github, repository, actions, options)?;
+    } else {
+        rfc::print_actions(&actions, options)?;
+    }
+
+    Ok(())
+}
+
+/// The actions moving the issues labeled `tracking_issue_label` from `from` to `to`: other
+/// issues in the milestone are not ours to move.
+fn plan_migration(
+    github: &dyn GithubBackend,
+    repository: &Repository,
+    from: &str,
+    to: &str,
+    tracking_issue_label: &str,
+    include_closed: bool,
+) -> anyhow::Result<BTreeSet<GithubAction<'static>>> {
+    let issues: Vec<_> = github
+        .list_issues_in_milestone(repository, from)?
+        .into_iter()
+        .filter(|issue| issue.has_label(tracking_issue_label))
+        .filter(|issue| include_closed || issue.state != GithubIssueState::Closed)
+        .collect();
+    let mut actions = BTreeSet::new();
+    if issues.is_empty() {
+        return Ok(actions);
+    }
+
+    if !github.list_milestones(repository)?.iter().any(|m| m == to) {
+        actions.insert(GithubAction::CreateMilestone {
+            title: to.to_string(),
+        });
+    }
+
+    for issue in &issues {
+        let comment = GoalComment {
+            previous_milestone: from,
+            milestone: to,
+            title: &issue.title,
+            owners: commas(issue.assignees.iter().map(|login| format!("@{login}"))),
+        };
+        actions.insert(GithubAction::ChangeMilestone {
+            number: issue.number,
+            milestone: to.to_string(),
+            period_by: PeriodBy::Milestone,
+            previous: vec![from.to_string()],
+            comment: Some(CommentTemplates::get()?.continuing_goal(&comment)?),
+        });
+    }
+
+    Ok(actions)
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_project_goals::gh::{
+        backend::MockGithub,
+        issues::{ExistingGithubIssue, TRACKING_ISSUE_LABEL},
+        labels::GhLabel,
+        milestone::GhMilestone,
+    };
+
+    use super::*;
+
+    /// An open tracking issue in `milestone`.
+    fn issue(number: u64, milestone: &str) -> ExistingGithubIssue {
+        ExistingGithubIssue {
+            number,
+            title: format!("Goal {number}"),
+            assignees: BTreeSet::from(["owner".to_string()]),
+            comments: vec![],
+            body: String::new(),
+            state: GithubIssueState::Open,
+            labels: vec![GhLabel {
+                name: TRACKING_ISSUE_LABEL.to_string(),
+                color: String::new(),
+            }],
+            milestone: Some(GhMilestone {
+                number: 1,
+                title: milestone.to_string(),
+                description: String::new(),
+                due_on: None,
+            }),
+        }
+    }
+
+    fn migrate_with(github: &MockGithub, include_closed: bool) {
+        let repository = Repository::new("mock", "mock");
+        let actions = plan_migration(
+            github,
+            &repository,
+            "2024h2",
+            "2024-h2",
+            TRACKING_ISSUE_LABEL,
+            include_closed,
+        )
+        .unwrap();
+        for action in actions {
+            action.execute(github, &repository).unwrap();
+        }
+    }
+
+    fn migrate(github: &MockGithub) {
+        migrate_with(github, false);
+    }
+
+    #[test]
+    fn issues_move_to_the_new_milestone_with_a_comment() {
+        let github = MockGithub::new()
+            .with_issue(issue(1, "2024h2"))
+            .with_issue(issue(2, "2024h2"))
+            .with_issue(issue(3, "2025h1"));
+        migrate(&github);
+        assert_eq!(
+            github.calls(),
+            [
+                "create milestone 2024-h2",
+                "change milestone #1",
+                "comment #1",
+                "change milestone #2",
+                "comment #2",
+            ]
+        );
+        let comment = &github.issues()[0].comments[0].body;
+        assert!(comment.contains("2024-h2"), "{comment}");
+
+        // Once migrated, there is nothing left to do.
+        migrate(&github);
+        assert_eq!(github.calls().len(), 5);
+    }
+
+    #[test]
+    fn existing_milestone_is_not_created() {
+        let github = MockGithub::new()
+            .with_milestone("2024-h2")
+            .with_issue(issue(1, "2024h2"));
+        migrate(&github);
+        assert_eq!(github.calls(), ["change milestone #1", "comment #1"]);
+    }
+
+    #[test]
+    fn only_open_tracking_issues_are_moved() {
+        let closed = ExistingGithubIssue {
+            state: GithubIssueState::Closed,
+            ..issue(2, "2024h2")
+        };
+        let unlabeled = ExistingGithubIssue {
+            labels: vec![],
+            ..issue(3, "2024h2")
+        };
+        let github = MockGithub::new()
+            .with_milestone("2024-h2")
+            .with_issue(issue(1, "2024h2"))
+            .with_issue(closed)
+            .with_issue(unlabeled);
+        migrate(&github);
+        assert_eq!(github.calls(), ["change milestone #1", "comment #1"]);
+
+        // Closed issues are only moved when asked for, and never issues that are not ours.
+        migrate_with(&github, true);
+        assert_eq!(github.calls()[2..], ["change milestone #2", "comment #2"]);
+    }
+}
@@ -0,0 +1,114 @@
+use std::collections::BTreeSet;
+
+use rust_project_goals::{
+    gh::{backend::GithubBackend, issue_id::Repository},
+    sync::GithubAction,
+};
+
+use crate::rfc::{self, IssuesOptions};
+
+/// Set the due date of the milestone `milestone` to `due`, creating the milestone if it
+/// does not exist.
+pub(crate) fn set_milestone_due(
+    github: &dyn GithubBackend,
+    repository: &Repository,
+    milestone: &str,
+    due: chrono::NaiveDate,
+    options: &IssuesOptions,
+) -> anyhow::Result<()> {
+    let actions = plan_milestone_due(github, repository, milestone, due)?;
+    if actions.is_empty() {
+        eprintln!("Milestone `{milestone}` is already due {due}, nothing to do.");
+        return Ok(());
+    }
+
+    if options.commit {
+        rfc::check_expected_repository(repository, options)?;
+        rfc::execute_actions(github, repository, actions, options)?;
+    } else {
+        rfc::print_actions(&actions, options)?;
+    }
+
+    Ok(())
+}
+
+fn plan_milestone_due(
+    github: &dyn GithubBackend,
+    repository: &Repository,
+    milestone: &str,
+    due: chrono::NaiveDate,
+) -> anyhow::Result<BTreeSet<GithubAction<'static>>> {
+    let due_on = due.format("%Y-%m-%d").to_string();
+    let mut actions = BTreeSet::new();
+
+    let previous = if github
+        .list_milestones(repository)?
+        .iter()
+        .any(|m| m == milestone)
+    {
+        github.milestone_due_on(repository, milestone)?
+    } else {
+        actions.insert(GithubAction::CreateMilestone {
+            title: milestone.to_string(),
+        });
+        None
+    };
+
+    // Github returns the due date as a timestamp, like `2025-06-30T00:00:00Z`.
+    if previous.as_deref().and_then(|p| p.get(..10)) != Some(due_on.as_str()) {
+        actions.insert(GithubAction::SetMilestoneDue {
+            title: milestone.to_string(),
+            due_on,
+            previous,
+        });
+    }
+
+    Ok(actions)
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_project_goals::gh::backend::MockGithub;
+
+    use super::*;
+
+    fn set_due(github: &MockGithub, due: &str) {
+        let repository = Repository::new("mock", "mock");
+        let due = chrono::NaiveDate::parse_from_str(due, "%Y-%m-%d").unwrap();
+        for action in plan_milestone_due(github, &repository, "2025h1", due).unwrap() {
+            action.execute(github, &repository).unwrap();
+        }
+    }
+
+    #[test]
+    fn missing_milestone_is_created_with_its_due_date() {
+        let github = MockGithub::new();
+        set_due(&github, "2025-06-30");
+        assert_eq!(
+            github.calls(),
+            [
+                "create milestone 2025h1",
+                "set due date of milestone 2025h1"
+            ]
+        );
+
+        // Once set, there is nothing left to do.
+        set_due(&github, "2025-06-30");
+        assert_eq!(github.calls().len(), 2);
+    }
+
+    #[test]
+    fn existing_milestone_gets_the_new_due_date() {
+        let github = MockGithub::new().with_milestone_due("2025h1", "2025-06-01T00:00:00Z");
+        let repository = Repository::new("mock", "mock");
+        let due = chrono::NaiveDate::from_ymd_opt(2025, 6, 30).unwrap();
+        let actions = plan_milestone_due(&github, &repository, "2025h1", due).unwrap();
+        assert_eq!(
+            actions.iter().map(|a| a.to_string()).collect::<Vec<_>>(),
+            ["set the due date of milestone \"2025h1\" to 2025-06-30 (was 2025-06-01T00:00:00Z)"]
+        );
+
+        set_due(&github, "2025-06-30");
+        assert_eq!(github.calls(), ["set due date of milestone 2025h1"]);
+    }
+}
@@ -0,0 +1,97 @@
+use std::fmt::Write;
+use std::path::Path;
+
+use rust_project_goals::{
+    gh::authenticated_user,
+    goal::{self, GoalDocument},
+};
+
+use rust_project_goals::sync::TaskCounts;
+
+/// Print the goals in `path` that `user` (by default, the user `gh` is authenticated as) owns,
+/// with their tracking issue and how many of their tasks are complete.
+pub(super) fn mine(path: &Path, user: Option<&str>, strict: bool) -> anyhow::Result<()> {
+    let user = match user {
+        Some(user) => user.to_string(),
+        None => authenticated_user()?,
+    };
+
+    let goal_documents = goal::goals_in_dir(path, strict)?;
+    let owned = owned_by(&goal_documents, &user);
+    if owned.is_empty() {
+        eprintln!(
+            "@{} owns no goals in `{}`",
+            user.trim_start_matches('@'),
+            path.display()
+        );
+        return Ok(());
+    }
+
+    let mut text = String::new();
+    for goal_document in owned {
+        let tracking_issue = match &goal_document.metadata.tracking_issue {
+            Some(issue_id) => issue_id.url(),
+            None => "no tracking issue".to_string(),
+        };
+        let counts = TaskCounts::of(goal_document);
+        writeln!(
+            text,
+            "- {}: {tracking_issue} ({} of {} tasks complete{}{})",
+            goal_document.metadata.title,
+            counts.completed,
+            counts.total,
+            counts.stretch_suffix(),
+            counts.blocked_suffix(),
+        )?;
+    }
+    print!("{text}");
+
+    Ok(())
+}
+
+/// The goals listing `user` (with or without the leading `@`) as point of contact or owner.
+fn owned_by<'doc>(goal_documents: &'doc [GoalDocument], user: &str) -> Vec<&'doc GoalDocument> {
+    let user = user.trim_start_matches('@');
+    goal_documents
+        .iter()
+        .filter(|goal_document| {
+            goal_document
+                .metadata
+                .owner_usernames()
+                .iter()
+                .any(|username| username.trim_start_matches('@').eq_ignore_ascii_case(user))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_project_goals::goal::test_support::GoalText;
+
+    use super::*;
+
+    fn goal(slug: &str, poc: &str, additional_owners: &str) -> GoalDocument {
+        GoalText::new(slug)
+            .row("Point of contact", poc)
+            .row("Additional owners", additional_owners)
+            .document(&format!("src/2025h1/{slug}.md"))
+    }
+
+    #[test]
+    fn goals_are_filtered_by_owner() {
+        let goals = [
+            goal("a", "@alice", ""),
+            goal("b", "@bob", ""),
+            goal("c", "@Bob", "@alice"),
+        ];
+        let titles = |user| {
+            owned_by(&goals, user)
+                .iter()
+                .map(|goal| goal.metadata.title.clone())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(titles("alice"), ["a", "c"]);
+        assert_eq!(titles("@bob"), ["b", "c"]);
+        assert!(titles("carol").is_empty());
+    }
+}
@@ -0,0 +1,100 @@
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+use rust_project_goals::{
+    goal::{self, GoalDocument},
+    team::get_person_data,
+};
+
+/// Write a CODEOWNERS-like file mapping the path of each goal document in `path`
+/// to the GitHub usernames of its owners, one `path @user1 @user2` line per goal.
+///
+/// Usernames that are not found in the Rust team data are kept as written and reported on stderr.
+pub(super) fn owners_export(
+    path: &Path,
+    output: &Option<PathBuf>,
+    strict: bool,
+) -> anyhow::Result<()> {
+    let goal_documents = goal::goals_in_dir(path, strict)?;
+    let (text, unknown) = owners_text(&goal_documents, |username| {
+        Ok(get_person_data(username)?.map(|person| person.github_username.clone()))
+    })?;
+
+    if let Some(output) = output {
+        if let Some(parent) = output.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(output, text)?;
+    } else {
+        print!("{text}");
+    }
+
+    for (path, username) in &unknown {
+        eprintln!(
+            "warning: {}: owner `{username}` not found in the Rust team data",
+            path.display()
+        );
+    }
+
+    Ok(())
+}
+
+/// The lines of the export for `goal_documents`, along with the `(path, username)` of each owner
+/// that `github_login` (mapping `@user` to a GitHub login) does not know.
+fn owners_text(
+    goal_documents: &[GoalDocument],
+    github_login: impl Fn(&str) -> anyhow::Result<Option<String>>,
+) -> anyhow::Result<(String, Vec<(PathBuf, String)>)> {
+    let mut text = String::new();
+    let mut unknown = vec![];
+    for goal_document in goal_documents {
+        let mut owners = vec![];
+        for username in goal_document.metadata.owner_usernames() {
+            match github_login(username)? {
+                Some(login) => owners.push(format!("@{login}")),
+                None => {
+                    unknown.push((goal_document.path.clone(), username.to_string()));
+                    owners.push(username.to_string());
+                }
+            }
+        }
+        writeln!(
+            text,
+            "{} {}",
+            goal_document.path.display(),
+            owners.join(" ")
+        )?;
+    }
+    Ok((text, unknown))
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_project_goals::goal::test_support::GoalText;
+
+    use super::*;
+
+    fn goal(file_name: &str, point_of_contact: &str) -> GoalDocument {
+        GoalText::new("Exported")
+            .row("Point of contact", point_of_contact)
+            .document(&format!("src/2025h1/{file_name}"))
+    }
+
+    #[test]
+    fn one_line_per_goal_with_known_logins() {
+        let goals = [goal("a.md", "@Alice"), goal("b.md", "@stranger")];
+        let (text, unknown) = owners_text(&goals, |username| {
+            Ok(match username {
+                "@Alice" => Some("alice".to_string()),
+                _ => None,
+            })
+        })
+        .unwrap();
+
+        assert_eq!(text, "src/2025h1/a.md @alice\nsrc/2025h1/b.md @stranger\n");
+        assert_eq!(
+            unknown,
+            vec![(PathBuf::from("src/2025h1/b.md"), "@stranger".to_string())]
+        );
+    }
+}
@@ -0,0 +1,114 @@
+use std::path::PathBuf;
+use std::process::Command;
+
+use rust_project_goals::{
+    gh::{issue_id::Repository, milestone::list_milestone_titles},
+    sync,
+};
+
+use crate::rfc;
+
+/// Outcome of a single preflight check.
+struct Check {
+    description: String,
+    result: Result<(), String>,
+}
+
+/// Check that everything needed by `issues --commit` is in place: the `gh` client is installed
+/// and authenticated, it can push to `repository`, and the milestone for each path exists.
+pub(crate) fn preflight(
+    repository: &Repository,
+    paths: &[PathBuf],
+    timeframe: Option<&str>,
+) -> anyhow::Result<()> {
+    if timeframe.is_some() && paths.len() > 1 {
+        anyhow::bail!("`--timeframe` can only be used with a single path");
+    }
+
+    let mut checks = vec![];
+
+    checks.push(Check {
+        description: "`gh` is installed".to_string(),
+        result: gh_version(),
+    });
+
+    checks.push(Check {
+        description: "`gh` is authenticated".to_string(),
+        result: gh_auth_status(),
+    });
+
+    checks.push(Check {
+        description: format!("can push to `{repository}`"),
+        result: can_push(repository),
+    });
+
+    let milestones = list_milestone_titles(repository).map_err(|e| format!("{e:#}"));
+    for path in paths {
+        let timeframe = sync::validate_path(path, timeframe)?;
+        checks.push(Check {
+            description: format!("milestone `{timeframe}` exists in `{repository}`"),
+            result: match &milestones {
+                Ok(titles) if titles.contains(&timeframe) => Ok(()),
+                Ok(_) => Err("no such milestone, create it before running `issues`".to_string()),
+                Err(e) => Err(e.clone()),
+            },
+        });
+    }
+
+    let color = !rfc::no_color();
+    for check in &checks {
+        match &check.result {
+            Ok(()) if color => eprintln!("\x1b[32m✔\x1b[0m {}", check.description),
+            Ok(()) => eprintln!("ok:     {}", check.description),
+            Err(e) if color => eprintln!("\x1b[31m✘\x1b[0m {}: {}", check.description, e.trim()),
+            Err(e) => eprintln!("failed: {}: {}", check.description, e.trim()),
+        }
+    }
+
+    let failed = checks.iter().filter(|c| c.result.is_err()).count();
+    if failed > 0 {
+        anyhow::bail!("{failed} preflight check(s) failed");
+    }
+    Ok(())
+}
+
+fn gh_version() -> Result<(), String> {
+    match Command::new("gh").arg("--version").output() {
+        Ok(output) if output.status.success() => Ok(()),
+        Ok(output) => Err(String::from_utf8_lossy(&output.stderr).into_owned()),
+        Err(e) => Err(format!("the github `gh` client is missing: {e}")),
+    }
+}
+
+fn gh_auth_status() -> Result<(), String> {
+    let output = Command::new("gh")
+        .arg("auth")
+        .arg("status")
+        .output()
+        .map_err(|e| e.to_string())?;
+
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(String::from_utf8_lossy(&output.stderr).into_owned())
+    }
+}
+
+fn can_push(repository: &Repository) -> Result<(), String> {
+    let output = Command::new("gh")
+        .arg("api")
+        .arg(format!("repos/{repository}"))
+        .arg("--jq")
+        .arg(".permissions.push")
+        .output()
+        .map_err(|e| e.to_string())?;
+
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).into_owned());
+    }
+
+    match String::from_utf8_lossy(&output.stdout).trim() {
+        "true" => Ok(()),
+        _ => Err("the authenticated user does not have write access".to_string()),
+    }
+}
@@ -0,0 +1,258 @@
+use std::collections::BTreeSet;
+use std::path::Path;
+
+use rust_project_goals::{
+    gh::{backend::GithubBackend, issue_id::Repository},
+    goal::{self, GoalDocument},
+    sync::GithubAction,
+    team,
+};
+
+use crate::rfc::{self, IssuesOptions};
+
+/// The goal `goal_slug` found in the directory `path`.
+pub(crate) fn find_goal(
+    path: &Path,
+    goal_slug: &str,
+    strict: bool,
+) -> anyhow::Result<GoalDocument> {
+    let goal_documents = goal::goals_in_dir(path, strict)?;
+    match goal_documents
+        .into_iter()
+        .find(|doc| doc.slug() == goal_slug)
+    {
+        Some(goal_document) => Ok(goal_document),
+        None => anyhow::bail!("no goal named `{goal_slug}` found in `{}`", path.display()),
+    }
+}
+
+/// Move ownership of `goal_document` from `from` to `to`, both in the goal document and in
+/// the assignees of its tracking issue.
+pub(crate) fn reassign(
+    github: &dyn GithubBackend,
+    repository: &Repository,
+    goal_document: &GoalDocument,
+    from: &str,
+    to: &str,
+    options: &IssuesOptions,
+) -> anyhow::Result<()> {
+    let from = with_at(from);
+    let to = with_at(to);
+
+    // Check everything before making any change.
+    let Some(new_owner) = team::get_person_data(&to)? else {
+        anyhow::bail!("`{to}` was not found in the Rust team data");
+    };
+
+    let old_login = match team::get_person_data(&from)? {
+        Some(person) => person.github_username.clone(),
+        None => from[1..].to_string(),
+    };
+
+    let actions = reassign_actions(goal_document, &from, &old_login, &new_owner.github_username)?;
+
+    if options.commit {
+        rfc::check_expected_repository(repository, options)?;
+        goal_document.replace_owner(&from, &to)?;
+        eprintln!("Updated `{}`", goal_document.path.display());
+        if !actions.is_empty() {
+            rfc::execute_actions(github, repository, actions, options)?;
+        }
+    } else {
+        eprintln!("Changes to `{}`:", goal_document.path.display());
+        for line in goal_document.replace_owner_preview(&from, &to)? {
+            eprintln!("    {line}");
+        }
+        eprintln!();
+        rfc::print_actions(&actions, options)?;
+    }
+
+    Ok(())
+}
+
+/// The actions that move the tracking issue of `goal_document` from `old_login` to `new_login`,
+/// after checking that `from` (the `@`-prefixed name of `old_login`) owns the goal.
+fn reassign_actions(
+    goal_document: &GoalDocument,
+    from: &str,
+    old_login: &str,
+    new_login: &str,
+) -> anyhow::Result<BTreeSet<GithubAction<'static>>> {
+    if !goal_document
+        .metadata
+        .owner_usernames()
+        .iter()
+        .any(|owner| owner.eq_ignore_ascii_case(from))
+    {
+        anyhow::bail!(
+            "`{from}` is not an owner of `{}` (owners are {})",
+            goal_document.slug(),
+            goal_document.metadata.owner_usernames().join(", ")
+        );
+    }
+
+    let mut actions = BTreeSet::new();
+    if let Some(tracking_issue) = &goal_document.metadata.tracking_issue {
+        actions.insert(GithubAction::SyncAssignees {
+            number: tracking_issue.number,
+            remove_owners: BTreeSet::from([old_login.to_string()]),
+            add_owners: BTreeSet::from([new_login.to_string()]),
+        });
+    } else {
+        eprintln!(
+            "note: `{}` has no tracking issue, only the markdown will be updated",
+            goal_document.slug()
+        );
+    }
+    Ok(actions)
+}
+
+/// Usernames are written with a leading `@` in goal documents; accept them with or without.
+fn with_at(username: &str) -> String {
+    if username.starts_with('@') {
+        username.to_string()
+    } else {
+        format!("@{username}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_project_goals::gh::{backend::MockGithub, issues::ExistingGithubIssue};
+    use rust_project_goals::goal::test_support::GoalText;
+    use rust_project_goals_json::GithubIssueState;
+
+    use super::*;
+    use crate::test_support::use_team_fixtures;
+
+    fn goal() -> GoalText {
+        GoalText::new("Reassigned")
+            .row("Point of contact", "@alice")
+            .row("Status", "Accepted")
+    }
+
+    #[test]
+    fn tracking_issue_assignee_is_replaced() {
+        let document = goal()
+            .row("Tracking issue", "[rust-lang/rust-project-goals#5]")
+            .document("src/2025h1/reassigned.md");
+        let actions = reassign_actions(&document, "@Alice", "alice", "bob").unwrap();
+
+        let repository = Repository::new("rust-lang", "rust-project-goals");
+        let github = MockGithub::new().with_issue(ExistingGithubIssue {
+            number: 5,
+            title: "Reassigned".to_string(),
+            assignees: BTreeSet::from(["alice".to_string(), "carol".to_string()]),
+            comments: vec![],
+            body: String::new(),
+            state: GithubIssueState::Open,
+            labels: vec![],
+            milestone: None,
+        });
+        for action in actions {
+            action.execute(&github, &repository).unwrap();
+        }
+
+        assert_eq!(
+            github.issues()[0].assignees,
+            BTreeSet::from(["bob".to_string(), "carol".to_string()])
+        );
+    }
+
+    #[test]
+    fn goal_document_and_tracking_issue_are_reassigned() {
+        use_team_fixtures();
+        let dir = std::env::temp_dir().join(format!(
+            "goal_document_and_tracking_issue_are_reassigned-{}",
+            std::process::id()
+        ));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("reassigned.md");
+        let text = goal()
+            .row("Tracking issue", "[rust-lang/rust-project-goals#5]")
+            .text();
+        std::fs::write(&path, text).unwrap();
+
+        // The logins come from the team data, where Alice is capitalized.
+        let repository = Repository::new("rust-lang", "rust-project-goals");
+        let github = MockGithub::new().with_issue(ExistingGithubIssue {
+            number: 5,
+            title: "Reassigned".to_string(),
+            assignees: BTreeSet::from(["Alice".to_string()]),
+            comments: vec![],
+            body: String::new(),
+            state: GithubIssueState::Open,
+            labels: vec![],
+            milestone: None,
+        });
+        let options = IssuesOptions {
+            sleep: 0,
+            commit: true,
+            confirm_repo: Some(repository.clone()),
+            force: false,
+            progress_state: None,
+            comment_on_regression: false,
+            no_progress: true,
+            output_actions_file: None,
+            detailed: false,
+            dry_run_apply_local: false,
+            summary: false,
+        };
+        let goal_document = find_goal(&dir, "reassigned", false).unwrap();
+        reassign(
+            &github,
+            &repository,
+            &goal_document,
+            "alice",
+            "@bob",
+            &options,
+        )
+        .unwrap();
+
+        assert_eq!(github.calls(), ["sync assignees #5"]);
+        assert_eq!(
+            github.issues()[0].assignees,
+            BTreeSet::from(["bob".to_string()])
+        );
+        let text = std::fs::read_to_string(&path).unwrap();
+        let point_of_contact = text
+            .lines()
+            .find(|line| line.starts_with("| Point of contact"))
+            .unwrap();
+        assert!(point_of_contact.contains("@bob"), "{text}");
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn goal_without_tracking_issue_has_no_actions() {
+        let actions = reassign_actions(
+            &goal().document("src/2025h1/reassigned.md"),
+            "@alice",
+            "alice",
+            "bob",
+        )
+        .unwrap();
+        assert!(actions.is_empty());
+    }
+
+    #[test]
+    fn only_owners_can_be_reassigned() {
+        let error = reassign_actions(
+            &goal().document("src/2025h1/reassigned.md"),
+            "@bob",
+            "bob",
+            "carol",
+        )
+        .unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("`@bob` is not an owner of `reassigned`"));
+    }
+
+    #[test]
+    fn usernames_get_a_leading_at() {
+        assert_eq!(with_at("alice"), "@alice");
+        assert_eq!(with_at("@alice"), "@alice");
+    }
+}
@@ -1,84 +1,283 @@
 use std::{
-    collections::BTreeSet,
-    fmt::Display,
+    collections::{BTreeMap, BTreeSet},
+    fmt::Write,
     path::{Path, PathBuf},
     process::Command,
     time::Duration,
 };
 
 use anyhow::Context;
-use regex::Regex;
+use serde::Serialize;
+use walkdir::WalkDir;
 
 use rust_project_goals::{
+    action::PeriodBy,
+    config::Configuration,
     gh::{
-        issue_id::{IssueId, Repository},
+        backend::GithubBackend,
+        issue_id::Repository,
         issues::{
-            change_milestone, change_title, create_comment, create_issue, fetch_issue,
-            list_issues_in_milestone, lock_issue, sync_assignees, update_issue_body,
-            CONTINUING_GOAL_PREFIX, FLAGSHIP_LABEL, LOCK_TEXT,
+            checkboxes, ARCHIVED_GOAL_PREFIX, COMPLETED_LABEL, FCP_CHECKLIST_MARKER,
+            TASK_REGRESSION_PREFIX,
         },
         labels::GhLabel,
     },
-    goal::{self, GoalDocument, GoalPlan, ParsedOwners},
-    team::{get_person_data, TeamName},
+    goal::{self, AcceptanceStatus, GoalDocument},
+    sync::{
+        goal_document_link, load_timeframes, plan_bodies, plan_timeframes, teams_with_asks,
+        validate_path, GithubAction, GithubIssue, LabelOptions, SyncOptions, TaskCounts,
+    },
+    team,
+    util::{commas, Warnings},
 };
+use rust_project_goals_json::{GithubIssueState, Progress};
 
-fn validate_path(path: &Path) -> anyhow::Result<String> {
-    if !path.is_dir() {
-        return Err(anyhow::anyhow!(
-            "RFC path should be a directory like src/2024h2"
-        ));
-    };
+use crate::error::{self, ClassifiedError, ErrorKind, JsonError};
 
-    if path.is_absolute() {
-        return Err(anyhow::anyhow!("RFC path should be relative"));
-    }
-
-    let timeframe = path
-        .components()
-        .last()
-        .unwrap()
-        .as_os_str()
-        .to_str()
-        .ok_or_else(|| anyhow::anyhow!("invalid path `{}`", path.display()))?;
+/// The FCP checklist for the goals in `path`. Team members in `acknowledged` (lowercase
+/// github usernames, see [`fcp_acknowledgements`]) have their box checked and marked ✅.
+/// Unless `mentions` is set, leads are named in a way that does not notify them (see [`mention`]).
+pub fn generate_comment(
+    path: &Path,
+    timeframe: Option<&str>,
+    acknowledged: &BTreeSet<String>,
+    mentions: bool,
+    strict: bool,
+) -> anyhow::Result<String> {
+    let timeframe = validate_path(path, timeframe)?;
+    let mut goal_documents = goal::goals_in_dir(path, strict)?;
+    goal_documents.retain(|gd| gd.is_not_not_accepted());
+    let teams_with_asks = teams_with_asks(&goal_documents);
 
-    Ok(timeframe.to_string())
-}
+    let mut text = String::new();
 
-pub fn generate_comment(path: &Path) -> anyhow::Result<()> {
-    let _ = validate_path(path)?;
-    let goal_documents = goal::goals_in_dir(path)?;
-    let teams_with_asks = teams_with_asks(&goal_documents);
+    // List the goals, flagship goals first.
+    let (flagship_goals, other_goals) = goal::flagship_first(&goal_documents);
+    for (header, goals) in [
+        ("⭐ Flagship", flagship_goals),
+        ("Other goals", other_goals),
+    ] {
+        if goals.is_empty() {
+            continue;
+        }
+        writeln!(text, "\n## {header}\n")?;
+        let groups = goal::by_category(&goals).unwrap_or_else(|| vec![("", goals)]);
+        for (category, goals) in groups {
+            if !category.is_empty() {
+                writeln!(text, "### {category}\n")?;
+            }
+            for goal_document in &goals {
+                writeln!(
+                    text,
+                    "* {} ({})",
+                    goal_document.metadata.title,
+                    goal_document_link(&timeframe, goal_document)
+                )?;
+            }
+            if !category.is_empty() {
+                writeln!(text)?;
+            }
+        }
+    }
 
     for team_name in teams_with_asks {
         let team_data = team_name.data();
 
-        println!("\n## {}\n", team_data.name);
+        writeln!(text, "\n## {}\n", team_data.name)?;
 
         let (leads, members): (Vec<_>, Vec<_>) = team_data.members.iter().partition(|m| m.is_lead);
 
         for lead in leads {
-            println!("* [ ] @{} (required, lead)", lead.github);
+            writeln!(
+                text,
+                "{}",
+                checklist_line(
+                    &format!("{} (required, lead)", mention(&lead.github, mentions)),
+                    &lead.github,
+                    acknowledged
+                )
+            )?;
         }
 
         for member in members {
-            println!("* [ ] {} (optional)", member.github);
+            writeln!(
+                text,
+                "{}",
+                checklist_line(
+                    &format!("{} (optional)", member.github),
+                    &member.github,
+                    acknowledged
+                )
+            )?;
+        }
+    }
+
+    Ok(text)
+}
+
+/// `@username`, or `` `@username` `` if `mentions` is false: github does not notify
+/// anyone mentioned in code, so previews can be regenerated without pinging people.
+fn mention(username: &str, mentions: bool) -> String {
+    if mentions {
+        format!("@{username}")
+    } else {
+        format!("`@{username}`")
+    }
+}
+
+/// A `* [ ]` line of the FCP checklist for `username`, checked if they acknowledged the asks.
+fn checklist_line(entry: &str, username: &str, acknowledged: &BTreeSet<String>) -> String {
+    if acknowledged.contains(&username.to_lowercase()) {
+        format!("* [x] {entry} ✅")
+    } else {
+        format!("* [ ] {entry}")
+    }
+}
+
+/// The (lowercase) github usernames that reacted with a thumbs-up to the FCP checklist
+/// posted on issue `number`, if any. Only the team members among them appear in the
+/// checklist, so reactions from anyone else have no effect.
+pub fn fcp_acknowledgements(
+    github: &dyn GithubBackend,
+    repository: &Repository,
+    number: u64,
+) -> anyhow::Result<BTreeSet<String>> {
+    let issue = github.fetch_issue(repository, number)?;
+    let Some(comment) = issue
+        .comments
+        .iter()
+        .find(|comment| comment.body.contains(FCP_CHECKLIST_MARKER))
+    else {
+        return Ok(BTreeSet::new());
+    };
+
+    Ok(github
+        .list_comment_reactions(repository, comment)?
+        .into_iter()
+        .filter(|reaction| reaction.is_thumbs_up())
+        .map(|reaction| reaction.user.to_lowercase())
+        .collect())
+}
+
+/// Post the FCP checklist `text` as a comment on issue `number`, or update the checklist
+/// posted there by an earlier run, keeping the boxes that were checked since.
+pub fn post_fcp_comment(
+    github: &dyn GithubBackend,
+    repository: &Repository,
+    number: u64,
+    text: &str,
+) -> anyhow::Result<()> {
+    let issue = github.fetch_issue(repository, number)?;
+    let existing = issue
+        .comments
+        .iter()
+        .find(|comment| comment.body.contains(FCP_CHECKLIST_MARKER));
+
+    match existing {
+        Some(comment) => {
+            let body = format!(
+                "{}\n{FCP_CHECKLIST_MARKER}",
+                keep_checked_boxes(&comment.body, text).trim_end()
+            );
+            if body.trim() == comment.body.trim() {
+                eprintln!("FCP checklist on #{number} is up to date");
+                return Ok(());
+            }
+            github.update_comment(repository, comment, &body)?;
+            eprintln!("updated FCP checklist: {}", comment.url);
+        }
+        None => {
+            let body = format!("{}\n{FCP_CHECKLIST_MARKER}", text.trim_end());
+            github.create_comment(repository, number, &body)?;
+            eprintln!("posted FCP checklist on #{number}");
         }
     }
 
     Ok(())
 }
 
-pub fn generate_rfc(path: &Path) -> anyhow::Result<()> {
-    let timeframe = &validate_path(path)?;
+/// `text`, with the `* [ ]` lines that are checked in `old` checked as well.
+fn keep_checked_boxes(old: &str, text: &str) -> String {
+    let checked: BTreeSet<&str> = old
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("* [x] "))
+        .collect();
+    text.lines()
+        .map(|line| match line.strip_prefix("* [ ] ") {
+            Some(rest) if checked.contains(rest) => format!("* [x] {rest}\n"),
+            _ => format!("{line}\n"),
+        })
+        .collect()
+}
 
-    // run mdbook build
-    Command::new("mdbook").arg("build").status()?;
+/// Generate the RFC for the goals in `path`, building the book with `mdbook_binary` first
+/// (or, if it is `None`, reusing the existing build).
+pub fn generate_rfc(
+    path: &Path,
+    output: Option<&Path>,
+    split: bool,
+    mdbook_binary: Option<&Path>,
+    timeframe: Option<&str>,
+    toc: bool,
+    strict: bool,
+) -> anyhow::Result<()> {
+    let timeframe = &validate_path(path, timeframe)?;
+
+    // Check the goal documents like `cargo rpg check` does, so that a malformed one is
+    // reported by file rather than as a confusing failure after a slow `mdbook build`.
+    let problems = goal::validate_metadata_in_dir(path, None, strict)?;
+    if !problems.is_empty() {
+        for problem in &problems {
+            eprintln!("{problem}");
+        }
+        return Err(ClassifiedError::new(
+            ErrorKind::Validation,
+            format!("found {} metadata problem(s), aborting", problems.len()),
+        )
+        .into());
+    }
+    goal::goals_in_dir(path, strict)?;
 
     // find the markdown output
     let generated_path = PathBuf::from("book/markdown")
         .join(timeframe)
         .join("index.md");
+
+    if let Some(mdbook_binary) = mdbook_binary {
+        // Verify `mdbook` is installed, so that we can say how to get it.
+        let sanity_check = Command::new(mdbook_binary).arg("--version").output();
+        if sanity_check.is_err() {
+            anyhow::bail!(
+                "`{}` could not be run; install mdbook via `cargo install mdbook` \
+                 (or pass `--mdbook-binary` with its path)",
+                mdbook_binary.display()
+            );
+        }
+
+        // run mdbook build
+        let status = Command::new(mdbook_binary)
+            .arg("build")
+            .status()
+            .with_context(|| format!("running `{} build`", mdbook_binary.display()))?;
+        if !status.success() {
+            anyhow::bail!("`{} build` failed ({status})", mdbook_binary.display());
+        }
+    } else {
+        // Reuse the existing output, but only if nothing changed since it was generated.
+        if !generated_path.exists() {
+            anyhow::bail!(
+                "no markdown generated at {}; rerun without `--no-build`",
+                generated_path.display()
+            );
+        }
+        if !is_newer_than_sources(&generated_path)? {
+            anyhow::bail!(
+                "markdown at {} is older than the book sources; rerun without `--no-build`",
+                generated_path.display()
+            );
+        }
+    }
+
     if !generated_path.exists() {
         return Err(anyhow::anyhow!(
             "no markdown generated at {}",
@@ -93,559 +292,1454 @@ pub fn generate_rfc(path: &Path) -> anyhow::Result<()> {
         )
     })?;
 
-    let regex = Regex::new(r"\]\(([^(]*)\.md(#[^)]*)?\)").unwrap();
-
-    let result = regex.replace_all(
+    let mut result = rewrite_md_links(
         &generated_text,
-        format!("](https://rust-lang.github.io/rust-project-goals/{timeframe}/$1.html$2)"),
+        &format!("https://rust-lang.github.io/rust-project-goals/{timeframe}"),
     );
+    if toc {
+        result = format!("{}\n{result}", table_of_contents(&result));
+    }
 
-    println!("{result}");
+    let Some(output) = output else {
+        println!("{result}");
+        return Ok(());
+    };
+
+    std::fs::write(output, &result)
+        .with_context(|| format!("writing RFC to `{}`", output.display()))?;
+
+    if split {
+        // Emit the sections into a directory next to the output file,
+        // e.g. `rfc.md` is split into `rfc/01-summary.md`, `rfc/02-motivation.md`, ...
+        let split_dir = output.with_extension("");
+        std::fs::create_dir_all(&split_dir)
+            .with_context(|| format!("creating directory `{}`", split_dir.display()))?;
+
+        for (index, (title, text)) in split_sections(&result).into_iter().enumerate() {
+            let split_path = split_dir.join(format!("{index:02}-{}.md", slugify(&title)));
+            std::fs::write(&split_path, text)
+                .with_context(|| format!("writing RFC section to `{}`", split_path.display()))?;
+        }
+    }
 
     Ok(())
 }
 
-pub fn generate_issues(
-    repository: &Repository,
-    path: &Path,
-    commit: bool,
-    sleep: u64,
-) -> anyhow::Result<()> {
-    // Verify the `gh` client is installed to compute which actions need to be taken in the repo.
-    let sanity_check = Command::new("gh").arg("--version").output();
-    if sanity_check.is_err() {
-        return Err(anyhow::anyhow!(
-            "The github `gh` client is missing and needs to be installed and configured with a token."
-        ));
+/// True if `generated_path` was modified after every file in the book sources (`src` and `book.toml`).
+fn is_newer_than_sources(generated_path: &Path) -> anyhow::Result<bool> {
+    let generated_modified = std::fs::metadata(generated_path)?.modified()?;
+
+    let source_paths = WalkDir::new("src")
+        .into_iter()
+        .map(|entry| Ok(entry?.into_path()))
+        .chain(std::iter::once(Ok(PathBuf::from("book.toml"))))
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
+    for source_path in source_paths {
+        let modified = std::fs::metadata(&source_path)
+            .and_then(|metadata| metadata.modified())
+            .with_context(|| format!("reading metadata for `{}`", source_path.display()))?;
+        if modified > generated_modified {
+            return Ok(false);
+        }
     }
 
-    // Hacky but works: we loop because after creating the issue, we sometimes have additional sync to do,
-    // and it's easier this way.
-    loop {
-        let timeframe = validate_path(path)?;
+    Ok(true)
+}
 
-        let mut goal_documents = goal::goals_in_dir(path)?;
-        goal_documents.retain(|gd| gd.is_not_not_accepted());
+/// Point the links to other goal documents in `text` (relative `.md` destinations, like
+/// `./async.md#summary`) at their rendered page under `base_url`. Only the destination is
+/// replaced, so titles, reference-style links and links inside code are left as they are;
+/// for reference-style links, it is the definition that gets rewritten.
+fn rewrite_md_links(text: &str, base_url: &str) -> String {
+    use pulldown_cmark::{Event, LinkType, Parser, Tag};
+
+    // Byte ranges of the destinations to replace, and their replacement.
+    let mut replacements = vec![];
+
+    let mut events = Parser::new(text).into_offset_iter();
+    for (event, range) in events.by_ref() {
+        if let Event::Start(Tag::Link {
+            link_type: LinkType::Inline,
+            dest_url,
+            ..
+        }) = event
+        {
+            if let Some(replacement) = rewritten_destination(&dest_url, base_url) {
+                if let Some(dest) = destination_range(text, range, "](", &dest_url) {
+                    replacements.push((dest, replacement));
+                }
+            }
+        }
+    }
+    for (_, definition) in events.reference_definitions().iter() {
+        if let Some(replacement) = rewritten_destination(&definition.dest, base_url) {
+            let range = definition.span.clone();
+            if let Some(dest) = destination_range(text, range, "]:", &definition.dest) {
+                replacements.push((dest, replacement));
+            }
+        }
+    }
 
-        let teams_with_asks = teams_with_asks(&goal_documents);
-        let mut actions = initialize_labels(repository, &teams_with_asks)?;
-        actions.extend(initialize_issues(repository, &timeframe, &goal_documents)?);
+    replacements.sort_by_key(|(range, _)| range.start);
+    let mut result = String::new();
+    let mut end = 0;
+    for (range, replacement) in replacements {
+        result.push_str(&text[end..range.start]);
+        result.push_str(&replacement);
+        end = range.end;
+    }
+    result.push_str(&text[end..]);
+    result
+}
 
-        if actions.is_empty() {
-            return Ok(());
-        }
+/// The rendered page for `dest`, if it is a relative link to a markdown file.
+fn rewritten_destination(dest: &str, base_url: &str) -> Option<String> {
+    if dest.contains("://") || dest.starts_with('/') {
+        return None;
+    }
+    let (path, anchor) = match dest.split_once('#') {
+        Some((path, anchor)) => (path, format!("#{anchor}")),
+        None => (dest, String::new()),
+    };
+    let page = path.strip_suffix(".md")?;
+    let page = page.strip_prefix("./").unwrap_or(page);
+    Some(format!("{base_url}/{page}.html{anchor}"))
+}
 
-        if commit {
-            progress_bar::init_progress_bar(actions.len());
-            progress_bar::set_progress_bar_action(
-                "Executing",
-                progress_bar::Color::Blue,
-                progress_bar::Style::Bold,
-            );
-            let mut success = 0;
-            for action in actions.into_iter() {
-                progress_bar::print_progress_bar_info(
-                    "Action",
-                    &format!("{}", action),
-                    progress_bar::Color::Green,
-                    progress_bar::Style::Bold,
-                );
-                if let Err(e) = action.execute(repository, &timeframe) {
-                    progress_bar::print_progress_bar_info(
-                        "Error",
-                        &format!("{}", e),
-                        progress_bar::Color::Red,
-                        progress_bar::Style::Bold,
-                    );
-                } else {
-                    success += 1;
-                }
-                progress_bar::inc_progress_bar();
+/// The byte range of `dest` in the link (or reference definition) at `range` of `text`,
+/// where the destination follows `separator` (`](` or `]:`) and optional whitespace or `<`.
+/// `None` if the destination was written differently (e.g., with escapes), in which case
+/// the link is left alone rather than risk mangling it.
+fn destination_range(
+    text: &str,
+    range: std::ops::Range<usize>,
+    separator: &str,
+    dest: &str,
+) -> Option<std::ops::Range<usize>> {
+    let source = &text[range.clone()];
+    source
+        .rmatch_indices(separator)
+        .map(|(index, _)| {
+            let after = &source[index + separator.len()..];
+            let trimmed = after.trim_start().trim_start_matches('<');
+            range.start + index + separator.len() + (after.len() - trimmed.len())
+        })
+        .find(|&start| text[start..].starts_with(dest))
+        .map(|start| start..start + dest.len())
+}
 
-                std::thread::sleep(Duration::from_millis(sleep));
-            }
-            progress_bar::finalize_progress_bar();
-            if success == 0 {
-                anyhow::bail!("all actions failed, aborting")
+/// A table of contents for `text`: a nested list linking to each of its headings down to `###`.
+fn table_of_contents(text: &str) -> String {
+    use pulldown_cmark::{Event, HeadingLevel, Parser, Tag, TagEnd};
+
+    // Every heading gets an anchor, even those too deep to be listed, since duplicates are
+    // numbered in the order of all headings.
+    let mut headings = vec![];
+    let mut anchors = BTreeSet::new();
+    let mut current: Option<(HeadingLevel, String)> = None;
+    for event in Parser::new(text) {
+        match event {
+            Event::Start(Tag::Heading { level, .. }) => current = Some((level, String::new())),
+            Event::Text(t) | Event::Code(t) => {
+                if let Some((_, title)) = &mut current {
+                    title.push_str(&t);
+                }
             }
-        } else {
-            eprintln!("Actions to be executed:");
-            for action in &actions {
-                eprintln!("* {action}");
+            Event::End(TagEnd::Heading(_)) => {
+                if let Some((level, title)) = current.take() {
+                    let anchor = unique_anchor(&heading_anchor(&title), &mut anchors);
+                    headings.push((level, title, anchor));
+                }
             }
-            eprintln!("");
-            eprintln!("Use `--commit` to execute the actions.");
-            return Ok(());
+            _ => {}
         }
     }
+
+    let listed: Vec<_> = headings
+        .into_iter()
+        .filter(|(level, _, _)| *level <= HeadingLevel::H3)
+        .collect();
+    let Some(top) = listed.iter().map(|(level, _, _)| *level).min() else {
+        return String::new();
+    };
+    let mut toc = String::from("**Contents**\n\n");
+    for (level, title, anchor) in listed {
+        let indent = "  ".repeat(level as usize - top as usize);
+        toc.push_str(&format!("{indent}* [{}](#{anchor})\n", title.trim()));
+    }
+    toc
 }
 
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
-pub struct GithubIssue<'doc> {
-    pub title: String,
-    pub assignees: BTreeSet<String>,
-    pub body: String,
-    pub labels: Vec<String>,
-    pub tracking_issue: Option<&'doc IssueId>,
-    pub goal_document: &'doc GoalDocument,
+/// The anchor that github and mdbook give a heading titled `title`: lowercase, with spaces
+/// turned into dashes and punctuation other than `-` and `_` dropped.
+fn heading_anchor(title: &str) -> String {
+    title
+        .trim()
+        .chars()
+        .filter_map(|ch| {
+            if ch.is_alphanumeric() || ch == '-' || ch == '_' {
+                Some(ch.to_lowercase().collect::<String>())
+            } else if ch.is_whitespace() {
+                Some("-".to_string())
+            } else {
+                None
+            }
+        })
+        .collect()
 }
 
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
-enum GithubAction<'doc> {
-    CreateLabel {
-        label: GhLabel,
-    },
+/// `anchor`, numbered like `anchor-1` if an earlier heading already has it.
+fn unique_anchor(anchor: &str, anchors: &mut BTreeSet<String>) -> String {
+    let mut unique = anchor.to_string();
+    let mut n = 0;
+    while !anchors.insert(unique.clone()) {
+        n += 1;
+        unique = format!("{anchor}-{n}");
+    }
+    unique
+}
 
-    CreateIssue {
-        issue: GithubIssue<'doc>,
-    },
+/// Split the generated RFC text into `(title, text)` pairs, one per `##` section.
+/// Any text before the first section is returned with the title `introduction`.
+fn split_sections(text: &str) -> Vec<(String, String)> {
+    let mut sections = vec![("introduction".to_string(), String::new())];
+    let mut in_code_block = false;
 
-    ChangeTitle {
-        number: u64,
-        title: String,
-    },
+    for line in text.lines() {
+        if line.trim_start().starts_with("```") {
+            in_code_block = !in_code_block;
+        }
 
-    ChangeMilestone {
-        number: u64,
-        milestone: String,
-    },
+        if !in_code_block {
+            if let Some(title) = line.strip_prefix("## ") {
+                sections.push((title.trim().to_string(), String::new()));
+            }
+        }
 
-    Comment {
-        number: u64,
-        body: String,
-    },
+        let (_, section_text) = sections.last_mut().unwrap();
+        section_text.push_str(line);
+        section_text.push('\n');
+    }
 
-    UpdateIssueBody {
-        number: u64,
-        body: String,
-    },
+    sections.retain(|(_, text)| !text.trim().is_empty());
+    sections
+}
 
-    // We intentionally do not sync the issue *text*, because it may have been edited.
-    SyncAssignees {
-        number: u64,
-        remove_owners: BTreeSet<String>,
-        add_owners: BTreeSet<String>,
-    },
+/// Convert a section title into something suitable for a file name.
+fn slugify(title: &str) -> String {
+    let mut slug = String::new();
+    for ch in title.chars() {
+        if ch.is_alphanumeric() {
+            slug.extend(ch.to_lowercase());
+        } else if !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    slug.trim_matches('-').to_string()
+}
 
-    LockIssue {
-        number: u64,
-    },
+/// Options shared by the commands that modify issues (e.g., `issues` and `archive`).
+#[derive(clap::Args, Debug)]
+pub struct IssuesOptions {
+    /// Number of milliseconds to pause between github commands
+    /// to avoid rate limiting
+    #[arg(long, default_value = "500")]
+    pub sleep: u64,
+
+    /// Without this option, no action is taken.
+    #[arg(long)]
+    pub commit: bool,
+
+    /// The repository you expect to be modifying. Required with `--commit` unless
+    /// `expected_repository` is set in `rust-project-goals.toml`.
+    #[arg(long)]
+    pub confirm_repo: Option<Repository>,
+
+    /// Proceed even if the repository does not match the expected one.
+    #[arg(long)]
+    pub force: bool,
+
+    /// JSON file recording the number of completed tasks per tracking issue.
+    /// If given, we warn when a goal has fewer completed tasks than on the last `--commit` run.
+    #[arg(long)]
+    pub progress_state: Option<PathBuf>,
+
+    /// Post a comment on the tracking issue when its completed task count went down
+    /// (requires `--progress-state`).
+    #[arg(long, requires = "progress_state")]
+    pub comment_on_regression: bool,
+
+    /// Print one plain line per action instead of a colored progress bar
+    /// (useful for CI logs). Also enabled when `NO_COLOR` is set.
+    #[arg(long)]
+    pub no_progress: bool,
+
+    /// With `--commit`, append each executed action (with a timestamp and whether it succeeded)
+    /// to this file, one JSON object per line.
+    #[arg(long)]
+    pub output_actions_file: Option<PathBuf>,
+
+    /// In a dry run, show the full body, labels, assignees and milestone
+    /// of each issue that would be created, not just its title.
+    #[arg(long)]
+    pub detailed: bool,
+
+    /// Only link goal documents to their existing tracking issues (a local edit of the
+    /// markdown), skipping every action that would change something on github.
+    #[arg(long, conflicts_with = "commit")]
+    pub dry_run_apply_local: bool,
+
+    /// In a dry run, group the actions into new goals, continuing goals and edits (with a
+    /// count for each) rather than listing them in the order they would be executed.
+    #[arg(long, conflicts_with_all = ["commit", "detailed"])]
+    pub summary: bool,
+}
 
-    LinkToTrackingIssue {
-        goal_document: &'doc GoalDocument,
-        issue_id: IssueId,
-    },
+impl IssuesOptions {
+    /// True if we should print plain lines rather than the progress bar.
+    fn plain_output(&self) -> bool {
+        self.no_progress || no_color() || error::json_errors()
+    }
+}
+
+/// True if the user asked for no colors (see <https://no-color.org>).
+pub(crate) fn no_color() -> bool {
+    std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty())
 }
 
-/// Initializes the required `T-<team>` labels on the repository.
-/// Warns if the labels are found with wrong color.
-fn initialize_labels(
+/// Sync the tracking issues for the goals in each of `paths` (one timeframe folder per path).
+/// The actions for all timeframes are computed together and executed in a single pass.
+pub fn generate_issues(
+    github: &dyn GithubBackend,
     repository: &Repository,
-    teams_with_asks: &BTreeSet<&TeamName>,
-) -> anyhow::Result<BTreeSet<GithubAction<'static>>> {
-    const TEAM_LABEL_COLOR: &str = "bfd4f2";
+    paths: &[PathBuf],
+    timeframe: Option<&str>,
+    label_options: &LabelOptions,
+    sync_options: SyncOptions<'_>,
+    options: &IssuesOptions,
+) -> anyhow::Result<()> {
+    if timeframe.is_some() && paths.len() > 1 {
+        anyhow::bail!("`--timeframe` can only be used with a single path");
+    }
 
-    let mut desired_labels: BTreeSet<_> = teams_with_asks
-        .iter()
-        .map(|team| {
-            let label_name = team.gh_label();
+    let IssuesOptions {
+        sleep: _,
+        commit,
+        confirm_repo: _,
+        force: _,
+        progress_state: _,
+        comment_on_regression,
+        no_progress: _,
+        output_actions_file: _,
+        detailed,
+        dry_run_apply_local,
+        summary,
+    } = *options;
 
-            GhLabel {
-                name: label_name,
-                color: TEAM_LABEL_COLOR.to_string(),
-            }
-        })
-        .collect();
+    // Verify the `gh` client is installed to compute which actions need to be taken in the repo.
+    let sanity_check = Command::new("gh").arg("--version").output();
+    if sanity_check.is_err() {
+        return Err(anyhow::anyhow!(
+            "The github `gh` client is missing and needs to be installed and configured with a token."
+        ));
+    }
 
-    desired_labels.insert(GhLabel {
-        name: "C-tracking-issue".to_string(),
-        color: "f5f1fd".to_string(),
-    });
+    // Make sure we are not about to create a pile of issues on the wrong repository.
+    if commit {
+        check_expected_repository(repository, options)?;
+    }
 
-    desired_labels.insert(GhLabel {
-        name: FLAGSHIP_LABEL.to_string(),
-        color: "5319E7".to_string(),
-    });
+    // Check the goal metadata up front so that we fail before touching the repository.
+    let mut problems = vec![];
+    for path in paths {
+        problems.extend(goal::validate_metadata_in_dir(
+            path,
+            Some(repository),
+            sync_options.strict,
+        )?);
+    }
+    if !problems.is_empty() {
+        for problem in &problems {
+            eprintln!("{problem}");
+        }
+        return Err(ClassifiedError::new(
+            ErrorKind::Validation,
+            format!("found {} metadata problem(s), aborting", problems.len()),
+        )
+        .into());
+    }
 
-    for existing_label in GhLabel::list(repository)? {
-        desired_labels.remove(&existing_label);
+    // The goals as they are before the sync, for the checks below. The loop reloads them,
+    // as executing the actions links goal documents to their new tracking issues.
+    let mut goal_documents = vec![];
+    for path in paths {
+        goal_documents.extend(goal::goals_in_dir(path, sync_options.strict)?);
     }
 
-    Ok(desired_labels
-        .into_iter()
-        .map(|label| GithubAction::CreateLabel { label })
-        .collect())
+    let mut warnings = Warnings::default();
+    for document in &goal_documents {
+        warn_if_lacking_tasks(document, &mut warnings);
+        warn_if_foreign_tracking_issue(document, &mut warnings);
+    }
+    if sync_options.require_team_members {
+        check_team_members(&goal_documents)?;
+    }
+
+    // Compare the task counts against the previous run (if requested). This is done once,
+    // before the loop below, so that any regression comments are only posted once.
+    let mut regression_actions = match &options.progress_state {
+        Some(state_path) => {
+            check_task_regressions(&goal_documents, state_path, comment_on_regression, commit)?
+        }
+        None => BTreeSet::new(),
+    };
+
+    // Bodies are only force-updated on the first pass, or we would never stop looping.
+    let mut sync_options = sync_options;
+
+    // Hacky but works: we loop because after creating the issue, we sometimes have additional sync to do,
+    // and it's easier this way.
+    loop {
+        let timeframes = load_timeframes(paths, timeframe, sync_options.strict)?;
+
+        // In a dry run, print each action as soon as it is planned rather than
+        // waiting for all the issues to be fetched.
+        let stream = match (commit || summary, detailed) {
+            (true, _) => Stream::Off,
+            (false, false) => Stream::Summary,
+            (false, true) => Stream::Detailed,
+        };
+        if stream != Stream::Off {
+            eprintln!("Actions to be executed:");
+            for action in &regression_actions {
+                print_action(action, stream == Stream::Detailed)?;
+            }
+        }
+
+        let mut actions = plan_timeframes(
+            github,
+            repository,
+            &timeframes,
+            label_options,
+            sync_options,
+            &mut |action| match stream {
+                Stream::Off => Ok(()),
+                Stream::Summary => print_action(action, false),
+                Stream::Detailed => print_action(action, true),
+            },
+        )?;
+        actions.extend(std::mem::take(&mut regression_actions));
+
+        if dry_run_apply_local {
+            let (local_actions, skipped) = split_local_actions(actions);
+            eprintln!();
+            eprintln!(
+                "Applying {} local link(s), skipping {skipped} github action(s).",
+                local_actions.len()
+            );
+            for action in local_actions {
+                action.execute(github, repository)?;
+            }
+            return Ok(());
+        }
+
+        if !commit {
+            if summary {
+                print_summary(&actions);
+            }
+            eprintln!();
+            eprintln!("{} action(s) planned.", actions.len());
+            if !actions.is_empty() {
+                eprintln!("Use `--commit` to execute the actions.");
+            }
+            return Ok(());
+        }
+
+        if actions.is_empty() {
+            return Ok(());
+        }
+
+        execute_actions(github, repository, actions, options)?;
+        sync_options.force_update_body = false;
+    }
 }
 
-/// Initializes the required `T-<team>` labels on the repository.
-/// Warns if the labels are found with wrong color.
-fn initialize_issues<'doc>(
-    repository: &Repository,
-    timeframe: &str,
-    goal_documents: &'doc [GoalDocument],
-) -> anyhow::Result<BTreeSet<GithubAction<'doc>>> {
-    // the set of issues we want to exist
-    let desired_issues: BTreeSet<GithubIssue> = goal_documents
-        .iter()
-        .map(|goal_document| issue(timeframe, goal_document))
-        .collect::<anyhow::Result<_>>()?;
+/// The actions that only edit local files (linking goal documents to their tracking issue
+/// and writing back their owners), and the number of the others, which would change something on github.
+fn split_local_actions(actions: BTreeSet<GithubAction<'_>>) -> (BTreeSet<GithubAction<'_>>, usize) {
+    let (local, github): (BTreeSet<_>, BTreeSet<_>) = actions.into_iter().partition(|action| {
+        matches!(
+            action,
+            GithubAction::LinkToTrackingIssue { .. }
+                | GithubAction::SyncOwnersFromIssue { .. }
+                | GithubAction::RetitleGoal { .. }
+        )
+    });
+    (local, github.len())
+}
 
-    // the list of existing issues in the target milestone
-    let milestone_issues = list_issues_in_milestone(repository, timeframe)?;
+/// Compare the task counts of each goal with a tracking issue against those recorded in `state_path`,
+/// warning about any goal whose number of completed tasks went down.
+/// If `comment` is set, returns actions to post a comment on the affected tracking issues.
+/// If `save` is set, the current counts are written back to `state_path`.
+fn check_task_regressions(
+    goal_documents: &[GoalDocument],
+    state_path: &Path,
+    comment: bool,
+    save: bool,
+) -> anyhow::Result<BTreeSet<GithubAction<'static>>> {
+    let previous: BTreeMap<u64, TaskCounts> = if state_path.exists() {
+        let text = std::fs::read_to_string(state_path)
+            .with_context(|| format!("reading `{}`", state_path.display()))?;
+        serde_json::from_str(&text)
+            .with_context(|| format!("parsing `{}`", state_path.display()))?
+    } else {
+        BTreeMap::new()
+    };
 
+    let mut current = previous.clone();
     let mut actions = BTreeSet::new();
 
-    // Go through each of the issues we want to exist (derived from the goals defined in the target folder)
-    for desired_issue in desired_issues {
-        // Check if we already created a tracking issue...
-        //
-        let existing_issue = if let Some(tracking_issue) = desired_issue.tracking_issue {
-            // a. We first check if there is a declared tracking issue in the markdown file.
-            // If so, check if we've already loaded its data.
-            if let Some(issue) = milestone_issues
-                .iter()
-                .find(|issue| issue.number == tracking_issue.number)
-            {
-                // If so, reuse it to avoid latency.
-                Some(issue.clone())
-            } else {
-                // If not, load its information from the repository by number.
-                Some(fetch_issue(repository, tracking_issue.number)?)
-            }
-        } else {
-            // b. If the markdown does not have a declared tracking issue, then we can search through
-            // the issues in the milestone for one with the correct title.
-            // We could also do a fresh GH query for an issue with the desired title
-            // but that is slower.
-            //
-            // This addresses a kind of awkward gap in our handling-- when a new project goal
-            // is created, we first create an issue for it, then do a loop and execute again.
-            // This second time, we will find the issue with the known title, get its
-            // number, and put that number into the markdown.
-            milestone_issues
-                .iter()
-                .find(|issue| issue.title == desired_issue.title)
-                .cloned()
+    for goal_document in goal_documents {
+        let Some(tracking_issue) = &goal_document.metadata.tracking_issue else {
+            continue;
         };
 
-        match existing_issue {
-            Some(existing_issue) => {
-                if existing_issue.assignees != desired_issue.assignees {
-                    actions.insert(GithubAction::SyncAssignees {
-                        number: existing_issue.number,
-                        remove_owners: existing_issue
-                            .assignees
-                            .difference(&desired_issue.assignees)
-                            .cloned()
-                            .collect(),
-                        add_owners: desired_issue
-                            .assignees
-                            .difference(&existing_issue.assignees)
-                            .cloned()
-                            .collect(),
-                    });
-                }
-
-                if existing_issue.title != desired_issue.title {
-                    actions.insert(GithubAction::ChangeTitle {
-                        number: existing_issue.number,
-                        title: desired_issue.title,
-                    });
-                }
+        let counts = TaskCounts::of(goal_document);
+
+        if let Some(old) = previous.get(&tracking_issue.number) {
+            if counts.completed < old.completed {
+                eprintln!(
+                    "warning: `{}` went from {}/{} to {}/{} completed tasks",
+                    goal_document.path.display(),
+                    old.completed,
+                    old.total,
+                    counts.completed,
+                    counts.total,
+                );
 
-                if existing_issue.milestone.as_ref().map(|m| m.title.as_str()) != Some(timeframe) {
-                    actions.insert(GithubAction::ChangeMilestone {
-                        number: existing_issue.number,
-                        milestone: timeframe.to_string(),
-                    });
+                if comment {
                     actions.insert(GithubAction::Comment {
-                        number: existing_issue.number,
-                        body: format!("{CONTINUING_GOAL_PREFIX} {timeframe}",),
+                        number: tracking_issue.number,
+                        body: format!(
+                            "{TASK_REGRESSION_PREFIX} from {}/{} to {}/{}. \
+                             Was a task unchecked or the plan rewritten?",
+                            old.completed, old.total, counts.completed, counts.total,
+                        ),
                     });
                 }
+            }
+        }
 
-                if !existing_issue.was_locked() {
-                    actions.insert(GithubAction::LockIssue {
-                        number: existing_issue.number,
-                    });
-                    actions.insert(GithubAction::Comment {
-                        number: existing_issue.number,
-                        body: LOCK_TEXT.to_string(),
-                    });
-                }
+        current.insert(tracking_issue.number, counts);
+    }
 
-                let link_text = goal_document_link(timeframe, &desired_issue.goal_document);
-                if !existing_issue.body.contains(&link_text) {
-                    // Let's update the tracking issue to the new goal description, while keeping
-                    // the old text in case we need it. It's surprisingly hard to get out of GH
-                    // otherwise.
-                    let body = format!(
-                        "{desired_body}\n---\nNote: we have updated the body to match the \
-                         {timeframe} goal. Your original text is preserved below. \
-                         <details>\n{existing_body}\n</details>",
-                        desired_body = desired_issue.body,
-                        existing_body = existing_issue.body,
-                    );
-                    actions.insert(GithubAction::UpdateIssueBody {
-                        number: existing_issue.number,
-                        body,
-                    });
-                }
+    if save {
+        std::fs::write(state_path, serde_json::to_string_pretty(&current)?)
+            .with_context(|| format!("writing `{}`", state_path.display()))?;
+    }
 
-                let issue_id = IssueId::new(repository.clone(), existing_issue.number);
-                if desired_issue.tracking_issue != Some(&issue_id) {
-                    actions.insert(GithubAction::LinkToTrackingIssue {
-                        goal_document: desired_issue.goal_document,
-                        issue_id,
-                    });
+    Ok(actions)
+}
+
+/// Execute `actions` one by one, pausing `sleep` milliseconds between them.
+/// Errors are reported but do not stop execution; we only fail if every action failed.
+pub(crate) fn execute_actions(
+    github: &dyn GithubBackend,
+    repository: &Repository,
+    actions: BTreeSet<GithubAction<'_>>,
+    options: &IssuesOptions,
+) -> anyhow::Result<()> {
+    let plain = options.plain_output();
+    let total = actions.len();
+
+    if !plain {
+        progress_bar::init_progress_bar(total);
+        progress_bar::set_progress_bar_action(
+            "Executing",
+            progress_bar::Color::Blue,
+            progress_bar::Style::Bold,
+        );
+    }
+    let mut actions_file = match &options.output_actions_file {
+        Some(path) => Some(
+            std::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)
+                .with_context(|| format!("opening `{}`", path.display()))?,
+        ),
+        None => None,
+    };
+
+    let mut success = 0;
+    let mut last_failure = None;
+    for (index, action) in actions.into_iter().enumerate() {
+        let description = action.to_string();
+        let issue = action.issue_number();
+        let serialized = action.to_json();
+        if plain {
+            eprintln!("[{}/{total}] {description}", index + 1);
+        } else {
+            progress_bar::print_progress_bar_info(
+                "Action",
+                &description,
+                progress_bar::Color::Green,
+                progress_bar::Style::Bold,
+            );
+        }
+        let result = action.execute(github, repository);
+
+        // Log each action as soon as it is done, so that an interrupted run leaves an accurate record.
+        if let Some(file) = &mut actions_file {
+            log_action(file, repository, serialized, &result)?;
+        }
+
+        if let Err(e) = result {
+            if error::json_errors() {
+                JsonError {
+                    kind: ErrorKind::Github,
+                    action: Some(&description),
+                    issue,
+                    message: format!("{e:#}"),
                 }
+                .print();
+            } else if plain {
+                eprintln!("[{}/{total}] error: {e}", index + 1);
+            } else {
+                progress_bar::print_progress_bar_info(
+                    "Error",
+                    &format!("{}", e),
+                    progress_bar::Color::Red,
+                    progress_bar::Style::Bold,
+                );
             }
+            last_failure = Some((description, issue));
+        } else {
+            success += 1;
+        }
+        if !plain {
+            progress_bar::inc_progress_bar();
+        }
 
-            None => {
-                actions.insert(GithubAction::CreateIssue {
-                    issue: desired_issue,
-                });
-            }
+        std::thread::sleep(Duration::from_millis(options.sleep));
+    }
+    if !plain {
+        progress_bar::finalize_progress_bar();
+    }
+    if success == 0 {
+        let mut error = ClassifiedError::new(ErrorKind::Github, "all actions failed, aborting");
+        if let Some((action, issue)) = last_failure {
+            error.action = Some(action);
+            error.issue = issue;
         }
+        return Err(error.into());
     }
+    Ok(())
+}
 
-    Ok(actions)
+/// One line of the `--output-actions-file` log.
+#[derive(Serialize)]
+struct ActionLogEntry {
+    timestamp: String,
+    repository: String,
+    success: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+    action: serde_json::Value,
+}
+
+fn log_action(
+    file: &mut impl std::io::Write,
+    repository: &Repository,
+    action: serde_json::Value,
+    result: &anyhow::Result<()>,
+) -> anyhow::Result<()> {
+    let entry = ActionLogEntry {
+        timestamp: chrono::Utc::now().to_rfc3339(),
+        repository: repository.to_string(),
+        success: result.is_ok(),
+        error: result.as_ref().err().map(|e| format!("{e:#}")),
+        action,
+    };
+    let mut line = serde_json::to_string(&entry)?;
+    line.push('\n');
+    file.write_all(line.as_bytes())?;
+    file.flush()?;
+    Ok(())
 }
 
-fn issue<'doc>(timeframe: &str, document: &'doc GoalDocument) -> anyhow::Result<GithubIssue<'doc>> {
-    let mut assignees = BTreeSet::default();
-    for username in document.metadata.owner_usernames() {
-        if let Some(data) = get_person_data(username)? {
-            assignees.insert(data.github_username.clone());
+/// Whether [`generate_issues`] prints each action as soon as it is planned (in a dry run),
+/// and if so, whether with [`print_action`]'s details.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) enum Stream {
+    Off,
+    Summary,
+    Detailed,
+}
+
+/// Print the actions that would be executed with `--commit`.
+pub(crate) fn print_actions(
+    actions: &BTreeSet<GithubAction<'_>>,
+    options: &IssuesOptions,
+) -> anyhow::Result<()> {
+    eprintln!("Actions to be executed:");
+    if options.summary {
+        print_summary(actions);
+    } else {
+        for action in actions {
+            print_action(action, options.detailed)?;
         }
     }
+    eprintln!("");
+    eprintln!("Use `--commit` to execute the actions.");
+    Ok(())
+}
+
+/// How `--summary` groups the actions, in the order the groups are printed.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum ActionGroup {
+    NewGoals,
+    ContinuingGoals,
+    Edits,
+    Other,
+}
 
-    let mut labels = vec!["C-tracking-issue".to_string()];
-    if document.metadata.status.is_flagship {
-        labels.push("Flagship Goal".to_string());
+impl ActionGroup {
+    fn of(action: &GithubAction<'_>) -> Self {
+        match action {
+            GithubAction::CreateIssue { .. } => ActionGroup::NewGoals,
+            GithubAction::ChangeMilestone { .. }
+            | GithubAction::Comment { .. }
+            | GithubAction::ReopenIssue { .. } => ActionGroup::ContinuingGoals,
+            GithubAction::ChangeTitle { .. }
+            | GithubAction::UpdateIssueBody { .. }
+            | GithubAction::SyncAssignees { .. }
+            | GithubAction::AddLabel { .. }
+            | GithubAction::SetIssueType { .. } => ActionGroup::Edits,
+            GithubAction::CreateMilestone { .. }
+            | GithubAction::SetMilestoneDue { .. }
+            | GithubAction::CreateLabel { .. }
+            | GithubAction::UpdateLabelColor { .. }
+            | GithubAction::UpdateComment { .. }
+            | GithubAction::DeleteComment { .. }
+            | GithubAction::LockIssue { .. }
+            | GithubAction::LinkToTrackingIssue { .. }
+            | GithubAction::SyncOwnersFromIssue { .. }
+            | GithubAction::RetitleGoal { .. }
+            | GithubAction::CloseIssue { .. }
+            | GithubAction::AddToProject { .. } => ActionGroup::Other,
+        }
     }
-    for team in document.teams_with_asks() {
-        labels.push(team.gh_label());
+
+    fn title(self) -> &'static str {
+        match self {
+            ActionGroup::NewGoals => "New goals",
+            ActionGroup::ContinuingGoals => "Continuing goals",
+            ActionGroup::Edits => "Edits",
+            ActionGroup::Other => "Other",
+        }
     }
+}
 
-    Ok(GithubIssue {
-        title: document.metadata.title.clone(),
-        assignees,
-        body: issue_text(timeframe, document)?,
-        labels,
-        tracking_issue: document.metadata.tracking_issue.as_ref(),
-        goal_document: document,
-    })
+/// The `actions` in each (non-empty) [`ActionGroup`], in execution order within a group.
+fn group_actions<'a, 'doc>(
+    actions: &'a BTreeSet<GithubAction<'doc>>,
+) -> BTreeMap<ActionGroup, Vec<&'a GithubAction<'doc>>> {
+    let mut groups: BTreeMap<_, Vec<_>> = BTreeMap::new();
+    for action in actions {
+        groups
+            .entry(ActionGroup::of(action))
+            .or_default()
+            .push(action);
+    }
+    groups
 }
 
-fn goal_document_link(timeframe: &str, document: &GoalDocument) -> String {
-    let goal_file = document.link_path.file_stem().unwrap().to_str().unwrap();
-    format!("[{timeframe}/{goal_file}](https://rust-lang.github.io/rust-project-goals/{timeframe}/{goal_file}.html)")
+/// Print the actions grouped for `--summary`, one line each under a heading with their count.
+fn print_summary(actions: &BTreeSet<GithubAction<'_>>) {
+    for (group, actions) in group_actions(actions) {
+        eprintln!();
+        eprintln!("{} ({}):", group.title(), actions.len());
+        for action in actions {
+            eprintln!("* {action}");
+        }
+    }
 }
 
-fn issue_text(timeframe: &str, document: &GoalDocument) -> anyhow::Result<String> {
-    let mut tasks = vec![];
-    for goal_plan in &document.goal_plans {
-        tasks.extend(task_items(goal_plan)?);
+/// Print a single action that would be executed with `--commit`.
+/// If `detailed` is set, issues to be created are shown in full.
+fn print_action(action: &GithubAction<'_>, detailed: bool) -> anyhow::Result<()> {
+    eprintln!("* {action}");
+
+    if let (GithubAction::CreateIssue { issue }, true) = (action, detailed) {
+        for line in issue_details(issue) {
+            eprintln!("    {line}");
+        }
     }
 
-    let teams = document
-        .teams_with_asks()
-        .iter()
-        .map(|team| team.name_and_link())
-        .collect::<Vec<_>>();
+    // Linking is a local edit, so we can show exactly what would change.
+    if let GithubAction::LinkToTrackingIssue {
+        goal_document,
+        issue_id,
+    } = action
+    {
+        for line in goal_document.link_issue_preview(issue_id)? {
+            eprintln!("    {line}");
+        }
+    }
+    if let GithubAction::SyncOwnersFromIssue {
+        goal_document,
+        remove_owners,
+        add_owners,
+        ..
+    } = action
+    {
+        for line in goal_document.sync_owners_preview(remove_owners, add_owners)? {
+            eprintln!("    {line}");
+        }
+    }
+    if let GithubAction::RetitleGoal {
+        goal_document,
+        title,
+        ..
+    } = action
+    {
+        for line in goal_document.retitle_preview(title)? {
+            eprintln!("    {line}");
+        }
+    }
+    Ok(())
+}
 
-    Ok(format!(
-        r##"
-| Metadata         | |
-| --------         | --- |
-| Point of contact | {poc} |
-| Team(s)          | {teams} |
-| Goal document    | {goaldocument} |
+/// The lines that `--detailed` shows for an issue to be created.
+fn issue_details(issue: &GithubIssue<'_>) -> Vec<String> {
+    let none = |s: String| {
+        if s.is_empty() {
+            "(none)".to_string()
+        } else {
+            s
+        }
+    };
+    let mut lines = vec![
+        format!("labels: {}", none(commas(&issue.labels))),
+        format!(
+            "assignees: {}",
+            none(commas(issue.assignees.iter().map(|a| format!("@{a}"))))
+        ),
+        format!("milestone: {}", none(issue.milestone.clone())),
+        "body:".to_string(),
+    ];
+    lines.extend(issue.body.lines().map(|line| format!("  {line}")));
+    lines
+}
 
-## Summary
+/// Close out the tracking issues for `milestone` at the end of the goal period:
+/// each open tracking issue gets the [`COMPLETED_LABEL`], a closing comment, and is closed.
+/// If `only_complete` is set, only goals whose plan items (from `src/<milestone>`) are all complete are archived.
+pub fn archive_milestone(
+    github: &dyn GithubBackend,
+    repository: &Repository,
+    milestone: &str,
+    only_complete: bool,
+    label_options: &LabelOptions,
+    options: &IssuesOptions,
+    strict: bool,
+) -> anyhow::Result<()> {
+    if options.commit {
+        check_expected_repository(repository, options)?;
+    }
 
-{summary}
+    let goal_documents = if only_complete {
+        goal::goals_in_dir(&PathBuf::from("src").join(milestone), strict)?
+    } else {
+        vec![]
+    };
 
-## Tasks and status
+    let mut actions = BTreeSet::new();
+    let tracking_issue_label = label_options.tracking_issue_label();
 
-{tasks}
+    let completed_label = GhLabel {
+        name: COMPLETED_LABEL.to_string(),
+        color: "0e8a16".to_string(),
+    };
+    if !github.list_labels(repository)?.contains(&completed_label) {
+        actions.insert(GithubAction::CreateLabel {
+            label: completed_label,
+        });
+    }
 
-[Team]: https://img.shields.io/badge/Team%20ask-red
-"##,
-        poc = &document.metadata.owner_usernames().join(", "),
-        teams = teams.join(", "),
-        summary = document.summary,
-        tasks = tasks.join("\n"),
-        goaldocument = goal_document_link(timeframe, document),
-    ))
-}
+    for issue in github.list_issues_in_milestone(repository, milestone)? {
+        if !issue.has_label(&tracking_issue_label) || issue.state == GithubIssueState::Closed {
+            continue;
+        }
 
-fn task_items(goal_plan: &GoalPlan) -> anyhow::Result<Vec<String>> {
-    use std::fmt::Write;
+        if only_complete {
+            let goal_document = goal_documents.iter().find(|doc| {
+                doc.metadata
+                    .tracking_issue
+                    .as_ref()
+                    .is_some_and(|id| id.number == issue.number)
+            });
+            let Some(goal_document) = goal_document else {
+                eprintln!(
+                    "warning: no goal document in `src/{milestone}` tracks issue #{}, skipping",
+                    issue.number
+                );
+                continue;
+            };
+            let all_complete = goal_document
+                .goal_plans
+                .iter()
+                .flat_map(|plan| &plan.plan_items)
+                .all(|item| item.is_complete());
+            if !all_complete {
+                continue;
+            }
+        }
 
-    let mut tasks = vec![];
+        let progress = match checkboxes(&issue) {
+            Progress::Tracked { completed, total } => {
+                format!("{completed} of {total} tasks were completed.")
+            }
+            Progress::Binary { .. } | Progress::Error { .. } => String::new(),
+        };
 
-    if let Some(title) = &goal_plan.subgoal {
-        tasks.push(format!("### {}", **title));
+        actions.insert(GithubAction::AddLabel {
+            number: issue.number,
+            label: COMPLETED_LABEL.to_string(),
+        });
+        actions.insert(GithubAction::Comment {
+            number: issue.number,
+            body: format!("{ARCHIVED_GOAL_PREFIX} (milestone {milestone}) {progress}")
+                .trim()
+                .to_string(),
+        });
+        actions.insert(GithubAction::CloseIssue {
+            number: issue.number,
+        });
     }
 
-    for plan_item in &goal_plan.plan_items {
-        let mut description = format!(
-            "* {box} {text}",
-            box = if plan_item.is_complete() { "[x]" } else { "[ ]" },
-            text = plan_item.text
-        );
+    if actions.is_empty() {
+        eprintln!("No tracking issues to archive in milestone {milestone}.");
+        Ok(())
+    } else if options.commit {
+        execute_actions(github, repository, actions, options)
+    } else {
+        print_actions(&actions, options)
+    }
+}
 
-        if let Some(parsed_owners) = plan_item.parse_owners()? {
-            match parsed_owners {
-                ParsedOwners::TeamAsks(asks) => {
-                    let teams: Vec<String> = asks.iter().map(|ask| ask.name_and_link()).collect();
+/// Regenerate the body of every tracking issue of the goals in `src/<milestone>` from the
+/// current body template, without touching titles, labels, assignees or anything else.
+/// Text edited on github since the last sync is preserved below the new body, as with
+/// `issues --force-update-body`.
+pub(crate) fn regenerate_all_bodies(
+    github: &dyn GithubBackend,
+    repository: &Repository,
+    milestone: &str,
+    period_by: PeriodBy,
+    assignee_limit: usize,
+    options: &IssuesOptions,
+    strict: bool,
+) -> anyhow::Result<()> {
+    if options.commit {
+        check_expected_repository(repository, options)?;
+    }
 
-                    write!(description, " ({} ![Team][])", teams.join(", "))?;
-                }
+    let goal_documents = goal::goals_in_dir(&PathBuf::from("src").join(milestone), strict)?;
+    let actions = plan_bodies(
+        github,
+        repository,
+        milestone,
+        &goal_documents,
+        period_by,
+        assignee_limit,
+        strict,
+    )?;
+    if actions.is_empty() {
+        eprintln!("No tracking issues to regenerate in milestone {milestone}.");
+        Ok(())
+    } else if options.commit {
+        execute_actions(github, repository, actions, options)
+    } else {
+        print_actions(&actions, options)
+    }
+}
 
-                ParsedOwners::Usernames(usernames) => {
-                    write!(description, " ({})", usernames.join(", "))?;
-                }
-            }
+/// Compare `repository` against the repository given with `--confirm-repo`
+/// and the `expected_repository` from the configuration.
+/// Mismatches are errors unless `--force` was given, in which case they are only reported.
+pub(crate) fn check_expected_repository(
+    repository: &Repository,
+    options: &IssuesOptions,
+) -> anyhow::Result<()> {
+    let configured = match &Configuration::get().expected_repository {
+        Some(r) => Some(
+            r.parse::<Repository>()
+                .context("invalid `expected_repository` in configuration")?,
+        ),
+        None => None,
+    };
+
+    let problem = match (&options.confirm_repo, &configured) {
+        (Some(confirmed), _) if confirmed != repository => Some(format!(
+            "`--confirm-repo {confirmed}` does not match the target repository `{repository}`"
+        )),
+        (_, Some(expected)) if expected != repository => Some(format!(
+            "the target repository `{repository}` does not match the configured \
+             `expected_repository` `{expected}`"
+        )),
+        (None, None) => Some(format!(
+            "no `expected_repository` configured; pass `--confirm-repo {repository}` to proceed"
+        )),
+        _ => None,
+    };
+
+    match problem {
+        Some(problem) if options.force => {
+            eprintln!("warning: {problem} (continuing because of `--force`)");
+            Ok(())
         }
+        Some(problem) => anyhow::bail!("{problem}"),
+        None => Ok(()),
+    }
+}
 
-        tasks.push(description);
+/// Warn about accepted goals without any task, see [`GoalDocument::lacks_tasks`].
+/// Proposed goals are still being written, and rejected ones will never get tasks.
+pub(crate) fn warn_if_lacking_tasks(document: &GoalDocument, warnings: &mut Warnings) {
+    if document.metadata.status.acceptance == AcceptanceStatus::Accepted && document.lacks_tasks() {
+        warnings.warn(
+            "goal without tasks",
+            format!(
+                "`{}`: goal has no tasks (add a `{}` metadata row with the value `true` if this is intended)",
+                document.path.display(),
+                goal::TASKS_INTENTIONALLY_EMPTY_ROW,
+            ),
+        );
     }
+}
 
-    Ok(tasks)
+/// Warn if the tracking issue of `document` is not in the `expected_repository` of the
+/// configuration (e.g., it was copied from a fork).
+pub(crate) fn warn_if_foreign_tracking_issue(document: &GoalDocument, warnings: &mut Warnings) {
+    let (Some(issue), Some(expected)) = (
+        &document.metadata.tracking_issue,
+        &Configuration::get().expected_repository,
+    ) else {
+        return;
+    };
+    if issue.repository.to_string() != *expected {
+        warnings.warn(
+            "tracking issue in another repository",
+            format!(
+                "tracking issue {issue} of `{}` is not in the expected repository `{expected}`",
+                document.path.display()
+            ),
+        );
+    }
 }
 
-fn teams_with_asks(goal_documents: &[GoalDocument]) -> BTreeSet<&'static TeamName> {
-    goal_documents
-        .iter()
-        .flat_map(|g| &g.team_asks)
-        .flat_map(|ask| &ask.teams)
-        .copied()
-        .collect()
+/// Fail if some owner of the goals is not a member of any Rust team, listing each such owner.
+pub(crate) fn check_team_members(goal_documents: &[GoalDocument]) -> anyhow::Result<()> {
+    let outsiders = non_team_member_owners(goal_documents, team::is_team_member)?;
+    if outsiders.is_empty() {
+        return Ok(());
+    }
+
+    for (path, username) in &outsiders {
+        eprintln!(
+            "{}: owner `{username}` is not a member of any Rust team",
+            path.display()
+        );
+    }
+    Err(ClassifiedError::new(
+        ErrorKind::Validation,
+        format!(
+            "found {} owner(s) who are not team members",
+            outsiders.len()
+        ),
+    )
+    .into())
 }
 
-impl Display for GithubAction<'_> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            GithubAction::CreateLabel {
-                label: GhLabel { name, color },
-            } => {
-                write!(f, "create label `{}` with color `{}`", name, color)
-            }
-            GithubAction::CreateIssue { issue } => {
-                write!(f, "create issue \"{}\"", issue.title)
-            }
-            GithubAction::ChangeMilestone { number, milestone } => {
-                write!(f, "update issue #{} milestone to \"{}\"", number, milestone)
-            }
-            GithubAction::ChangeTitle { number, title } => {
-                write!(f, "update issue #{} title to \"{}\"", number, title)
-            }
-            GithubAction::Comment { number, body } => {
-                write!(f, "post comment on issue #{}: \"{}\"", number, body)
-            }
-            GithubAction::UpdateIssueBody { number, body: _ } => {
-                write!(f, "update the body on issue #{} for new milestone", number)
-            }
-            GithubAction::SyncAssignees {
-                number,
-                remove_owners,
-                add_owners,
-            } => {
-                write!(
-                    f,
-                    "sync issue #{} ({})",
-                    number,
-                    remove_owners
-                        .iter()
-                        .map(|s| format!("-{}", s))
-                        .chain(add_owners.iter().map(|s| format!("+{}", s)))
-                        .collect::<Vec<_>>()
-                        .join(", ")
-                )
-            }
-            GithubAction::LockIssue { number } => {
-                write!(f, "lock issue #{}", number)
-            }
-            GithubAction::LinkToTrackingIssue {
-                goal_document,
-                issue_id,
-            } => {
-                write!(
-                    f,
-                    "link issue {issue_id:?} to the markdown document at {}",
-                    goal_document.path.display()
-                )
+/// The owners of each goal (with the path of its document) for whom `is_member` is false.
+fn non_team_member_owners(
+    goal_documents: &[GoalDocument],
+    is_member: impl Fn(&str) -> anyhow::Result<bool>,
+) -> anyhow::Result<Vec<(&Path, &str)>> {
+    let mut outsiders = vec![];
+    for goal_document in goal_documents {
+        for username in goal_document.metadata.owner_usernames() {
+            if !is_member(username)? {
+                outsiders.push((goal_document.path.as_path(), username));
             }
         }
     }
+    Ok(outsiders)
 }
 
-impl GithubAction<'_> {
-    pub fn execute(self, repository: &Repository, timeframe: &str) -> anyhow::Result<()> {
-        match self {
-            GithubAction::CreateLabel { label } => {
-                label.create(repository)?;
-                Ok(())
-            }
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use rust_project_goals::{
+        gh::{
+            backend::MockGithub,
+            issue_id::IssueId,
+            issues::{ExistingGithubIssue, TRACKING_ISSUE_LABEL},
+            milestone::GhMilestone,
+        },
+        goal::test_support::GoalText,
+    };
+
+    use super::*;
+
+    fn goal_without_asks() -> GoalDocument {
+        GoalText::new("Do it ourselves").document("src/2025h1/ourselves.md")
+    }
+
+    fn repository() -> Repository {
+        Repository::new("rust-lang", "rust-project-goals")
+    }
+
+    /// A tracking issue to create for `document`, with `body` as is.
+    fn desired_issue<'doc>(document: &'doc GoalDocument, body: &str) -> GithubIssue<'doc> {
+        GithubIssue {
+            title: document.metadata.title.clone(),
+            assignees: BTreeSet::new(),
+            body: body.to_string(),
+            labels: vec![TRACKING_ISSUE_LABEL.to_string()],
+            milestone: "2025h1".to_string(),
+            issue_type: None,
+            content_hash: String::new(),
+            tasks_comment: None,
+            tracking_issue: None,
+            goal_document: document,
+        }
+    }
 
+    fn existing_issue(body: &str) -> ExistingGithubIssue {
+        ExistingGithubIssue {
+            number: 22,
+            title: "Do it ourselves".to_string(),
+            assignees: BTreeSet::new(),
+            comments: vec![],
+            body: body.to_string(),
+            state: GithubIssueState::Open,
+            labels: vec![],
+            milestone: Some(GhMilestone {
+                number: 1,
+                title: "2025h1".to_string(),
+                description: String::new(),
+                due_on: None,
+            }),
+        }
+    }
+
+    /// Execute `actions` against `github`.
+    fn execute(github: &MockGithub, actions: BTreeSet<GithubAction<'_>>) {
+        for action in actions {
+            action.execute(github, &repository()).unwrap();
+        }
+    }
+
+    #[test]
+    fn owners_outside_teams_are_listed() {
+        let document = GoalText::new("Do it ourselves")
+            .row("Additional owners", "@outsider, @Member")
+            .document("src/2025h1/ourselves.md");
+        let documents = [document];
+        let outsiders = non_team_member_owners(&documents, |username| {
+            Ok(["@owner", "@member"].contains(&username.to_lowercase().as_str()))
+        })
+        .unwrap();
+        assert_eq!(
+            outsiders,
+            [(Path::new("src/2025h1/ourselves.md"), "@outsider")]
+        );
+    }
+
+    #[test]
+    fn summary_groups_new_continuing_and_edited_goals() {
+        let document = goal_without_asks();
+        let actions = BTreeSet::from([
             GithubAction::CreateIssue {
-                issue:
-                    GithubIssue {
-                        title,
-                        assignees,
-                        body,
-                        labels,
-                        tracking_issue: _,
-                        goal_document: _,
-                    },
-            } => {
-                create_issue(repository, &body, &title, &labels, &assignees, timeframe)?;
-
-                // Note: the issue is not locked, but we will reloop around later.
-
-                Ok(())
-            }
+                issue: desired_issue(&document, "Body"),
+            },
+            GithubAction::ChangeMilestone {
+                number: 1,
+                milestone: "2025h1".to_string(),
+                period_by: PeriodBy::Milestone,
+                previous: vec![],
+                comment: None,
+            },
+            GithubAction::ChangeTitle {
+                number: 2,
+                title: "New title".to_string(),
+            },
+            GithubAction::LockIssue { number: 2 },
+        ]);
+
+        let groups = group_actions(&actions);
+        let counts: Vec<_> = groups
+            .iter()
+            .map(|(group, actions)| (group.title(), actions.len()))
+            .collect();
+        assert_eq!(
+            counts,
+            [
+                ("New goals", 1),
+                ("Continuing goals", 1),
+                ("Edits", 1),
+                ("Other", 1)
+            ]
+        );
+        assert_eq!(
+            groups[&ActionGroup::Edits][0].to_string(),
+            "update issue #2 title to \"New title\""
+        );
+    }
 
-            GithubAction::ChangeMilestone { number, milestone } => {
-                change_milestone(repository, number, &milestone)?;
-                Ok(())
-            }
+    #[test]
+    fn table_of_contents_links_to_headings() {
+        let text = "# Summary\n\n## What's `new`?\n\n#### Too deep\n\n```\n# not a heading\n```\n\n# Summary\n";
+        assert_eq!(
+            table_of_contents(text),
+            "**Contents**\n\n\
+             * [Summary](#summary)\n\
+             \x20 * [What's new?](#whats-new)\n\
+             * [Summary](#summary-1)\n"
+        );
+        assert_eq!(table_of_contents("No headings"), "");
+    }
 
-            GithubAction::ChangeTitle { number, title } => {
-                change_title(repository, number, &title)?;
-                Ok(())
-            }
+    #[test]
+    fn rfc_is_split_at_second_level_headings() {
+        let text = "# RFC\n\nIntro.\n\n## Summary\n\nFirst.\n\n```\n## not a section\n```\n\n## Summary\n\nSecond.\n";
+        assert_eq!(
+            split_sections(text),
+            vec![
+                (
+                    "introduction".to_string(),
+                    "# RFC\n\nIntro.\n\n".to_string()
+                ),
+                (
+                    "Summary".to_string(),
+                    "## Summary\n\nFirst.\n\n```\n## not a section\n```\n\n".to_string()
+                ),
+                ("Summary".to_string(), "## Summary\n\nSecond.\n".to_string()),
+            ]
+        );
 
-            GithubAction::Comment { number, body } => {
-                create_comment(repository, number, &body)?;
-                Ok(())
-            }
+        // Without an introduction, the first section comes first.
+        let sections = split_sections("## Motivation\n\nWhy.\n");
+        assert_eq!(sections.len(), 1);
+        assert_eq!(sections[0].0, "Motivation");
+    }
 
-            GithubAction::UpdateIssueBody { number, body } => {
-                update_issue_body(repository, number, &body)?;
-                Ok(())
-            }
+    #[test]
+    fn section_titles_are_slugified() {
+        assert_eq!(slugify("Summary"), "summary");
+        assert_eq!(slugify("What's `new`?"), "what-s-new");
+        assert_eq!(
+            slugify("  Goals -- 2025H1 (flagship)  "),
+            "goals-2025h1-flagship"
+        );
+        assert_eq!(slugify("Ünïcode & more"), "ünïcode-more");
+        assert_eq!(slugify("!!!"), "");
+    }
 
-            GithubAction::SyncAssignees {
-                number,
-                remove_owners,
-                add_owners,
-            } => {
-                // NOTE: Swallow errors here because sometimes people are not present in the org.
-                // We don't want to stop everything for that.
-                sync_assignees(repository, number, &remove_owners, &add_owners)?;
-                Ok(())
-            }
+    #[test]
+    fn relative_md_links_point_at_the_book() {
+        let rewrite = |text| rewrite_md_links(text, "https://book/2025h1");
+        assert_eq!(
+            rewrite("See [async](./async.md#summary) and [docs](https://docs.rs/a.md).\n"),
+            "See [async](https://book/2025h1/async.html#summary) and [docs](https://docs.rs/a.md).\n"
+        );
+        assert_eq!(
+            rewrite("A [titled](foo.md \"The (foo) goal\") link.\n"),
+            "A [titled](https://book/2025h1/foo.html \"The (foo) goal\") link.\n"
+        );
+        assert_eq!(
+            rewrite("A [parenthesized](a(b).md) path, and `[code](c.md)`.\n"),
+            "A [parenthesized](https://book/2025h1/a(b).html) path, and `[code](c.md)`.\n"
+        );
+        assert_eq!(
+            rewrite("A [reference][r] link.\n\n[r]: ../r.md \"Title\"\n"),
+            "A [reference][r] link.\n\n[r]: https://book/2025h1/../r.html \"Title\"\n"
+        );
+    }
+
+    #[test]
+    fn thumbs_up_reactions_acknowledge_asks() {
+        let github = MockGithub::new().with_issue(existing_issue("RFC"));
+        let text = "\n## Lang\n\n* [ ] @lead (required, lead)\n* [ ] member (optional)\n";
+        post_fcp_comment(&github, &repository(), 22, text).unwrap();
+        let comment = github.issues()[0].comments[0].clone();
+        let github = github
+            .with_reaction(&comment, "Lead", "+1")
+            .with_reaction(&comment, "member", "eyes");
+
+        let acknowledged = fcp_acknowledgements(&github, &repository(), 22).unwrap();
+        assert_eq!(acknowledged, BTreeSet::from(["lead".to_string()]));
+        assert_eq!(
+            checklist_line("@Lead (required, lead)", "Lead", &acknowledged),
+            "* [x] @Lead (required, lead) ✅"
+        );
+        assert_eq!(
+            checklist_line("member (optional)", "member", &acknowledged),
+            "* [ ] member (optional)"
+        );
+    }
+
+    #[test]
+    fn previews_do_not_mention_leads() {
+        assert_eq!(mention("lead", true), "@lead");
+        assert_eq!(mention("lead", false), "`@lead`");
+    }
+
+    #[test]
+    fn fcp_comment_is_updated_not_duplicated() {
+        let github = MockGithub::new().with_issue(existing_issue("RFC"));
+        let text = "\n## Lang\n\n* [ ] @lead (required, lead)\n* [ ] member (optional)\n";
+        post_fcp_comment(&github, &repository(), 22, text).unwrap();
+        post_fcp_comment(&github, &repository(), 22, text).unwrap();
+        assert_eq!(github.calls(), ["comment #22"]);
+
+        // An approver checks their box, then a new team is added to the checklist.
+        let comment = github.issues()[0].comments[0].clone();
+        let checked = comment.body.replace("* [ ] @lead", "* [x] @lead");
+        github
+            .update_comment(&repository(), &comment, &checked)
+            .unwrap();
+        let text = format!("{text}\n## Libs\n\n* [ ] @other (required, lead)\n");
+        post_fcp_comment(&github, &repository(), 22, &text).unwrap();
+
+        let comments = &github.issues()[0].comments;
+        assert_eq!(comments.len(), 1);
+        assert!(
+            comments[0].body.contains("* [x] @lead"),
+            "{}",
+            comments[0].body
+        );
+        assert!(
+            comments[0].body.contains("* [ ] @other"),
+            "{}",
+            comments[0].body
+        );
+        assert!(comments[0].body.ends_with(FCP_CHECKLIST_MARKER));
+    }
 
-            GithubAction::LockIssue { number } => lock_issue(repository, number),
+    #[test]
+    fn detailed_dry_run_shows_the_issue_to_create() {
+        let document = goal_without_asks();
+        let mut issue = desired_issue(&document, "Line one\nLine two");
+        issue.assignees.insert("owner".to_string());
+        let details = issue_details(&issue);
+        assert_eq!(
+            details[..4],
+            [
+                format!("labels: {TRACKING_ISSUE_LABEL}"),
+                "assignees: @owner".to_string(),
+                "milestone: 2025h1".to_string(),
+                "body:".to_string(),
+            ]
+        );
+        assert_eq!(details[4..6], ["  Line one", "  Line two"]);
+    }
 
+    #[test]
+    fn only_links_are_applied_locally() {
+        let document = goal_without_asks();
+        let actions = BTreeSet::from([
             GithubAction::LinkToTrackingIssue {
-                goal_document,
-                issue_id: number,
-            } => goal_document.link_issue(number),
-        }
+                goal_document: &document,
+                issue_id: IssueId::new(repository(), 22),
+            },
+            GithubAction::Comment {
+                number: 22,
+                body: "hello".to_string(),
+            },
+            GithubAction::LockIssue { number: 22 },
+        ]);
+        let (local, skipped) = split_local_actions(actions);
+        assert!(
+            matches!(
+                Vec::from_iter(&local)[..],
+                [GithubAction::LinkToTrackingIssue { issue_id, .. }] if issue_id.number == 22
+            ),
+            "{local:?}"
+        );
+        assert_eq!(skipped, 2);
+    }
+
+    #[test]
+    fn executed_actions_are_logged_as_json_lines() {
+        let mut log = vec![];
+        let action = GithubAction::Comment {
+            number: 3,
+            body: "hi".to_string(),
+        };
+        log_action(&mut log, &repository(), action.to_json(), &Ok(())).unwrap();
+        let action = GithubAction::CloseIssue { number: 3 };
+        let failure = Err(anyhow::anyhow!("no issue #3"));
+        log_action(&mut log, &repository(), action.to_json(), &failure).unwrap();
+
+        let lines: Vec<serde_json::Value> = String::from_utf8(log)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["success"], true);
+        assert_eq!(lines[0]["error"], serde_json::Value::Null);
+        assert_eq!(
+            lines[0]["action"],
+            json!({ "kind": "comment", "number": 3, "body": "hi" })
+        );
+        assert_eq!(lines[1]["success"], false);
+        assert_eq!(lines[1]["error"], "no issue #3");
+        assert_eq!(lines[1]["action"]["kind"], "close_issue");
+    }
+
+    #[test]
+    fn unchecked_task_is_reported_as_a_regression() {
+        let goal = |notes: &str| {
+            GoalText::new("Do it ourselves")
+                .row("Tracking issue", "[rust-lang/rust-project-goals#22]")
+                .task("Implementation", "@owner", notes)
+                .document("src/2025h1/ourselves.md")
+        };
+        let state_path = std::env::temp_dir().join(format!(
+            "unchecked_task_is_reported_as_a_regression-{}.json",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_file(&state_path);
+
+        // The task was checked off when the counts were last saved...
+        let checked = [goal("![Complete][]")];
+        assert!(check_task_regressions(&checked, &state_path, true, true)
+            .unwrap()
+            .is_empty());
+
+        // ...and has since been unchecked in the markdown.
+        let unchecked = [goal("")];
+        let actions = check_task_regressions(&unchecked, &state_path, true, true).unwrap();
+        let github = MockGithub::new().with_issue(existing_issue("body"));
+        execute(&github, actions);
+
+        let comments = &github.issues()[0].comments;
+        assert_eq!(comments.len(), 1, "{comments:?}");
+        assert!(comments[0].body.starts_with(TASK_REGRESSION_PREFIX));
+        assert!(comments[0].body.contains("from 1/1 to 0/1"));
+
+        // The lower count was saved, so the next pass is quiet.
+        assert!(check_task_regressions(&unchecked, &state_path, true, true)
+            .unwrap()
+            .is_empty());
+        std::fs::remove_file(&state_path).unwrap();
     }
 }
@@ -0,0 +1,173 @@
+use std::collections::BTreeSet;
+
+use rust_project_goals::gh::{backend::GithubBackend, issue_id::Repository, labels::GhLabel};
+
+/// Label put on the throwaway issue, created if the repository does not have it yet.
+const SELF_TEST_LABEL: &str = "rpg-selftest";
+
+/// Prefix of the title of the throwaway issue, so that it is easy to spot (and clean up by hand
+/// if a step failed).
+const SELF_TEST_PREFIX: &str = "[rpg-selftest]";
+
+const SELF_TEST_BODY: &str = "Temporary issue created by `cargo rpg self-test` to check that \
+     syncing tracking issues is allowed on this repository. It is closed right away.";
+
+/// Check that our github credentials allow the operations of a sync on `repository`, by
+/// creating a labeled throwaway issue, commenting on it, locking it, and closing it (then
+/// deleting it, if `delete` is set). Each operation is reported, and it is an error if any failed.
+pub(crate) fn self_test(
+    github: &dyn GithubBackend,
+    repository: &Repository,
+    delete: bool,
+) -> anyhow::Result<()> {
+    let results = run_steps(github, repository, delete);
+    for (step, result) in &results {
+        match result {
+            Ok(()) => eprintln!("ok      {step}"),
+            Err(e) => eprintln!("FAILED  {step}: {e:#}"),
+        }
+    }
+
+    let failed = results.iter().filter(|(_, result)| result.is_err()).count();
+    if failed > 0 {
+        anyhow::bail!(
+            "{failed} of {} operations failed on `{repository}`",
+            results.len()
+        );
+    }
+    eprintln!("All operations succeeded on `{repository}`.");
+    Ok(())
+}
+
+/// Run each step, going on after a failure so that we report as much as we can (and still
+/// clean up). Failing to create the label or the issue stops the test: we find the issue by
+/// its label, so an unlabeled one could not be found again to be closed.
+fn run_steps(
+    github: &dyn GithubBackend,
+    repository: &Repository,
+    delete: bool,
+) -> Vec<(String, anyhow::Result<()>)> {
+    let mut results = vec![];
+
+    let label = ensure_label(github, repository);
+    let label_failed = label.is_err();
+    results.push((format!("create label `{SELF_TEST_LABEL}`"), label));
+    if label_failed {
+        return results;
+    }
+
+    let title = format!(
+        "{SELF_TEST_PREFIX} permissions check {}",
+        chrono::Utc::now().format("%Y-%m-%dT%H:%M:%SZ")
+    );
+    let number = match create_issue(github, repository, &title) {
+        Ok(number) => number,
+        Err(e) => {
+            results.push((format!("create issue {title:?}"), Err(e)));
+            return results;
+        }
+    };
+    results.push((format!("create issue #{number} ({title:?})"), Ok(())));
+
+    results.push((
+        format!("comment on #{number}"),
+        github.create_comment(repository, number, "Checking that we can comment."),
+    ));
+    results.push((
+        format!("lock #{number}"),
+        github.lock_issue(repository, number),
+    ));
+    results.push((
+        format!("close #{number}"),
+        github.close_issue(repository, number),
+    ));
+    if delete {
+        results.push((
+            format!("delete #{number}"),
+            github.delete_issue(repository, number),
+        ));
+    }
+
+    results
+}
+
+fn ensure_label(github: &dyn GithubBackend, repository: &Repository) -> anyhow::Result<()> {
+    if github
+        .list_labels(repository)?
+        .iter()
+        .any(|label| label.name == SELF_TEST_LABEL)
+    {
+        return Ok(());
+    }
+    github.create_label(
+        repository,
+        &GhLabel {
+            name: SELF_TEST_LABEL.to_string(),
+            color: "ededed".to_string(),
+        },
+    )
+}
+
+/// Create the labeled throwaway issue, and find its number (which creating does not tell us).
+fn create_issue(
+    github: &dyn GithubBackend,
+    repository: &Repository,
+    title: &str,
+) -> anyhow::Result<u64> {
+    github.create_issue(
+        repository,
+        SELF_TEST_BODY,
+        title,
+        &[SELF_TEST_LABEL.to_string()],
+        &BTreeSet::new(),
+        "",
+    )?;
+    let issues = github.list_issues_with_label(repository, SELF_TEST_LABEL)?;
+    match issues
+        .iter()
+        .filter(|issue| issue.title == title)
+        .map(|issue| issue.number)
+        .max()
+    {
+        Some(number) => Ok(number),
+        None => anyhow::bail!("created issue {title:?}, but cannot find it"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_project_goals::gh::backend::MockGithub;
+
+    use super::*;
+
+    #[test]
+    fn throwaway_issue_is_created_and_cleaned_up() {
+        let github = MockGithub::new();
+        let repository = Repository::new("mock", "mock");
+        self_test(&github, &repository, false).unwrap();
+        assert_eq!(
+            github.calls(),
+            [
+                "create label rpg-selftest",
+                "create issue #1",
+                "comment #1",
+                "lock #1",
+                "close #1",
+            ]
+        );
+        assert!(github.issues()[0].title.starts_with(SELF_TEST_PREFIX));
+
+        self_test(&github, &repository, true).unwrap();
+        assert_eq!(
+            github.calls()[5..],
+            [
+                "create issue #2",
+                "comment #2",
+                "lock #2",
+                "close #2",
+                "delete #2"
+            ]
+        );
+        assert_eq!(github.issues().len(), 1);
+    }
+}
@@ -0,0 +1,92 @@
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+
+use rust_project_goals::{
+    gh::{backend::GithubBackend, issue_id::Repository},
+    goal,
+    sync::{self, GithubAction, LabelOptions},
+};
+
+use crate::rfc::{self, IssuesOptions};
+
+/// Create (or fix the color of) the labels used by tracking issues, including the `T-<team>`
+/// label of every team with asks in the goals of `paths`, in each of `repositories`.
+/// The labels declared in a `Labels` row are only created if missing.
+pub(crate) fn sync_labels(
+    github: &dyn GithubBackend,
+    paths: &[PathBuf],
+    repositories: &[Repository],
+    label_options: &LabelOptions,
+    options: &IssuesOptions,
+    strict: bool,
+) -> anyhow::Result<()> {
+    let mut teams = BTreeSet::new();
+    let mut extra_labels = BTreeSet::new();
+    for path in paths {
+        let goal_documents = goal::goals_in_dir(path, strict)?;
+        teams.extend(sync::labelled_teams_in(&goal_documents)?);
+        extra_labels.extend(sync::extra_labels_in(&goal_documents));
+    }
+
+    for repository in repositories {
+        let actions =
+            sync::initialize_labels(github, repository, &teams, &extra_labels, label_options)?;
+        eprintln!("{repository}: {}", summary(&actions));
+        if actions.is_empty() {
+            continue;
+        }
+
+        if options.commit {
+            rfc::execute_actions(github, repository, actions, options)?;
+        } else {
+            rfc::print_actions(&actions, options)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// How many labels `actions` create and recolor.
+fn summary(actions: &BTreeSet<GithubAction<'_>>) -> String {
+    let created = actions
+        .iter()
+        .filter(|action| matches!(action, GithubAction::CreateLabel { .. }))
+        .count();
+    let recolored = actions
+        .iter()
+        .filter(|action| matches!(action, GithubAction::UpdateLabelColor { .. }))
+        .count();
+    if created == 0 && recolored == 0 {
+        "labels are up to date".to_string()
+    } else {
+        format!("{created} label(s) to create, {recolored} to recolor")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_project_goals::gh::{backend::MockGithub, issues::TRACKING_ISSUE_LABEL};
+
+    use super::*;
+
+    #[test]
+    fn each_repository_reports_its_changes() {
+        let label_options = LabelOptions {
+            team_label_color: sync::TEAM_LABEL_COLOR.to_string(),
+            label_prefix: String::new(),
+            tracking_label: TRACKING_ISSUE_LABEL.to_string(),
+        };
+        let github = MockGithub::new().with_label(TRACKING_ISSUE_LABEL, "000000");
+        let repository = Repository::new("mock", "mock");
+        let actions = sync::initialize_labels(
+            &github,
+            &repository,
+            &BTreeSet::new(),
+            &BTreeSet::new(),
+            &label_options,
+        )
+        .unwrap();
+        assert_eq!(summary(&actions), "1 label(s) to create, 1 to recolor");
+        assert_eq!(summary(&BTreeSet::new()), "labels are up to date");
+    }
+}
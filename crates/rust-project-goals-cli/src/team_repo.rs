@@ -9,6 +9,7 @@ use rust_project_goals::{goal, team};
 pub(crate) fn generate_team_repo(
     paths: &[std::path::PathBuf],
     team_repo_path: &std::path::PathBuf,
+    strict: bool,
 ) -> anyhow::Result<()> {
     if !team_repo_path.is_dir() {
         anyhow::bail!(
@@ -19,7 +20,7 @@ pub(crate) fn generate_team_repo(
 
     let mut goal_documents = vec![];
     for path in paths {
-        goal_documents.extend(goal::goals_in_dir(path)?);
+        goal_documents.extend(goal::goals_in_dir(path, strict)?);
     }
 
     let owners: BTreeSet<&str> = goal_documents
@@ -0,0 +1,93 @@
+use std::fmt::Write;
+use std::path::Path;
+
+use rust_project_goals::{
+    goal::{self, GoalDocument},
+    sync,
+    team::TeamName,
+};
+
+/// Print each team that the goals in `path` ask for, with how many goals ask for it and a
+/// link to each of them.
+pub(super) fn teams(path: &Path, strict: bool) -> anyhow::Result<()> {
+    let timeframe = sync::validate_path(path, None)?;
+    let goal_documents = goal::goals_in_dir(path, strict)?;
+
+    let mut text = String::new();
+    for (team, goals) in goals_by_team(&goal_documents) {
+        writeln!(
+            text,
+            "## {} ({} goal{})\n",
+            team.name_and_link(),
+            goals.len(),
+            if goals.len() == 1 { "" } else { "s" },
+        )?;
+        for goal_document in goals {
+            writeln!(
+                text,
+                "* {} ({})",
+                goal_document.metadata.title,
+                sync::goal_document_link(&timeframe, goal_document),
+            )?;
+        }
+        writeln!(text)?;
+    }
+    print!("{text}");
+
+    Ok(())
+}
+
+/// The teams with asks (see [`sync::teams_with_asks`]), each with the goals that ask for it.
+fn goals_by_team(goal_documents: &[GoalDocument]) -> Vec<(&'static TeamName, Vec<&GoalDocument>)> {
+    sync::teams_with_asks(goal_documents)
+        .into_iter()
+        .map(|team| {
+            let goals = goal_documents
+                .iter()
+                .filter(|goal_document| {
+                    goal_document
+                        .team_asks
+                        .iter()
+                        .any(|ask| ask.teams.contains(&team))
+                })
+                .collect();
+            (team, goals)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_project_goals::goal::test_support::GoalText;
+
+    use super::*;
+    use crate::test_support::use_team_fixtures;
+
+    fn goal(slug: &str, asked: &str) -> GoalDocument {
+        GoalText::new(slug)
+            .task("Implementation", "@owner", "")
+            .task("Standard reviews", &format!("![Team][] {asked}"), "")
+            .document(&format!("src/2025h1/{slug}.md"))
+    }
+
+    #[test]
+    fn goals_are_grouped_by_the_teams_they_ask() {
+        use_team_fixtures();
+        let goals = [
+            goal("a", "[lang]"),
+            goal("b", "[lang], [compiler]"),
+            GoalText::new("c").document("src/2025h1/c.md"),
+        ];
+        let teams: Vec<(&str, Vec<&str>)> = goals_by_team(&goals)
+            .into_iter()
+            .map(|(team, goals)| {
+                let titles = goals
+                    .iter()
+                    .map(|goal| goal.metadata.title.as_str())
+                    .collect();
+                (team.as_str(), titles)
+            })
+            .collect();
+        assert_eq!(teams, [("compiler", vec!["b"]), ("lang", vec!["a", "b"])]);
+    }
+}
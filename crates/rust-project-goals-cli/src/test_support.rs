@@ -0,0 +1,18 @@
+//! Helpers shared by the tests of the commands.
+
+use rust_project_goals::team;
+
+/// Make the team lookups use the offline team data of the `rust-project-goals` tests
+/// rather than the team API.
+pub(crate) fn use_team_fixtures() {
+    team::use_team_data(
+        serde_json::from_str(include_str!(
+            "../../rust-project-goals/src/team/fixtures/teams.json"
+        ))
+        .unwrap(),
+        serde_json::from_str(include_str!(
+            "../../rust-project-goals/src/team/fixtures/people.json"
+        ))
+        .unwrap(),
+    );
+}
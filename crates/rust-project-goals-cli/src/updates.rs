@@ -3,7 +3,8 @@ use chrono::{Datelike, NaiveDate};
 use regex::Regex;
 use rust_project_goals::markwaydown;
 use rust_project_goals::re::{HELP_WANTED, TLDR};
-use rust_project_goals::util::comma;
+use rust_project_goals::team::get_person_data;
+use rust_project_goals::util::commas;
 use rust_project_goals_json::GithubIssueState;
 use spanned::{Span, Spanned};
 use std::io::Write;
@@ -122,11 +123,18 @@ fn prepare_goals(
         let mut comments = issue.comments.clone();
         comments.sort_by_key(|c| c.created_at.clone());
         comments.retain(|c| !c.is_automated_comment() && filter.matches(c));
-        // Prettify the comments' timestamp after using it for sorting.
+        // Prettify the comments' timestamp after using it for sorting, and name their authors.
         for comment in comments.iter_mut() {
             comment.created_at = format!("{}", comment.created_at_date());
+            comment.author = display_name(&comment.author)?;
         }
 
+        let issue_assignees = issue
+            .assignees
+            .iter()
+            .map(|login| display_name(&format!("@{login}")))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
         let tldr = tldr(&issue_id, &mut comments)?;
 
         let (has_help_wanted, help_wanted) = help_wanted(&issue_id, &tldr, &comments)?;
@@ -141,7 +149,7 @@ fn prepare_goals(
         result.push(UpdatesGoal {
             title: title.clone(),
             issue_number: issue.number,
-            issue_assignees: comma(&issue.assignees),
+            issue_assignees: commas(&issue_assignees),
             issue_url: issue_id.url(),
             progress,
             has_help_wanted,
@@ -168,6 +176,17 @@ fn prepare_goals(
     Ok(result)
 }
 
+/// Given a username like `@foo`, returns `Full Name (@foo)` if the person is
+/// in the Rust team repo, or else just `@foo`.
+fn display_name(username: &str) -> anyhow::Result<String> {
+    Ok(match get_person_data(username)? {
+        Some(person) if !person.data.name.is_empty() => {
+            format!("{} ({username})", person.data.name)
+        }
+        _ => username.to_string(),
+    })
+}
+
 /// Search for a TL;DR comment. If one is found, remove it and return the text.
 fn tldr(
     _issue_id: &IssueId,
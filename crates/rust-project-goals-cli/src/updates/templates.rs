@@ -79,7 +79,7 @@ pub struct UpdatesGoal {
     /// Tracking issue number on the project goals repository
     pub issue_number: u64,
 
-    /// Comma-separated list of assignees
+    /// Comma-separated list of assignees, with their names when known
     pub issue_assignees: String,
 
     /// URL of the tracking issue
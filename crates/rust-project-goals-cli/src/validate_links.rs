@@ -0,0 +1,196 @@
+use std::path::{Component, Path, PathBuf};
+
+use anyhow::Context;
+use rust_project_goals::{re, util::markdown_files};
+
+/// Where the book sources are, as configured in `book.toml`.
+const BOOK_SOURCES: &str = "src";
+
+/// Where `mdbook build` writes the html pages.
+const PUBLISHED_BOOK: &str = "book/html";
+
+/// Absolute links to the published book are checked like relative ones.
+const PUBLISHED_URL: &str = "https://rust-lang.github.io/rust-project-goals/";
+
+/// A link whose target does not exist.
+struct BrokenLink {
+    path: PathBuf,
+    line: usize,
+    target: String,
+}
+
+/// Check that every relative `.md` link in the markdown files under `paths` points at a file
+/// that exists (relative to the file containing the link). Broken links are reported with
+/// the file and line they appear on.
+///
+/// With `published`, check the built book instead: every `.md` link (which the book and the
+/// RFC text turn into an `.html` one) and every hand-written `.html` link to the book must
+/// point at a page that `mdbook build` generated.
+pub(super) fn validate_links(paths: &[PathBuf], published: bool) -> anyhow::Result<()> {
+    if published && !Path::new(PUBLISHED_BOOK).is_dir() {
+        anyhow::bail!("no book found in `{PUBLISHED_BOOK}`; run `mdbook build` first");
+    }
+
+    let mut broken = vec![];
+    for path in paths {
+        for (file, _) in markdown_files(path)? {
+            let text = std::fs::read_to_string(&file)
+                .with_context(|| format!("reading `{}`", file.display()))?;
+            if published {
+                broken.extend(unpublished_links(&file, &text)?);
+            } else {
+                broken.extend(broken_links(&file, &text));
+            }
+        }
+    }
+
+    for link in &broken {
+        eprintln!(
+            "{}:{}: broken link to `{}`",
+            link.path.display(),
+            link.line,
+            link.target
+        );
+    }
+
+    if !broken.is_empty() {
+        anyhow::bail!("found {} broken link(s)", broken.len());
+    }
+    Ok(())
+}
+
+fn broken_links(path: &Path, text: &str) -> Vec<BrokenLink> {
+    let directory = path.parent().unwrap_or(Path::new(""));
+    let mut broken = vec![];
+    for (index, line) in text.lines().enumerate() {
+        for c in re::MD_LINK.captures_iter(line) {
+            let target = format!("{}.md", &c[1]);
+
+            // Links to other sites are not ours to check.
+            if target.contains("://") || target.starts_with('/') {
+                continue;
+            }
+
+            if !directory.join(&target).is_file() {
+                broken.push(BrokenLink {
+                    path: path.to_path_buf(),
+                    line: index + 1,
+                    target,
+                });
+            }
+        }
+    }
+    broken
+}
+
+/// The links in `text` (from the book source `path`) to pages missing from the built book.
+fn unpublished_links(path: &Path, text: &str) -> anyhow::Result<Vec<BrokenLink>> {
+    let Ok(page) = path.strip_prefix(BOOK_SOURCES) else {
+        anyhow::bail!(
+            "`{}` is not in the book sources (`{BOOK_SOURCES}`)",
+            path.display()
+        );
+    };
+
+    let mut broken = vec![];
+    for (index, line) in text.lines().enumerate() {
+        for (target, published) in published_targets(page, line) {
+            if !Path::new(PUBLISHED_BOOK).join(&published).is_file() {
+                broken.push(BrokenLink {
+                    path: path.to_path_buf(),
+                    line: index + 1,
+                    target,
+                });
+            }
+        }
+    }
+    Ok(broken)
+}
+
+/// The links in `line` of the book page `page` (relative to the book sources) that point
+/// into the book, each with the `.html` file it resolves to (relative to the built book).
+fn published_targets(page: &Path, line: &str) -> Vec<(String, PathBuf)> {
+    let directory = page.parent().unwrap_or(Path::new(""));
+    let md_links = re::MD_LINK
+        .captures_iter(line)
+        .map(|c| (format!("{}.md", &c[1]), c[1].to_string()));
+    let html_links = re::HTML_LINK
+        .captures_iter(line)
+        .map(|c| (format!("{}.html", &c[1]), c[1].to_string()));
+
+    md_links
+        .chain(html_links)
+        .filter_map(|(target, stem)| {
+            let published = match stem.strip_prefix(PUBLISHED_URL) {
+                Some(stem) => PathBuf::from(stem),
+                // Links to other sites are not ours to check.
+                None if stem.contains("://") || stem.starts_with('/') => return None,
+                None => normalize(&directory.join(stem)),
+            };
+            Some((target, published.with_extension("html")))
+        })
+        .collect()
+}
+
+/// Resolve the `.` and `..` components of a relative path.
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            component => normalized.push(component),
+        }
+    }
+    normalized
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_relative_links_are_broken() {
+        let dir = std::env::temp_dir().join(format!(
+            "missing_relative_links_are_broken-{}",
+            std::process::id()
+        ));
+        std::fs::create_dir_all(dir.join("2025h1")).unwrap();
+        std::fs::write(dir.join("2025h1/exists.md"), "# Exists\n").unwrap();
+        let path = dir.join("2025h1/index.md");
+        let text = "See [a](./exists.md#summary).\n\
+                    And [b](../2024h2/missing.md) and [c](https://example.com/page.md).\n";
+        std::fs::write(&path, text).unwrap();
+
+        let broken: Vec<(usize, String)> = broken_links(&path, text)
+            .into_iter()
+            .map(|link| (link.line, link.target))
+            .collect();
+        assert_eq!(broken, [(2, "../2024h2/missing.md".to_string())]);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn links_resolve_to_published_pages() {
+        let line = "See [a](./async.md#summary), [b](../2024h2/rfl.html), \
+                    [c](https://rust-lang.github.io/rust-project-goals/2025h1/goals.html) \
+                    and [d](https://example.com/page.html).";
+        assert_eq!(
+            published_targets(Path::new("2025h1/index.md"), line),
+            [
+                ("./async.md".to_string(), PathBuf::from("2025h1/async.html")),
+                (
+                    "../2024h2/rfl.html".to_string(),
+                    PathBuf::from("2024h2/rfl.html")
+                ),
+                (
+                    "https://rust-lang.github.io/rust-project-goals/2025h1/goals.html".to_string(),
+                    PathBuf::from("2025h1/goals.html")
+                ),
+            ]
+        );
+    }
+}
@@ -0,0 +1,87 @@
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::time::Duration;
+
+use anyhow::Context;
+use notify_debouncer_mini::{new_debouncer, notify::RecursiveMode, DebouncedEvent};
+
+/// How long to wait for more changes before re-running, so that saving several files
+/// (or an editor writing a file in several steps) only triggers one run.
+const DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// Call `run` now and again whenever a markdown file under one of `paths` changes, until interrupted.
+/// Errors from `run` are reported but do not stop watching, since the next edit may fix them.
+pub(crate) fn watch(
+    paths: &[PathBuf],
+    mut run: impl FnMut() -> anyhow::Result<()>,
+) -> anyhow::Result<()> {
+    let (sender, receiver) = mpsc::channel();
+    let mut debouncer = new_debouncer(DEBOUNCE, sender).context("starting to watch files")?;
+    for path in paths {
+        debouncer
+            .watcher()
+            .watch(path, RecursiveMode::Recursive)
+            .with_context(|| format!("watching `{}`", path.display()))?;
+    }
+
+    run_reporting_errors(&mut run);
+    for events in receiver {
+        let events = events.context("watching files")?;
+        let changed = markdown_paths(&events);
+        if changed.is_empty() {
+            continue;
+        }
+
+        eprintln!();
+        for path in changed {
+            eprintln!("`{}` changed", path.display());
+        }
+        run_reporting_errors(&mut run);
+    }
+
+    Ok(())
+}
+
+fn run_reporting_errors(run: &mut impl FnMut() -> anyhow::Result<()>) {
+    if let Err(e) = run() {
+        eprintln!("error: {e:#}");
+    }
+    eprintln!("Watching for changes (press Ctrl-C to stop)...");
+}
+
+/// The markdown files among the changed paths.
+fn markdown_paths(events: &[DebouncedEvent]) -> Vec<&Path> {
+    let mut paths: Vec<&Path> = events
+        .iter()
+        .map(|event| event.path.as_path())
+        .filter(|path| path.extension().is_some_and(|extension| extension == "md"))
+        .collect();
+    paths.sort();
+    paths.dedup();
+    paths
+}
+
+#[cfg(test)]
+mod tests {
+    use notify_debouncer_mini::DebouncedEventKind;
+
+    use super::*;
+
+    #[test]
+    fn only_markdown_changes_count() {
+        let event = |path: &str| DebouncedEvent {
+            path: PathBuf::from(path),
+            kind: DebouncedEventKind::Any,
+        };
+        let events = [
+            event("src/2025h1/b.md"),
+            event("src/2025h1/.b.md.swp"),
+            event("src/2025h1/a.md"),
+            event("src/2025h1/b.md"),
+        ];
+        assert_eq!(
+            markdown_paths(&events),
+            [Path::new("src/2025h1/a.md"), Path::new("src/2025h1/b.md")]
+        );
+    }
+}
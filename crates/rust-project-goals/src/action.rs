@@ -0,0 +1,385 @@
+//! The actions that a sync of the tracking issues plans, as owned data that can be serialized,
+//! so that tools can inspect, store, or execute a plan without the goal documents it came from.
+
+use std::collections::BTreeSet;
+use std::fmt::Display;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    gh::{
+        backend::GithubBackend,
+        issue_id::{IssueId, Repository},
+        issues::ExistingGithubIssue,
+        labels::GhLabel,
+    },
+    re,
+    util::commas,
+};
+
+/// A change to the repository, one of its issues, or a goal document.
+/// Serialized as an object whose `kind` is the snake-case name of the variant.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Action {
+    CreateMilestone {
+        title: String,
+    },
+    SetMilestoneDue {
+        title: String,
+        /// Like `2025-06-30`.
+        due_on: String,
+        /// The due date the milestone has now, if any.
+        previous: Option<String>,
+    },
+    CreateLabel {
+        label: GhLabel,
+    },
+    UpdateLabelColor {
+        label: GhLabel,
+        old_color: String,
+    },
+    CreateIssue {
+        title: String,
+        assignees: BTreeSet<String>,
+        labels: Vec<String>,
+        /// Empty if the period is recorded with a label rather than a milestone.
+        milestone: String,
+        body: String,
+        /// Set on the next sync, once we know the number of the issue.
+        #[serde(default)]
+        issue_type: Option<String>,
+    },
+    ReopenIssue {
+        number: u64,
+    },
+    ChangeTitle {
+        number: u64,
+        title: String,
+    },
+    /// Move the issue to another period.
+    ChangeMilestone {
+        number: u64,
+        milestone: String,
+        period_by: PeriodBy,
+        /// The periods the issue is in now (labels to remove, if the period is a label).
+        previous: Vec<String>,
+        /// Comment posted once the issue is moved.
+        comment: Option<String>,
+    },
+    AddLabel {
+        number: u64,
+        label: String,
+    },
+    SetIssueType {
+        number: u64,
+        issue_type: String,
+    },
+    Comment {
+        number: u64,
+        body: String,
+    },
+    UpdateComment {
+        number: u64,
+        url: String,
+        body: String,
+    },
+    DeleteComment {
+        number: u64,
+        url: String,
+    },
+    UpdateIssueBody {
+        number: u64,
+        body: String,
+        last_synced: Option<String>,
+    },
+    SyncAssignees {
+        number: u64,
+        remove_owners: BTreeSet<String>,
+        add_owners: BTreeSet<String>,
+    },
+    LockIssue {
+        number: u64,
+    },
+    /// Record the issue as the tracking issue of the goal document (a local edit).
+    LinkToTrackingIssue {
+        goal_document: PathBuf,
+        /// Like `[rust-lang/rust-project-goals#123]`.
+        issue: String,
+    },
+    /// Bring the owners in the goal document in line with the assignees of the issue
+    /// (a local edit).
+    SyncOwnersFromIssue {
+        goal_document: PathBuf,
+        number: u64,
+        remove_owners: BTreeSet<String>,
+        add_owners: BTreeSet<String>,
+    },
+    /// Give the goal document the title of the issue (a local edit).
+    RetitleGoal {
+        goal_document: PathBuf,
+        number: u64,
+        title: String,
+    },
+    CloseIssue {
+        number: u64,
+    },
+    AddToProject {
+        number: u64,
+        project: u64,
+        status: Option<String>,
+    },
+}
+
+/// How the goal period (e.g., `2025h1`) of a tracking issue is recorded on github.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+#[serde(rename_all = "snake_case")]
+pub enum PeriodBy {
+    /// The github milestone of the issue.
+    #[default]
+    Milestone,
+
+    /// A label named after the period (e.g., `2025h1`), for repositories without milestones.
+    Label,
+}
+
+impl PeriodBy {
+    /// The issues in `period`.
+    pub fn list_issues(
+        self,
+        github: &dyn GithubBackend,
+        repository: &Repository,
+        period: &str,
+    ) -> anyhow::Result<Vec<ExistingGithubIssue>> {
+        match self {
+            PeriodBy::Milestone => github.list_issues_in_milestone(repository, period),
+            PeriodBy::Label => github.list_issues_with_label(repository, period),
+        }
+    }
+
+    /// The period(s) that `issue` is currently in. An issue can only have one milestone,
+    /// but it may have several period labels.
+    pub fn periods_of(self, issue: &ExistingGithubIssue) -> Vec<String> {
+        match self {
+            PeriodBy::Milestone => issue.milestone.iter().map(|m| m.title.clone()).collect(),
+            PeriodBy::Label => issue
+                .labels
+                .iter()
+                .filter(|label| re::TIMEFRAME.is_match(&label.name))
+                .map(|label| label.name.clone())
+                .collect(),
+        }
+    }
+}
+
+impl Action {
+    /// The number of the issue this action modifies, if any.
+    pub fn issue_number(&self) -> Option<u64> {
+        match self {
+            Action::CreateMilestone { .. }
+            | Action::SetMilestoneDue { .. }
+            | Action::CreateLabel { .. }
+            | Action::UpdateLabelColor { .. }
+            | Action::CreateIssue { .. } => None,
+            Action::ReopenIssue { number }
+            | Action::ChangeTitle { number, .. }
+            | Action::ChangeMilestone { number, .. }
+            | Action::AddLabel { number, .. }
+            | Action::SetIssueType { number, .. }
+            | Action::Comment { number, .. }
+            | Action::UpdateComment { number, .. }
+            | Action::DeleteComment { number, .. }
+            | Action::UpdateIssueBody { number, .. }
+            | Action::SyncAssignees { number, .. }
+            | Action::SyncOwnersFromIssue { number, .. }
+            | Action::RetitleGoal { number, .. }
+            | Action::LockIssue { number }
+            | Action::CloseIssue { number }
+            | Action::AddToProject { number, .. } => Some(*number),
+            Action::LinkToTrackingIssue { issue, .. } => {
+                Some(issue.parse::<IssueId>().ok()?.number)
+            }
+        }
+    }
+
+    /// The goal document that this action edits, if it is a local edit rather than a change
+    /// on github.
+    pub fn goal_document(&self) -> Option<&PathBuf> {
+        match self {
+            Action::LinkToTrackingIssue { goal_document, .. }
+            | Action::SyncOwnersFromIssue { goal_document, .. }
+            | Action::RetitleGoal { goal_document, .. } => Some(goal_document),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Action {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let changes = |remove_owners: &BTreeSet<String>, add_owners: &BTreeSet<String>| {
+            remove_owners
+                .iter()
+                .map(|s| format!("-{}", s))
+                .chain(add_owners.iter().map(|s| format!("+{}", s)))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        match self {
+            Action::CreateMilestone { title } => {
+                write!(f, "create milestone \"{title}\"")
+            }
+            Action::SetMilestoneDue {
+                title,
+                due_on,
+                previous,
+            } => {
+                write!(f, "set the due date of milestone \"{title}\" to {due_on}")?;
+                match previous {
+                    Some(previous) => write!(f, " (was {previous})"),
+                    None => write!(f, " (had none)"),
+                }
+            }
+            Action::CreateLabel {
+                label: GhLabel { name, color },
+            } => {
+                write!(f, "create label `{}` with color `{}`", name, color)
+            }
+            Action::UpdateLabelColor {
+                label: GhLabel { name, color },
+                old_color,
+            } => {
+                write!(
+                    f,
+                    "change color of label `{}` from `{}` to `{}`",
+                    name, old_color, color
+                )
+            }
+            Action::CreateIssue { title, .. } => {
+                write!(f, "create issue \"{}\"", title)
+            }
+            Action::ChangeMilestone {
+                number,
+                milestone,
+                period_by,
+                previous,
+                comment,
+            } => {
+                if *period_by == PeriodBy::Label {
+                    write!(f, "add label \"{milestone}\" to issue #{number}")?;
+                    if !previous.is_empty() {
+                        write!(
+                            f,
+                            " and remove {}",
+                            commas(previous.iter().map(|p| format!("\"{p}\"")))
+                        )?;
+                    }
+                } else {
+                    write!(f, "update issue #{} milestone to \"{}\"", number, milestone)?;
+                }
+                match comment {
+                    Some(comment) => write!(f, ", then comment {comment:?}"),
+                    None => Ok(()),
+                }
+            }
+            Action::ReopenIssue { number } => {
+                write!(f, "reopen issue #{}", number)
+            }
+            Action::ChangeTitle { number, title } => {
+                write!(f, "update issue #{} title to \"{}\"", number, title)
+            }
+            Action::AddLabel { number, label } => {
+                write!(f, "add label `{}` to issue #{}", label, number)
+            }
+            Action::SetIssueType { number, issue_type } => {
+                write!(f, "set the type of issue #{number} to `{issue_type}`")
+            }
+            Action::Comment { number, body } => {
+                write!(f, "post comment on issue #{}: \"{}\"", number, body)
+            }
+            Action::UpdateComment { number, url, .. } => match re::COMMENT_ID.captures(url) {
+                Some(c) => write!(f, "update comment {} on issue #{number}", &c[1]),
+                None => write!(f, "update comment {url} on issue #{number}"),
+            },
+            Action::DeleteComment { number, url } => match re::COMMENT_ID.captures(url) {
+                Some(c) => write!(f, "delete comment {} on issue #{number}", &c[1]),
+                None => write!(f, "delete comment {url} on issue #{number}"),
+            },
+            Action::UpdateIssueBody {
+                number,
+                body: _,
+                last_synced,
+            } => {
+                write!(f, "update the body on issue #{} to match the goal", number)?;
+                match last_synced {
+                    Some(last_synced) => write!(f, " (last synced {last_synced})"),
+                    None => write!(f, " (never synced)"),
+                }
+            }
+            Action::SyncAssignees {
+                number,
+                remove_owners,
+                add_owners,
+            } => {
+                write!(
+                    f,
+                    "sync issue #{} ({})",
+                    number,
+                    changes(remove_owners, add_owners)
+                )
+            }
+            Action::LockIssue { number } => {
+                write!(f, "lock issue #{}", number)
+            }
+            Action::LinkToTrackingIssue {
+                goal_document,
+                issue,
+            } => {
+                write!(
+                    f,
+                    "link issue {issue} to the markdown document at {}",
+                    goal_document.display()
+                )
+            }
+            Action::SyncOwnersFromIssue {
+                goal_document,
+                number,
+                remove_owners,
+                add_owners,
+            } => {
+                write!(
+                    f,
+                    "sync the owners in {} with issue #{number} ({})",
+                    goal_document.display(),
+                    changes(remove_owners, add_owners)
+                )
+            }
+            Action::RetitleGoal {
+                goal_document,
+                number,
+                title,
+            } => {
+                write!(
+                    f,
+                    "retitle {} to {title:?} like issue #{number}",
+                    goal_document.display()
+                )
+            }
+            Action::CloseIssue { number } => {
+                write!(f, "close issue #{}", number)
+            }
+            Action::AddToProject {
+                number,
+                project,
+                status,
+            } => {
+                write!(f, "add issue #{number} to project {project}")?;
+                if let Some(status) = status {
+                    write!(f, " with status \"{status}\"")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
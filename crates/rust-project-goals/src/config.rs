@@ -9,6 +9,11 @@ pub struct Configuration {
     /// Defines the valid "asks" of teams. The key is the ask, the value is an extended description.
     /// IndexMap is used to preserve the ordering as defined in the TOML file.
     pub team_asks: IndexMap<String, TeamAskDetails>,
+
+    /// The repository (e.g., `rust-lang/rust-project-goals`) that tracking issues are expected to live in.
+    /// Used as a guard against syncing goals into the wrong repository (e.g., a fork).
+    #[serde(default)]
+    pub expected_repository: Option<String>,
 }
 
 #[derive(Deserialize)]
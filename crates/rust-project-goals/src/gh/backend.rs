@@ -0,0 +1,391 @@
+//! An abstraction over the github operations we perform, so that the code deciding
+//! *what* to do can be exercised without talking to github.
+//!
+//! [`GhCli`] is the real backend and shells out to the `gh` command-line tool;
+//! `MockGithub` keeps everything in memory and is meant for tests; it is only built for the
+//! tests of this crate, or with the `mock` feature.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use super::{
+    issue_id::Repository,
+    issues::{self, ExistingGithubComment, ExistingGithubIssue, GhReaction},
+    labels::GhLabel,
+    milestone,
+};
+
+#[cfg(any(test, feature = "mock"))]
+mod mock;
+#[cfg(any(test, feature = "mock"))]
+pub use mock::MockGithub;
+
+/// The github operations needed to sync tracking issues.
+pub trait GithubBackend {
+    /// The titles of all milestones, open or closed.
+    fn list_milestones(&self, repository: &Repository) -> anyhow::Result<Vec<String>>;
+
+    fn create_milestone(&self, repository: &Repository, title: &str) -> anyhow::Result<()>;
+
+    /// The due date of the milestone titled `title` (like `2025-06-30T00:00:00Z`), if the
+    /// milestone exists and has one.
+    fn milestone_due_on(
+        &self,
+        repository: &Repository,
+        title: &str,
+    ) -> anyhow::Result<Option<String>>;
+
+    /// Set the due date of the existing milestone titled `title` to `due_on` (`YYYY-MM-DD`).
+    fn set_milestone_due(
+        &self,
+        repository: &Repository,
+        title: &str,
+        due_on: &str,
+    ) -> anyhow::Result<()>;
+
+    fn list_labels(&self, repository: &Repository) -> anyhow::Result<Vec<GhLabel>>;
+
+    fn create_label(&self, repository: &Repository, label: &GhLabel) -> anyhow::Result<()>;
+
+    fn update_label_color(&self, repository: &Repository, label: &GhLabel) -> anyhow::Result<()>;
+
+    fn list_issues_in_milestone(
+        &self,
+        repository: &Repository,
+        milestone: &str,
+    ) -> anyhow::Result<Vec<ExistingGithubIssue>>;
+
+    fn list_issues_with_label(
+        &self,
+        repository: &Repository,
+        label: &str,
+    ) -> anyhow::Result<Vec<ExistingGithubIssue>>;
+
+    fn fetch_issue(
+        &self,
+        repository: &Repository,
+        number: u64,
+    ) -> anyhow::Result<ExistingGithubIssue>;
+
+    fn create_issue(
+        &self,
+        repository: &Repository,
+        body: &str,
+        title: &str,
+        labels: &[String],
+        assignees: &BTreeSet<String>,
+        milestone: &str,
+    ) -> anyhow::Result<()>;
+
+    fn change_title(&self, repository: &Repository, number: u64, title: &str)
+        -> anyhow::Result<()>;
+
+    fn change_milestone(
+        &self,
+        repository: &Repository,
+        number: u64,
+        milestone: &str,
+    ) -> anyhow::Result<()>;
+
+    fn create_comment(
+        &self,
+        repository: &Repository,
+        number: u64,
+        body: &str,
+    ) -> anyhow::Result<()>;
+
+    fn update_comment(
+        &self,
+        repository: &Repository,
+        comment: &ExistingGithubComment,
+        body: &str,
+    ) -> anyhow::Result<()>;
+
+    fn delete_comment(
+        &self,
+        repository: &Repository,
+        comment: &ExistingGithubComment,
+    ) -> anyhow::Result<()>;
+
+    fn list_comment_reactions(
+        &self,
+        repository: &Repository,
+        comment: &ExistingGithubComment,
+    ) -> anyhow::Result<Vec<GhReaction>>;
+
+    fn update_issue_body(
+        &self,
+        repository: &Repository,
+        number: u64,
+        body: &str,
+    ) -> anyhow::Result<()>;
+
+    fn add_label(&self, repository: &Repository, number: u64, label: &str) -> anyhow::Result<()>;
+
+    fn remove_label(&self, repository: &Repository, number: u64, label: &str)
+        -> anyhow::Result<()>;
+
+    fn close_issue(&self, repository: &Repository, number: u64) -> anyhow::Result<()>;
+
+    fn reopen_issue(&self, repository: &Repository, number: u64) -> anyhow::Result<()>;
+
+    fn delete_issue(&self, repository: &Repository, number: u64) -> anyhow::Result<()>;
+
+    fn sync_assignees(
+        &self,
+        repository: &Repository,
+        number: u64,
+        remove_owners: &BTreeSet<String>,
+        add_owners: &BTreeSet<String>,
+    ) -> anyhow::Result<()>;
+
+    fn lock_issue(&self, repository: &Repository, number: u64) -> anyhow::Result<()>;
+
+    fn list_project_issues(
+        &self,
+        repository: &Repository,
+        project: u64,
+    ) -> anyhow::Result<BTreeSet<u64>>;
+
+    fn add_to_project(
+        &self,
+        repository: &Repository,
+        project: u64,
+        number: u64,
+        status: Option<&str>,
+    ) -> anyhow::Result<()>;
+
+    /// The names of the issue types (e.g., `Task`) available on the repository; empty if
+    /// it does not use issue types.
+    fn list_issue_types(&self, repository: &Repository) -> anyhow::Result<Vec<String>>;
+
+    /// The names of the types of the issues `numbers`, for those that have one.
+    fn fetch_issue_types(
+        &self,
+        repository: &Repository,
+        numbers: &[u64],
+    ) -> anyhow::Result<BTreeMap<u64, String>>;
+
+    fn set_issue_type(
+        &self,
+        repository: &Repository,
+        number: u64,
+        issue_type: &str,
+    ) -> anyhow::Result<()>;
+}
+
+/// The real backend, which runs the `gh` command-line tool.
+pub struct GhCli;
+
+impl GithubBackend for GhCli {
+    fn list_milestones(&self, repository: &Repository) -> anyhow::Result<Vec<String>> {
+        milestone::list_milestone_titles(repository)
+    }
+
+    fn create_milestone(&self, repository: &Repository, title: &str) -> anyhow::Result<()> {
+        milestone::create_milestone(repository, title)
+    }
+
+    fn milestone_due_on(
+        &self,
+        repository: &Repository,
+        title: &str,
+    ) -> anyhow::Result<Option<String>> {
+        milestone::milestone_due_on(repository, title)
+    }
+
+    fn set_milestone_due(
+        &self,
+        repository: &Repository,
+        title: &str,
+        due_on: &str,
+    ) -> anyhow::Result<()> {
+        milestone::set_milestone_due(repository, title, due_on)
+    }
+
+    fn list_labels(&self, repository: &Repository) -> anyhow::Result<Vec<GhLabel>> {
+        GhLabel::list(repository)
+    }
+
+    fn create_label(&self, repository: &Repository, label: &GhLabel) -> anyhow::Result<()> {
+        label.create(repository)
+    }
+
+    fn update_label_color(&self, repository: &Repository, label: &GhLabel) -> anyhow::Result<()> {
+        label.update_color(repository)
+    }
+
+    fn list_issues_in_milestone(
+        &self,
+        repository: &Repository,
+        milestone: &str,
+    ) -> anyhow::Result<Vec<ExistingGithubIssue>> {
+        issues::list_issues_in_milestone(repository, milestone)
+    }
+
+    fn list_issues_with_label(
+        &self,
+        repository: &Repository,
+        label: &str,
+    ) -> anyhow::Result<Vec<ExistingGithubIssue>> {
+        issues::list_issues_with_label(repository, label)
+    }
+
+    fn fetch_issue(
+        &self,
+        repository: &Repository,
+        number: u64,
+    ) -> anyhow::Result<ExistingGithubIssue> {
+        issues::fetch_issue(repository, number)
+    }
+
+    fn create_issue(
+        &self,
+        repository: &Repository,
+        body: &str,
+        title: &str,
+        labels: &[String],
+        assignees: &BTreeSet<String>,
+        milestone: &str,
+    ) -> anyhow::Result<()> {
+        issues::create_issue(repository, body, title, labels, assignees, milestone)
+    }
+
+    fn change_title(
+        &self,
+        repository: &Repository,
+        number: u64,
+        title: &str,
+    ) -> anyhow::Result<()> {
+        issues::change_title(repository, number, title)
+    }
+
+    fn change_milestone(
+        &self,
+        repository: &Repository,
+        number: u64,
+        milestone: &str,
+    ) -> anyhow::Result<()> {
+        issues::change_milestone(repository, number, milestone)
+    }
+
+    fn create_comment(
+        &self,
+        repository: &Repository,
+        number: u64,
+        body: &str,
+    ) -> anyhow::Result<()> {
+        issues::create_comment(repository, number, body)
+    }
+
+    fn update_comment(
+        &self,
+        repository: &Repository,
+        comment: &ExistingGithubComment,
+        body: &str,
+    ) -> anyhow::Result<()> {
+        issues::update_comment(repository, comment, body)
+    }
+
+    fn delete_comment(
+        &self,
+        repository: &Repository,
+        comment: &ExistingGithubComment,
+    ) -> anyhow::Result<()> {
+        issues::delete_comment(repository, comment)
+    }
+
+    fn list_comment_reactions(
+        &self,
+        repository: &Repository,
+        comment: &ExistingGithubComment,
+    ) -> anyhow::Result<Vec<GhReaction>> {
+        issues::list_comment_reactions(repository, comment)
+    }
+
+    fn update_issue_body(
+        &self,
+        repository: &Repository,
+        number: u64,
+        body: &str,
+    ) -> anyhow::Result<()> {
+        issues::update_issue_body(repository, number, body)
+    }
+
+    fn add_label(&self, repository: &Repository, number: u64, label: &str) -> anyhow::Result<()> {
+        issues::add_label(repository, number, label)
+    }
+
+    fn remove_label(
+        &self,
+        repository: &Repository,
+        number: u64,
+        label: &str,
+    ) -> anyhow::Result<()> {
+        issues::remove_label(repository, number, label)
+    }
+
+    fn close_issue(&self, repository: &Repository, number: u64) -> anyhow::Result<()> {
+        issues::close_issue(repository, number)
+    }
+
+    fn reopen_issue(&self, repository: &Repository, number: u64) -> anyhow::Result<()> {
+        issues::reopen_issue(repository, number)
+    }
+
+    fn delete_issue(&self, repository: &Repository, number: u64) -> anyhow::Result<()> {
+        issues::delete_issue(repository, number)
+    }
+
+    fn sync_assignees(
+        &self,
+        repository: &Repository,
+        number: u64,
+        remove_owners: &BTreeSet<String>,
+        add_owners: &BTreeSet<String>,
+    ) -> anyhow::Result<()> {
+        issues::sync_assignees(repository, number, remove_owners, add_owners)
+    }
+
+    fn lock_issue(&self, repository: &Repository, number: u64) -> anyhow::Result<()> {
+        issues::lock_issue(repository, number)
+    }
+
+    fn list_project_issues(
+        &self,
+        repository: &Repository,
+        project: u64,
+    ) -> anyhow::Result<BTreeSet<u64>> {
+        issues::list_project_issues(repository, project)
+    }
+
+    fn add_to_project(
+        &self,
+        repository: &Repository,
+        project: u64,
+        number: u64,
+        status: Option<&str>,
+    ) -> anyhow::Result<()> {
+        issues::add_to_project(repository, project, number, status)
+    }
+
+    fn list_issue_types(&self, repository: &Repository) -> anyhow::Result<Vec<String>> {
+        issues::list_issue_types(repository)
+    }
+
+    fn fetch_issue_types(
+        &self,
+        repository: &Repository,
+        numbers: &[u64],
+    ) -> anyhow::Result<BTreeMap<u64, String>> {
+        issues::fetch_issue_types(repository, numbers)
+    }
+
+    fn set_issue_type(
+        &self,
+        repository: &Repository,
+        number: u64,
+        issue_type: &str,
+    ) -> anyhow::Result<()> {
+        issues::set_issue_type(repository, number, issue_type)
+    }
+}
@@ -0,0 +1,514 @@
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, BTreeSet},
+};
+
+use rust_project_goals_json::GithubIssueState;
+
+use super::GithubBackend;
+use crate::gh::{
+    issue_id::Repository,
+    issues::{ExistingGithubComment, ExistingGithubIssue, GhReaction},
+    labels::GhLabel,
+    milestone::GhMilestone,
+};
+
+/// An in-memory github for tests. The repository argument is ignored: there is only one.
+///
+/// Every mutating call is also recorded in [`MockGithub::calls`] as a short description
+/// (e.g., `comment #3`), so tests can check what was done and in which order.
+#[derive(Default)]
+pub struct MockGithub {
+    /// Milestones without issues; those of the issues are implied.
+    milestones: RefCell<BTreeSet<String>>,
+    /// `(milestone title, due date)` for each milestone that has a due date.
+    due_dates: RefCell<BTreeMap<String, String>>,
+    labels: RefCell<Vec<GhLabel>>,
+    issues: RefCell<Vec<ExistingGithubIssue>>,
+    /// `(project, issue number)` for each issue on a project board.
+    project_items: RefCell<BTreeSet<(u64, u64)>>,
+    /// `(comment URL, reaction)` for each reaction left on a comment.
+    reactions: RefCell<Vec<(String, GhReaction)>>,
+    /// The issue types available on the repository (none unless added).
+    issue_types: RefCell<Vec<String>>,
+    /// `(issue number, issue type)` for each issue that has a type.
+    typed_issues: RefCell<BTreeMap<u64, String>>,
+    calls: RefCell<Vec<String>>,
+}
+
+impl MockGithub {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an existing milestone.
+    pub fn with_milestone(self, title: &str) -> Self {
+        self.milestones.borrow_mut().insert(title.to_string());
+        self
+    }
+
+    /// Add an existing milestone due on `due_on` (like `2025-06-30T00:00:00Z`).
+    pub fn with_milestone_due(self, title: &str, due_on: &str) -> Self {
+        self.due_dates
+            .borrow_mut()
+            .insert(title.to_string(), due_on.to_string());
+        self.with_milestone(title)
+    }
+
+    /// Add an existing label.
+    pub fn with_label(self, name: &str, color: &str) -> Self {
+        self.labels.borrow_mut().push(GhLabel {
+            name: name.to_string(),
+            color: color.to_string(),
+        });
+        self
+    }
+
+    /// Add an existing issue.
+    pub fn with_issue(self, issue: ExistingGithubIssue) -> Self {
+        self.issues.borrow_mut().push(issue);
+        self
+    }
+
+    /// Put an existing issue on a project board.
+    pub fn with_project_item(self, project: u64, number: u64) -> Self {
+        self.project_items.borrow_mut().insert((project, number));
+        self
+    }
+
+    /// Make issue types available on the repository.
+    pub fn with_issue_types(self, names: &[&str]) -> Self {
+        self.issue_types
+            .borrow_mut()
+            .extend(names.iter().map(|name| name.to_string()));
+        self
+    }
+
+    /// Give an existing issue a type.
+    pub fn with_issue_type(self, number: u64, issue_type: &str) -> Self {
+        self.typed_issues
+            .borrow_mut()
+            .insert(number, issue_type.to_string());
+        self
+    }
+
+    /// Add a reaction (e.g., `+1`) by `user` on `comment`.
+    pub fn with_reaction(self, comment: &ExistingGithubComment, user: &str, content: &str) -> Self {
+        self.reactions.borrow_mut().push((
+            comment.url.clone(),
+            GhReaction {
+                user: user.to_string(),
+                content: content.to_string(),
+            },
+        ));
+        self
+    }
+
+    /// Current state of the labels.
+    pub fn labels(&self) -> Vec<GhLabel> {
+        self.labels.borrow().clone()
+    }
+
+    /// Current state of the issues, in the order they were added or created.
+    pub fn issues(&self) -> Vec<ExistingGithubIssue> {
+        self.issues.borrow().clone()
+    }
+
+    /// The mutating calls made so far, in order.
+    pub fn calls(&self) -> Vec<String> {
+        self.calls.borrow().clone()
+    }
+
+    fn record(&self, call: String) {
+        self.calls.borrow_mut().push(call);
+    }
+
+    fn with_issue_mut<R>(
+        &self,
+        number: u64,
+        op: impl FnOnce(&mut ExistingGithubIssue) -> R,
+    ) -> anyhow::Result<R> {
+        let mut issues = self.issues.borrow_mut();
+        let Some(issue) = issues.iter_mut().find(|issue| issue.number == number) else {
+            anyhow::bail!("no issue #{number}");
+        };
+        Ok(op(issue))
+    }
+
+    fn milestone(title: &str) -> GhMilestone {
+        GhMilestone {
+            number: 0,
+            title: title.to_string(),
+            description: String::new(),
+            due_on: None,
+        }
+    }
+
+    fn comment(number: u64, id: usize, body: &str) -> ExistingGithubComment {
+        ExistingGithubComment {
+            author: "mock".to_string(),
+            body: body.to_string(),
+            created_at: "2024-01-01T00:00:00Z".to_string(),
+            url: format!("https://github.com/mock/mock/issues/{number}#issuecomment-{id}"),
+        }
+    }
+}
+
+impl GithubBackend for MockGithub {
+    fn list_milestones(&self, _repository: &Repository) -> anyhow::Result<Vec<String>> {
+        let mut milestones = self.milestones.borrow().clone();
+        milestones.extend(
+            self.issues
+                .borrow()
+                .iter()
+                .filter_map(|issue| Some(issue.milestone.as_ref()?.title.clone())),
+        );
+        Ok(milestones.into_iter().collect())
+    }
+
+    fn create_milestone(&self, _repository: &Repository, title: &str) -> anyhow::Result<()> {
+        self.record(format!("create milestone {title}"));
+        if !self.milestones.borrow_mut().insert(title.to_string()) {
+            anyhow::bail!("milestone `{title}` already exists");
+        }
+        Ok(())
+    }
+
+    fn milestone_due_on(
+        &self,
+        _repository: &Repository,
+        title: &str,
+    ) -> anyhow::Result<Option<String>> {
+        Ok(self.due_dates.borrow().get(title).cloned())
+    }
+
+    fn set_milestone_due(
+        &self,
+        repository: &Repository,
+        title: &str,
+        due_on: &str,
+    ) -> anyhow::Result<()> {
+        self.record(format!("set due date of milestone {title}"));
+        if !self.list_milestones(repository)?.iter().any(|m| m == title) {
+            anyhow::bail!("no milestone `{title}`");
+        }
+        self.due_dates
+            .borrow_mut()
+            .insert(title.to_string(), format!("{due_on}T00:00:00Z"));
+        Ok(())
+    }
+
+    fn list_labels(&self, _repository: &Repository) -> anyhow::Result<Vec<GhLabel>> {
+        Ok(self.labels())
+    }
+
+    fn create_label(&self, _repository: &Repository, label: &GhLabel) -> anyhow::Result<()> {
+        self.record(format!("create label {}", label.name));
+        let mut labels = self.labels.borrow_mut();
+        labels.retain(|l| l.name != label.name);
+        labels.push(label.clone());
+        Ok(())
+    }
+
+    fn update_label_color(&self, _repository: &Repository, label: &GhLabel) -> anyhow::Result<()> {
+        self.record(format!("update label {}", label.name));
+        let mut labels = self.labels.borrow_mut();
+        let Some(existing) = labels.iter_mut().find(|l| l.name == label.name) else {
+            anyhow::bail!("no label `{}`", label.name);
+        };
+        existing.color = label.color.clone();
+        Ok(())
+    }
+
+    fn list_issues_in_milestone(
+        &self,
+        _repository: &Repository,
+        milestone: &str,
+    ) -> anyhow::Result<Vec<ExistingGithubIssue>> {
+        Ok(self
+            .issues
+            .borrow()
+            .iter()
+            .filter(|issue| {
+                issue
+                    .milestone
+                    .as_ref()
+                    .is_some_and(|m| m.title == milestone)
+            })
+            .cloned()
+            .collect())
+    }
+
+    fn list_issues_with_label(
+        &self,
+        _repository: &Repository,
+        label: &str,
+    ) -> anyhow::Result<Vec<ExistingGithubIssue>> {
+        Ok(self
+            .issues
+            .borrow()
+            .iter()
+            .filter(|issue| issue.has_label(label))
+            .cloned()
+            .collect())
+    }
+
+    fn fetch_issue(
+        &self,
+        _repository: &Repository,
+        number: u64,
+    ) -> anyhow::Result<ExistingGithubIssue> {
+        self.with_issue_mut(number, |issue| issue.clone())
+    }
+
+    fn create_issue(
+        &self,
+        _repository: &Repository,
+        body: &str,
+        title: &str,
+        labels: &[String],
+        assignees: &BTreeSet<String>,
+        milestone: &str,
+    ) -> anyhow::Result<()> {
+        let mut issues = self.issues.borrow_mut();
+        let number = issues.iter().map(|issue| issue.number).max().unwrap_or(0) + 1;
+        self.record(format!("create issue #{number}"));
+        issues.push(ExistingGithubIssue {
+            number,
+            title: title.to_string(),
+            assignees: assignees.clone(),
+            comments: vec![],
+            body: body.to_string(),
+            state: GithubIssueState::Open,
+            labels: labels
+                .iter()
+                .map(|name| GhLabel {
+                    name: name.clone(),
+                    color: String::new(),
+                })
+                .collect(),
+            milestone: (!milestone.is_empty()).then(|| Self::milestone(milestone)),
+        });
+        Ok(())
+    }
+
+    fn change_title(
+        &self,
+        _repository: &Repository,
+        number: u64,
+        title: &str,
+    ) -> anyhow::Result<()> {
+        self.record(format!("change title #{number}"));
+        self.with_issue_mut(number, |issue| issue.title = title.to_string())
+    }
+
+    fn change_milestone(
+        &self,
+        _repository: &Repository,
+        number: u64,
+        milestone: &str,
+    ) -> anyhow::Result<()> {
+        self.record(format!("change milestone #{number}"));
+        self.with_issue_mut(number, |issue| {
+            issue.milestone = Some(Self::milestone(milestone))
+        })
+    }
+
+    fn create_comment(
+        &self,
+        _repository: &Repository,
+        number: u64,
+        body: &str,
+    ) -> anyhow::Result<()> {
+        self.record(format!("comment #{number}"));
+        // Comment ids are unique across issues, and never reused even after a deletion.
+        let id = self.calls.borrow().len();
+        self.with_issue_mut(number, |issue| {
+            issue.comments.push(Self::comment(number, id, body))
+        })
+    }
+
+    fn update_comment(
+        &self,
+        _repository: &Repository,
+        comment: &ExistingGithubComment,
+        body: &str,
+    ) -> anyhow::Result<()> {
+        self.record(format!("update comment {}", comment.id()?));
+        let mut issues = self.issues.borrow_mut();
+        let Some(existing) = issues
+            .iter_mut()
+            .flat_map(|issue| &mut issue.comments)
+            .find(|c| c.url == comment.url)
+        else {
+            anyhow::bail!("no comment `{}`", comment.url);
+        };
+        existing.body = body.to_string();
+        Ok(())
+    }
+
+    fn delete_comment(
+        &self,
+        _repository: &Repository,
+        comment: &ExistingGithubComment,
+    ) -> anyhow::Result<()> {
+        self.record(format!("delete comment {}", comment.id()?));
+        for issue in self.issues.borrow_mut().iter_mut() {
+            if let Some(index) = issue.comments.iter().position(|c| c.url == comment.url) {
+                issue.comments.remove(index);
+                return Ok(());
+            }
+        }
+        anyhow::bail!("no comment `{}`", comment.url)
+    }
+
+    fn list_comment_reactions(
+        &self,
+        _repository: &Repository,
+        comment: &ExistingGithubComment,
+    ) -> anyhow::Result<Vec<GhReaction>> {
+        Ok(self
+            .reactions
+            .borrow()
+            .iter()
+            .filter(|(url, _)| *url == comment.url)
+            .map(|(_, reaction)| reaction.clone())
+            .collect())
+    }
+
+    fn update_issue_body(
+        &self,
+        _repository: &Repository,
+        number: u64,
+        body: &str,
+    ) -> anyhow::Result<()> {
+        self.record(format!("update body #{number}"));
+        self.with_issue_mut(number, |issue| issue.body = body.to_string())
+    }
+
+    fn add_label(&self, _repository: &Repository, number: u64, label: &str) -> anyhow::Result<()> {
+        self.record(format!("add label {label} #{number}"));
+        self.with_issue_mut(number, |issue| {
+            issue.labels.push(GhLabel {
+                name: label.to_string(),
+                color: String::new(),
+            })
+        })
+    }
+
+    fn remove_label(
+        &self,
+        _repository: &Repository,
+        number: u64,
+        label: &str,
+    ) -> anyhow::Result<()> {
+        self.record(format!("remove label {label} #{number}"));
+        self.with_issue_mut(number, |issue| issue.labels.retain(|l| l.name != label))
+    }
+
+    fn close_issue(&self, _repository: &Repository, number: u64) -> anyhow::Result<()> {
+        self.record(format!("close #{number}"));
+        self.with_issue_mut(number, |issue| issue.state = GithubIssueState::Closed)
+    }
+
+    fn reopen_issue(&self, _repository: &Repository, number: u64) -> anyhow::Result<()> {
+        self.record(format!("reopen #{number}"));
+        self.with_issue_mut(number, |issue| issue.state = GithubIssueState::Open)
+    }
+
+    fn delete_issue(&self, _repository: &Repository, number: u64) -> anyhow::Result<()> {
+        self.record(format!("delete #{number}"));
+        let mut issues = self.issues.borrow_mut();
+        let Some(index) = issues.iter().position(|issue| issue.number == number) else {
+            anyhow::bail!("no issue #{number}");
+        };
+        issues.remove(index);
+        Ok(())
+    }
+
+    fn sync_assignees(
+        &self,
+        _repository: &Repository,
+        number: u64,
+        remove_owners: &BTreeSet<String>,
+        add_owners: &BTreeSet<String>,
+    ) -> anyhow::Result<()> {
+        self.record(format!("sync assignees #{number}"));
+        self.with_issue_mut(number, |issue| {
+            issue
+                .assignees
+                .retain(|owner| !remove_owners.contains(owner));
+            issue.assignees.extend(add_owners.iter().cloned());
+        })
+    }
+
+    fn lock_issue(&self, _repository: &Repository, number: u64) -> anyhow::Result<()> {
+        self.record(format!("lock #{number}"));
+        // Like the real thing, we only learn that an issue is locked through the lock comment,
+        // which is posted as a separate action.
+        self.with_issue_mut(number, |_| ())
+    }
+
+    fn list_project_issues(
+        &self,
+        _repository: &Repository,
+        project: u64,
+    ) -> anyhow::Result<BTreeSet<u64>> {
+        Ok(self
+            .project_items
+            .borrow()
+            .iter()
+            .filter(|(p, _)| *p == project)
+            .map(|(_, number)| *number)
+            .collect())
+    }
+
+    fn add_to_project(
+        &self,
+        _repository: &Repository,
+        project: u64,
+        number: u64,
+        _status: Option<&str>,
+    ) -> anyhow::Result<()> {
+        self.record(format!("add #{number} to project {project}"));
+        self.with_issue_mut(number, |_| ())?;
+        self.project_items.borrow_mut().insert((project, number));
+        Ok(())
+    }
+
+    fn list_issue_types(&self, _repository: &Repository) -> anyhow::Result<Vec<String>> {
+        Ok(self.issue_types.borrow().clone())
+    }
+
+    fn fetch_issue_types(
+        &self,
+        _repository: &Repository,
+        numbers: &[u64],
+    ) -> anyhow::Result<BTreeMap<u64, String>> {
+        let mut types = BTreeMap::new();
+        for &number in numbers {
+            self.with_issue_mut(number, |_| ())?;
+            if let Some(issue_type) = self.typed_issues.borrow().get(&number) {
+                types.insert(number, issue_type.clone());
+            }
+        }
+        Ok(types)
+    }
+
+    fn set_issue_type(
+        &self,
+        _repository: &Repository,
+        number: u64,
+        issue_type: &str,
+    ) -> anyhow::Result<()> {
+        self.record(format!("set type {issue_type} #{number}"));
+        self.with_issue_mut(number, |_| ())?;
+        if !self.issue_types.borrow().iter().any(|t| t == issue_type) {
+            anyhow::bail!("no issue type `{issue_type}`");
+        }
+        self.typed_issues
+            .borrow_mut()
+            .insert(number, issue_type.to_string());
+        Ok(())
+    }
+}
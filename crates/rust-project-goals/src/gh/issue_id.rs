@@ -1,5 +1,7 @@
-use crate::re::{REPOSITORY, TRACKING_ISSUE};
+use crate::re::{ISSUE_URL, REPOSITORY, TRACKING_ISSUE};
+use anyhow::Context;
 use std::fmt::Display;
+use std::process::Command;
 
 #[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Debug)]
 pub struct Repository {
@@ -17,6 +19,54 @@ impl Repository {
             repo: repo.to_string(),
         }
     }
+
+    /// Parse the repository from a github remote URL, either SSH (`git@github.com:org/repo.git`,
+    /// `ssh://git@github.com/org/repo.git`) or HTTPS (`https://github.com/org/repo.git`).
+    /// Remotes on any other host are rejected.
+    pub fn from_remote_url(url: &str) -> Option<Self> {
+        let url = url.trim();
+        let path = [
+            "git@github.com:",
+            "ssh://git@github.com/",
+            "https://github.com/",
+        ]
+        .iter()
+        .find_map(|prefix| url.strip_prefix(prefix))?;
+        let path = path.trim_end_matches('/');
+        let path = path.strip_suffix(".git").unwrap_or(path);
+
+        let (org, repo) = path.split_once('/')?;
+        if org.is_empty() || repo.is_empty() || repo.contains('/') {
+            return None;
+        }
+        Some(Repository::new(org, repo))
+    }
+
+    /// The repository of the `origin` remote of the git checkout we are running in.
+    pub fn from_git_origin() -> anyhow::Result<Self> {
+        let output = Command::new("git")
+            .arg("remote")
+            .arg("get-url")
+            .arg("origin")
+            .output()
+            .context("running `git`")?;
+
+        if !output.status.success() {
+            anyhow::bail!(
+                "no `origin` git remote found ({}); pass `--repository owner/name`",
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+
+        let url = String::from_utf8_lossy(&output.stdout);
+        Repository::from_remote_url(&url).with_context(|| {
+            format!(
+                "cannot tell the github repository from the `origin` remote `{}`; \
+                 pass `--repository owner/name`",
+                url.trim()
+            )
+        })
+    }
 }
 
 impl std::fmt::Display for Repository {
@@ -76,14 +126,72 @@ impl std::fmt::Display for IssueId {
     }
 }
 
+/// Accepts `[org/repo#123]` as well as `https://github.com/org/repo/issues/123`.
 impl std::str::FromStr for IssueId {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let Some(c) = TRACKING_ISSUE.captures(s) else {
+        let Some(c) = TRACKING_ISSUE
+            .captures(s)
+            .or_else(|| ISSUE_URL.captures(s.trim()))
+        else {
             anyhow::bail!("invalid issue-id")
         };
 
         Ok(IssueId::new(Repository::new(&c[1], &c[2]), c[3].parse()?))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{IssueId, Repository};
+
+    #[test]
+    fn repository_from_remote_url() {
+        let expected = Some(Repository::new("rust-lang", "rust-project-goals"));
+        for url in [
+            "git@github.com:rust-lang/rust-project-goals.git",
+            "git@github.com:rust-lang/rust-project-goals",
+            "ssh://git@github.com/rust-lang/rust-project-goals.git",
+            "https://github.com/rust-lang/rust-project-goals.git",
+            "https://github.com/rust-lang/rust-project-goals\n",
+        ] {
+            assert_eq!(Repository::from_remote_url(url), expected, "{url}");
+        }
+
+        assert_eq!(
+            Repository::from_remote_url("https://github.com/rust-lang"),
+            None
+        );
+        assert_eq!(Repository::from_remote_url("/some/local/path"), None);
+        for url in [
+            "https://gitlab.com/rust-lang/rust-project-goals.git",
+            "https://notgithub.com/rust-lang/rust-project-goals.git",
+            "git@github.com.evil.org:rust-lang/rust-project-goals.git",
+            "https://example.org/mirror/github.com/rust-lang/rust-project-goals",
+        ] {
+            assert_eq!(Repository::from_remote_url(url), None, "{url}");
+        }
+    }
+
+    #[test]
+    fn issue_id_from_url() {
+        let expected = IssueId::new(Repository::new("rust-lang", "rust-project-goals"), 123);
+        for text in [
+            "[rust-lang/rust-project-goals#123]",
+            "https://github.com/rust-lang/rust-project-goals/issues/123",
+            "<https://github.com/rust-lang/rust-project-goals/issues/123>",
+            " https://github.com/rust-lang/rust-project-goals/issues/123/ ",
+        ] {
+            assert_eq!(text.parse::<IssueId>().unwrap(), expected, "{text}");
+        }
+
+        for text in [
+            "https://github.com/rust-lang/rust-project-goals/pull/123",
+            "https://github.com/rust-lang/issues/123",
+            "123",
+        ] {
+            assert!(text.parse::<IssueId>().is_err(), "{text}");
+        }
+    }
+}
@@ -1,4 +1,9 @@
-use std::{collections::BTreeSet, process::Command, str::FromStr};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    process::Command,
+    str::FromStr,
+    sync::{Mutex, OnceLock},
+};
 
 use anyhow::Context;
 use chrono::NaiveDate;
@@ -7,7 +12,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::{re, util::comma};
 
-use super::{issue_id::Repository, labels::GhLabel, milestone::GhMilestone};
+use super::{issue_id::Repository, labels::GhLabel, milestone::GhMilestone, LoggedOutput};
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
 pub struct ExistingGithubIssue {
@@ -88,7 +93,7 @@ pub fn count_issues_matching_search(
         .arg("all")
         .arg("--json")
         .arg("state")
-        .output()?;
+        .logged_output()?;
 
     let existing_issues: Vec<JustState> = serde_json::from_slice(&output.stdout)?;
 
@@ -113,7 +118,7 @@ pub fn fetch_issue(repository: &Repository, issue: u64) -> anyhow::Result<Existi
         .arg(&format!("{issue}"))
         .arg("--json")
         .arg("title,assignees,number,comments,body,state,labels,milestone")
-        .output()?;
+        .logged_output()?;
 
     let e_i: ExistingGithubIssueJson = serde_json::from_slice(&output.stdout)?;
 
@@ -127,6 +132,18 @@ pub fn list_issues_in_milestone(
     list_issues(repository, &[("-m", timeframe)])
 }
 
+pub fn list_issues_with_label(
+    repository: &Repository,
+    label: &str,
+) -> anyhow::Result<Vec<ExistingGithubIssue>> {
+    list_issues(repository, &[("-l", label)])
+}
+
+/// Maximum number of issues fetched by [`list_issues`]. The GitHub API returns at most 100 issues
+/// per request, but `gh issue list -L` does the paging itself and returns everything up to the
+/// limit as a single JSON array, so anything below the limit is the complete list.
+const ISSUE_LIST_LIMIT: usize = 5000;
+
 pub fn list_issues(
     repository: &Repository,
     filter: &[(&str, &str)],
@@ -140,7 +157,7 @@ pub fn list_issues(
         .arg("-s")
         .arg("all")
         .arg("-L")
-        .arg("5000");
+        .arg(ISSUE_LIST_LIMIT.to_string());
 
     for (opt, val) in filter {
         cmd.arg(opt);
@@ -150,10 +167,32 @@ pub fn list_issues(
     let output = cmd
         .arg("--json")
         .arg("title,assignees,number,comments,body,state,labels,milestone")
-        .output()
+        .logged_output()
         .with_context(|| format!("running github cli tool `gh`"))?;
 
-    let existing_issues: Vec<ExistingGithubIssueJson> = serde_json::from_slice(&output.stdout)?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "failed to list issues in `{}`: {}",
+            repository,
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    parse_issue_list(&output.stdout, ISSUE_LIST_LIMIT)
+}
+
+/// Parse the JSON output of `gh issue list`. If we got `limit` issues, there may be more that
+/// were not returned; we error out rather than have callers believe those issues do not exist
+/// (which would lead to duplicate issues being created).
+fn parse_issue_list(json: &[u8], limit: usize) -> anyhow::Result<Vec<ExistingGithubIssue>> {
+    let existing_issues: Vec<ExistingGithubIssueJson> = serde_json::from_slice(json)?;
+
+    if existing_issues.len() >= limit {
+        anyhow::bail!(
+            "found {} issues, which is the limit; the list may be incomplete",
+            existing_issues.len()
+        );
+    }
 
     Ok(existing_issues
         .into_iter()
@@ -161,6 +200,7 @@ pub fn list_issues(
         .collect())
 }
 
+/// Create an issue. If `milestone` is empty, the issue is created without a milestone.
 pub fn create_issue(
     repository: &Repository,
     body: &str,
@@ -169,7 +209,8 @@ pub fn create_issue(
     assignees: &BTreeSet<String>,
     milestone: &str,
 ) -> anyhow::Result<()> {
-    let output = Command::new("gh")
+    let mut command = Command::new("gh");
+    command
         .arg("-R")
         .arg(&repository.to_string())
         .arg("issue")
@@ -181,10 +222,11 @@ pub fn create_issue(
         .arg("-l")
         .arg(labels.join(","))
         .arg("-a")
-        .arg(comma(&assignees))
-        .arg("-m")
-        .arg(&milestone)
-        .output()?;
+        .arg(comma(&assignees));
+    if !milestone.is_empty() {
+        command.arg("-m").arg(milestone);
+    }
+    let output = command.logged_output()?;
 
     if !output.status.success() {
         Err(anyhow::anyhow!(
@@ -208,7 +250,7 @@ pub fn change_title(repository: &Repository, number: u64, title: &str) -> anyhow
         .arg("-t")
         .arg(title);
 
-    let output = command.output()?;
+    let output = command.logged_output()?;
     if !output.status.success() {
         Err(anyhow::anyhow!(
             "failed to change milestone `{}`: {}",
@@ -235,7 +277,7 @@ pub fn change_milestone(
         .arg("-m")
         .arg(milestone);
 
-    let output = command.output()?;
+    let output = command.logged_output()?;
     if !output.status.success() {
         Err(anyhow::anyhow!(
             "failed to change milestone `{}`: {}",
@@ -256,7 +298,7 @@ pub fn create_comment(repository: &Repository, number: u64, body: &str) -> anyho
         .arg(number.to_string())
         .arg("-b")
         .arg(body)
-        .output()?;
+        .logged_output()?;
 
     if !output.status.success() {
         Err(anyhow::anyhow!(
@@ -269,6 +311,109 @@ pub fn create_comment(repository: &Repository, number: u64, body: &str) -> anyho
     }
 }
 
+pub fn update_comment(
+    repository: &Repository,
+    comment: &ExistingGithubComment,
+    body: &str,
+) -> anyhow::Result<()> {
+    let output = Command::new("gh")
+        .arg("api")
+        .arg("--method")
+        .arg("PATCH")
+        .arg(format!(
+            "repos/{repository}/issues/comments/{}",
+            comment.id()?
+        ))
+        .arg("-f")
+        .arg(format!("body={body}"))
+        .logged_output()?;
+
+    if !output.status.success() {
+        Err(anyhow::anyhow!(
+            "failed to update comment `{}`: {}",
+            comment.url,
+            String::from_utf8_lossy(&output.stderr)
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+pub fn delete_comment(
+    repository: &Repository,
+    comment: &ExistingGithubComment,
+) -> anyhow::Result<()> {
+    let output = Command::new("gh")
+        .arg("api")
+        .arg("--method")
+        .arg("DELETE")
+        .arg(format!(
+            "repos/{repository}/issues/comments/{}",
+            comment.id()?
+        ))
+        .logged_output()?;
+
+    if !output.status.success() {
+        Err(anyhow::anyhow!(
+            "failed to delete comment `{}`: {}",
+            comment.url,
+            String::from_utf8_lossy(&output.stderr)
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+/// A reaction (e.g., 👍) left on a comment.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+pub struct GhReaction {
+    /// Just github username, no `@`
+    pub user: String,
+    /// As named by the github API, e.g. `+1` for a thumbs-up.
+    pub content: String,
+}
+
+impl GhReaction {
+    pub fn is_thumbs_up(&self) -> bool {
+        self.content == "+1"
+    }
+}
+
+/// All reactions left on `comment`.
+pub fn list_comment_reactions(
+    repository: &Repository,
+    comment: &ExistingGithubComment,
+) -> anyhow::Result<Vec<GhReaction>> {
+    let output = Command::new("gh")
+        .arg("api")
+        .arg("--paginate")
+        .arg(format!(
+            "repos/{repository}/issues/comments/{}/reactions",
+            comment.id()?
+        ))
+        .arg("--jq")
+        .arg(".[] | {user: .user.login, content: .content}")
+        .logged_output()?;
+
+    if !output.status.success() {
+        anyhow::bail!(
+            "failed to list the reactions on comment `{}`: {}",
+            comment.url,
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    // One JSON object per line, so that the pages can simply be concatenated.
+    String::from_utf8(output.stdout)?
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            serde_json::from_str(line)
+                .with_context(|| format!("parsing reaction on `{}`: {line}", comment.url))
+        })
+        .collect()
+}
+
 pub fn update_issue_body(repository: &Repository, number: u64, body: &str) -> anyhow::Result<()> {
     let output = Command::new("gh")
         .arg("-R")
@@ -278,7 +423,7 @@ pub fn update_issue_body(repository: &Repository, number: u64, body: &str) -> an
         .arg(number.to_string())
         .arg("-b")
         .arg(body)
-        .output()?;
+        .logged_output()?;
 
     if !output.status.success() {
         Err(anyhow::anyhow!(
@@ -291,6 +436,470 @@ pub fn update_issue_body(repository: &Repository, number: u64, body: &str) -> an
     }
 }
 
+pub fn add_label(repository: &Repository, number: u64, label: &str) -> anyhow::Result<()> {
+    let output = Command::new("gh")
+        .arg("-R")
+        .arg(repository.to_string())
+        .arg("issue")
+        .arg("edit")
+        .arg(number.to_string())
+        .arg("--add-label")
+        .arg(label)
+        .logged_output()?;
+
+    if !output.status.success() {
+        Err(anyhow::anyhow!(
+            "failed to add label `{}` to issue `{}`: {}",
+            label,
+            number,
+            String::from_utf8_lossy(&output.stderr)
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+pub fn remove_label(repository: &Repository, number: u64, label: &str) -> anyhow::Result<()> {
+    let output = Command::new("gh")
+        .arg("-R")
+        .arg(repository.to_string())
+        .arg("issue")
+        .arg("edit")
+        .arg(number.to_string())
+        .arg("--remove-label")
+        .arg(label)
+        .logged_output()?;
+
+    if !output.status.success() {
+        Err(anyhow::anyhow!(
+            "failed to remove label `{}` from issue `{}`: {}",
+            label,
+            number,
+            String::from_utf8_lossy(&output.stderr)
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+pub fn close_issue(repository: &Repository, number: u64) -> anyhow::Result<()> {
+    let output = Command::new("gh")
+        .arg("-R")
+        .arg(repository.to_string())
+        .arg("issue")
+        .arg("close")
+        .arg(number.to_string())
+        .logged_output()?;
+
+    if !output.status.success() {
+        Err(anyhow::anyhow!(
+            "failed to close issue `{}`: {}",
+            number,
+            String::from_utf8_lossy(&output.stderr)
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+/// Delete an issue for good, which requires admin rights on the repository.
+pub fn delete_issue(repository: &Repository, number: u64) -> anyhow::Result<()> {
+    let output = Command::new("gh")
+        .arg("-R")
+        .arg(repository.to_string())
+        .arg("issue")
+        .arg("delete")
+        .arg(number.to_string())
+        .arg("--yes")
+        .logged_output()?;
+
+    if !output.status.success() {
+        Err(anyhow::anyhow!(
+            "failed to delete issue `{}`: {}",
+            number,
+            String::from_utf8_lossy(&output.stderr)
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+pub fn reopen_issue(repository: &Repository, number: u64) -> anyhow::Result<()> {
+    let output = Command::new("gh")
+        .arg("-R")
+        .arg(repository.to_string())
+        .arg("issue")
+        .arg("reopen")
+        .arg(number.to_string())
+        .logged_output()?;
+
+    if !output.status.success() {
+        Err(anyhow::anyhow!(
+            "failed to reopen issue `{}`: {}",
+            number,
+            String::from_utf8_lossy(&output.stderr)
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+/// Maximum number of items fetched by [`list_project_issues`].
+const PROJECT_ITEM_LIMIT: usize = 5000;
+
+#[derive(Debug, Deserialize)]
+struct ProjectItemsJson {
+    items: Vec<ProjectItemJson>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ProjectItemJson {
+    content: ProjectItemContentJson,
+}
+
+#[derive(Debug, Deserialize)]
+struct ProjectItemContentJson {
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    number: Option<u64>,
+    #[serde(default)]
+    repository: Option<String>,
+}
+
+/// The numbers of the issues of `repository` that are on project (board) `project`
+/// of the repository's owner.
+pub fn list_project_issues(repository: &Repository, project: u64) -> anyhow::Result<BTreeSet<u64>> {
+    let output = Command::new("gh")
+        .arg("project")
+        .arg("item-list")
+        .arg(project.to_string())
+        .arg("--owner")
+        .arg(&repository.org)
+        .arg("--format")
+        .arg("json")
+        .arg("--limit")
+        .arg(PROJECT_ITEM_LIMIT.to_string())
+        .logged_output()?;
+
+    if !output.status.success() {
+        anyhow::bail!(
+            "failed to list the items of project {}: {}",
+            project,
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    let items: ProjectItemsJson = serde_json::from_slice(&output.stdout)
+        .with_context(|| format!("parsing the items of project {project}"))?;
+    if items.items.len() >= PROJECT_ITEM_LIMIT {
+        anyhow::bail!(
+            "project {project} has {PROJECT_ITEM_LIMIT} items or more, some may be missing"
+        );
+    }
+    let repository = repository.to_string();
+    Ok(items
+        .items
+        .into_iter()
+        .filter(|item| item.content.kind == "Issue")
+        .filter(|item| item.content.repository.as_deref() == Some(repository.as_str()))
+        .filter_map(|item| item.content.number)
+        .collect())
+}
+
+#[derive(Debug, Deserialize)]
+struct ProjectIdJson {
+    id: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ProjectFieldsJson {
+    fields: Vec<ProjectFieldJson>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ProjectFieldJson {
+    id: String,
+    name: String,
+    #[serde(default)]
+    options: Vec<ProjectFieldOptionJson>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ProjectFieldOptionJson {
+    id: String,
+    name: String,
+}
+
+/// Runs `gh project <args>` and returns its output, which is requested in JSON.
+fn gh_project(args: &[&str]) -> anyhow::Result<Vec<u8>> {
+    let output = Command::new("gh")
+        .arg("project")
+        .args(args)
+        .arg("--format")
+        .arg("json")
+        .logged_output()?;
+
+    if !output.status.success() {
+        anyhow::bail!(
+            "`gh project {}` failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+    Ok(output.stdout)
+}
+
+/// Add issue `number` to project (board) `project` of the repository's owner, and set its
+/// `Status` field to `status` if given.
+pub fn add_to_project(
+    repository: &Repository,
+    project: u64,
+    number: u64,
+    status: Option<&str>,
+) -> anyhow::Result<()> {
+    let project = project.to_string();
+    let owner = repository.org.as_str();
+    let url = format!("https://github.com/{repository}/issues/{number}");
+    let item: ProjectIdJson = serde_json::from_slice(&gh_project(&[
+        "item-add", &project, "--owner", owner, "--url", &url,
+    ])?)?;
+
+    let Some(status) = status else {
+        return Ok(());
+    };
+
+    let project_id: ProjectIdJson =
+        serde_json::from_slice(&gh_project(&["view", &project, "--owner", owner])?)?;
+    let fields: ProjectFieldsJson =
+        serde_json::from_slice(&gh_project(&["field-list", &project, "--owner", owner])?)?;
+    let Some(field) = fields.fields.iter().find(|field| field.name == "Status") else {
+        anyhow::bail!("project {project} has no `Status` field");
+    };
+    let Some(option) = field.options.iter().find(|option| option.name == status) else {
+        anyhow::bail!(
+            "project {project} has no status `{status}` (expected one of {})",
+            field
+                .options
+                .iter()
+                .map(|option| format!("`{}`", option.name))
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+    };
+
+    let output = Command::new("gh")
+        .arg("project")
+        .arg("item-edit")
+        .arg("--id")
+        .arg(&item.id)
+        .arg("--project-id")
+        .arg(&project_id.id)
+        .arg("--field-id")
+        .arg(&field.id)
+        .arg("--single-select-option-id")
+        .arg(&option.id)
+        .logged_output()?;
+
+    if !output.status.success() {
+        Err(anyhow::anyhow!(
+            "failed to set the status of issue `{}` in project {}: {}",
+            number,
+            project,
+            String::from_utf8_lossy(&output.stderr)
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+/// Runs a GraphQL query (or mutation) with `gh api graphql` and returns its `data`.
+/// Each of `fields` is a flag (`-f` for a string variable, `-F` for a number) and `name=value`.
+fn gh_graphql(query: &str, fields: &[(&str, String)]) -> anyhow::Result<serde_json::Value> {
+    let mut command = Command::new("gh");
+    command
+        .arg("api")
+        .arg("graphql")
+        .arg("-f")
+        .arg(format!("query={query}"));
+    for (flag, field) in fields {
+        command.arg(flag).arg(field);
+    }
+    let output = command.logged_output()?;
+
+    if !output.status.success() {
+        anyhow::bail!(
+            "GraphQL query failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+    let mut response: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    Ok(response["data"].take())
+}
+
+/// The `-f`/`-F` fields for the `$owner`, `$name` and `$number` variables of a query on
+/// issue `number` of `repository`.
+fn issue_fields(repository: &Repository, number: u64) -> [(&'static str, String); 3] {
+    [
+        ("-f", format!("owner={}", repository.org)),
+        ("-f", format!("name={}", repository.repo)),
+        ("-F", format!("number={number}")),
+    ]
+}
+
+#[derive(Clone, Debug, Deserialize)]
+struct IssueTypeJson {
+    id: String,
+    name: String,
+}
+
+/// The ids and names of the issue types (e.g., `Task`) available on `repository`, which is
+/// empty if the repository does not use issue types (they are only available to organizations).
+/// They are only queried once per repository.
+fn issue_types(repository: &Repository) -> anyhow::Result<Vec<IssueTypeJson>> {
+    static ISSUE_TYPES: OnceLock<Mutex<BTreeMap<Repository, Vec<IssueTypeJson>>>> = OnceLock::new();
+    let cache = ISSUE_TYPES.get_or_init(Default::default);
+    if let Some(issue_types) = cache.lock().unwrap().get(repository) {
+        return Ok(issue_types.clone());
+    }
+    let issue_types = query_issue_types(repository)?;
+    cache
+        .lock()
+        .unwrap()
+        .insert(repository.clone(), issue_types.clone());
+    Ok(issue_types)
+}
+
+fn query_issue_types(repository: &Repository) -> anyhow::Result<Vec<IssueTypeJson>> {
+    let data = gh_graphql(
+        "query($owner: String!, $name: String!) { repository(owner: $owner, name: $name) { \
+         issueTypes(first: 100) { nodes { id name } } } }",
+        &[
+            ("-f", format!("owner={}", repository.org)),
+            ("-f", format!("name={}", repository.repo)),
+        ],
+    );
+    let data = match data {
+        Ok(data) => data,
+        // Older github servers do not know about issue types at all.
+        Err(e) if e.to_string().contains("issueTypes") => return Ok(vec![]),
+        Err(e) => return Err(e.context(format!("listing the issue types of `{repository}`"))),
+    };
+    match data["repository"]["issueTypes"]["nodes"].as_array() {
+        Some(nodes) => nodes
+            .iter()
+            .map(|node| Ok(IssueTypeJson::deserialize(node)?))
+            .collect(),
+        None => Ok(vec![]),
+    }
+}
+
+/// The names of the issue types (e.g., `Task`) available on `repository`; empty if the
+/// repository does not use issue types.
+pub fn list_issue_types(repository: &Repository) -> anyhow::Result<Vec<String>> {
+    Ok(issue_types(repository)?
+        .into_iter()
+        .map(|issue_type| issue_type.name)
+        .collect())
+}
+
+/// How many issues [`fetch_issue_types`] looks up per query.
+const ISSUE_TYPE_BATCH: usize = 50;
+
+/// The node ids of issues, which mutations need, by repository and issue number.
+fn issue_node_ids() -> &'static Mutex<BTreeMap<(Repository, u64), String>> {
+    static ISSUE_NODE_IDS: OnceLock<Mutex<BTreeMap<(Repository, u64), String>>> = OnceLock::new();
+    ISSUE_NODE_IDS.get_or_init(Default::default)
+}
+
+/// The names of the types of the issues `numbers`, for those that have one. The issues are
+/// looked up in batches (one aliased field per issue), and their node ids are kept for
+/// [`set_issue_type`].
+pub fn fetch_issue_types(
+    repository: &Repository,
+    numbers: &[u64],
+) -> anyhow::Result<BTreeMap<u64, String>> {
+    let mut issue_types = BTreeMap::new();
+    for batch in numbers.chunks(ISSUE_TYPE_BATCH) {
+        let fields: String = batch
+            .iter()
+            .map(|number| {
+                format!("i{number}: issue(number: {number}) {{ id issueType {{ name }} }} ")
+            })
+            .collect();
+        let data = gh_graphql(
+            &format!(
+                "query($owner: String!, $name: String!) {{ \
+                 repository(owner: $owner, name: $name) {{ {fields}}} }}"
+            ),
+            &[
+                ("-f", format!("owner={}", repository.org)),
+                ("-f", format!("name={}", repository.repo)),
+            ],
+        )
+        .with_context(|| format!("fetching the types of {} issue(s)", batch.len()))?;
+
+        let mut node_ids = issue_node_ids().lock().unwrap();
+        for &number in batch {
+            let issue = &data["repository"][format!("i{number}")];
+            if let Some(id) = issue["id"].as_str() {
+                node_ids.insert((repository.clone(), number), id.to_string());
+            }
+            if let Some(name) = issue["issueType"]["name"].as_str() {
+                issue_types.insert(number, name.to_string());
+            }
+        }
+    }
+    Ok(issue_types)
+}
+
+/// The node id of issue `number`, unless it was already looked up.
+fn issue_node_id(repository: &Repository, number: u64) -> anyhow::Result<String> {
+    let key = (repository.clone(), number);
+    if let Some(id) = issue_node_ids().lock().unwrap().get(&key) {
+        return Ok(id.clone());
+    }
+    let data = gh_graphql(
+        "query($owner: String!, $name: String!, $number: Int!) { \
+         repository(owner: $owner, name: $name) { issue(number: $number) { id } } }",
+        &issue_fields(repository, number),
+    )?;
+    let Some(id) = data["repository"]["issue"]["id"].as_str() else {
+        anyhow::bail!("no issue #{number} in `{repository}`");
+    };
+    issue_node_ids().lock().unwrap().insert(key, id.to_string());
+    Ok(id.to_string())
+}
+
+/// Give issue `number` the type named `issue_type`, which must be available on `repository`.
+pub fn set_issue_type(
+    repository: &Repository,
+    number: u64,
+    issue_type: &str,
+) -> anyhow::Result<()> {
+    let issue_types = issue_types(repository)?;
+    let Some(issue_type) = issue_types.iter().find(|t| t.name == issue_type) else {
+        anyhow::bail!("`{repository}` has no issue type `{issue_type}`");
+    };
+
+    let issue_id = issue_node_id(repository, number)?;
+    gh_graphql(
+        "mutation($issue: ID!, $type: ID!) { \
+         updateIssueIssueType(input: {issueId: $issue, issueTypeId: $type}) { issue { number } } }",
+        &[
+            ("-f", format!("issue={issue_id}")),
+            ("-f", format!("type={}", issue_type.id)),
+        ],
+    )
+    .with_context(|| {
+        format!(
+            "setting the type of issue #{number} to `{}`",
+            issue_type.name
+        )
+    })?;
+    Ok(())
+}
+
 pub fn sync_assignees(
     repository: &Repository,
     number: u64,
@@ -313,7 +922,7 @@ pub fn sync_assignees(
         command.arg("--add-assignee").arg(comma(&add_owners));
     }
 
-    let output = command.output()?;
+    let output = command.logged_output()?;
     if !output.status.success() {
         Err(anyhow::anyhow!(
             "failed to sync issue `{}`: {}",
@@ -327,15 +936,41 @@ pub fn sync_assignees(
 
 pub const FLAGSHIP_LABEL: &str = "Flagship Goal";
 
+pub const TRACKING_ISSUE_LABEL: &str = "C-tracking-issue";
+
+/// Label applied to tracking issues that were closed at the end of a goal period.
+pub const COMPLETED_LABEL: &str = "S-completed";
+
 pub const LOCK_TEXT: &str = "This issue is intended for status updates only.\n\nFor general questions or comments, please contact the owner(s) directly.";
 
 pub const CONTINUING_GOAL_PREFIX: &str = "This is a continuing project goal, and the updates below this comment will be for the new period";
 
+/// Hidden markers appended to lock and continuation comments rendered from custom templates,
+/// which may not contain [`LOCK_TEXT`] or [`CONTINUING_GOAL_PREFIX`].
+pub const LOCK_MARKER: &str = "<!-- rust-project-goals: lock -->";
+
+pub const CONTINUING_GOAL_MARKER: &str = "<!-- rust-project-goals: continuing -->";
+
+pub const ARCHIVED_GOAL_PREFIX: &str =
+    "This goal period has ended, so we are closing this tracking issue.";
+
+pub const TASK_REGRESSION_PREFIX: &str = "The number of completed tasks for this goal went down";
+
+pub const DUPLICATE_GOAL_PREFIX: &str =
+    "Closing as a duplicate of the tracking issue for this goal,";
+
+/// Hidden marker on the FCP checklist comment, so that posting it again updates it.
+pub const FCP_CHECKLIST_MARKER: &str = "<!-- rust-project-goals: fcp checklist -->";
+
+/// Hidden marker on the comment that lists the tasks of a goal whose tracking issue body
+/// would be too long to include them.
+pub const TASKS_COMMENT_MARKER: &str = "<!-- rust-project-goals: tasks -->";
+
 impl ExistingGithubIssue {
     /// We use the presence of a "lock comment" as a signal that we successfully locked the issue.
     /// The github CLI doesn't let you query that directly.
     pub fn was_locked(&self) -> bool {
-        self.comments.iter().any(|c| c.body.trim() == LOCK_TEXT)
+        self.comments.iter().any(|c| c.is_lock_comment())
     }
 
     /// True if we have a label with the given name.
@@ -356,7 +991,7 @@ pub fn lock_issue(repository: &Repository, number: u64) -> anyhow::Result<()> {
         .arg("issue")
         .arg("lock")
         .arg(number.to_string())
-        .output()?;
+        .logged_output()?;
 
     if !output.status.success() {
         if !output.stderr.starts_with(b"already locked") {
@@ -375,7 +1010,39 @@ impl ExistingGithubComment {
     /// True if this is one of the special comments that we put on issues.
     pub fn is_automated_comment(&self) -> bool {
         let trimmed_body = self.body.trim();
-        trimmed_body == LOCK_TEXT || trimmed_body.starts_with(CONTINUING_GOAL_PREFIX)
+        trimmed_body == LOCK_TEXT
+            || trimmed_body.starts_with(CONTINUING_GOAL_PREFIX)
+            || trimmed_body.starts_with(ARCHIVED_GOAL_PREFIX)
+            || trimmed_body.starts_with(TASK_REGRESSION_PREFIX)
+            || trimmed_body.starts_with(DUPLICATE_GOAL_PREFIX)
+            || trimmed_body.contains(LOCK_MARKER)
+            || trimmed_body.contains(CONTINUING_GOAL_MARKER)
+            || trimmed_body.contains(FCP_CHECKLIST_MARKER)
+            || trimmed_body.contains(TASKS_COMMENT_MARKER)
+    }
+
+    /// True if this is the comment we post when locking an issue.
+    pub fn is_lock_comment(&self) -> bool {
+        self.body.trim() == LOCK_TEXT || self.body.contains(LOCK_MARKER)
+    }
+
+    /// True if this is the comment listing the tasks of the goal (see [`TASKS_COMMENT_MARKER`]).
+    pub fn is_tasks_comment(&self) -> bool {
+        self.body.contains(TASKS_COMMENT_MARKER)
+    }
+
+    /// True if this is the comment we post when a goal continues into a new period.
+    pub fn is_continuing_goal_comment(&self) -> bool {
+        self.body.trim().starts_with(CONTINUING_GOAL_PREFIX)
+            || self.body.contains(CONTINUING_GOAL_MARKER)
+    }
+
+    /// The id of the comment, from the end of its URL.
+    pub fn id(&self) -> anyhow::Result<u64> {
+        let Some(captures) = re::COMMENT_ID.captures(&self.url) else {
+            anyhow::bail!("no comment id in `{}`", self.url);
+        };
+        Ok(captures[1].parse()?)
     }
 
     pub fn created_at_date(&self) -> NaiveDate {
@@ -411,7 +1078,8 @@ impl From<ExistingGithubIssueJson> for ExistingGithubIssue {
 /// Identify how many sub-items have been completed.
 /// These can be encoded in two different ways:
 ///
-/// * Option A, the most common, is to have checkboxes in the issue. We just count the number that are checked.
+/// * Option A, the most common, is to have checkboxes in the issue. We just count the number that are checked,
+///   leaving out stretch goals (marked `(stretch)`), which do not count towards completion.
 /// * Option B is to include a metadata line called "Tracked issues" that lists a search query. We count the number of open vs closed issues in that query.
 ///
 /// Returns a tuple (completed, total) with the number of completed items and the total number of items.
@@ -478,7 +1146,9 @@ fn try_checkboxes(issue: &ExistingGithubIssue) -> anyhow::Result<Progress> {
             }
         }
 
-        if re::CHECKED_CHECKBOX.is_match(line) {
+        if re::STRETCH.is_match(line) {
+            continue;
+        } else if re::CHECKED_CHECKBOX.is_match(line) {
             total += 1;
             completed += 1;
         } else if re::CHECKBOX.is_match(line) {
@@ -494,3 +1164,44 @@ fn try_checkboxes(issue: &ExistingGithubIssue) -> anyhow::Result<Progress> {
         Ok(Progress::Tracked { completed, total })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// JSON in the format returned by `gh issue list --json ...`, with `count` issues.
+    fn issue_list_json(count: u64) -> String {
+        let issues: Vec<_> = (1..=count)
+            .map(|number| {
+                serde_json::json!({
+                    "title": format!("Goal {number}"),
+                    "number": number,
+                    "assignees": [{ "login": "octocat", "name": "" }],
+                    "comments": [],
+                    "body": "",
+                    "state": "OPEN",
+                    "labels": [{ "name": "C-tracking-issue", "color": "f5f1fd" }],
+                    "milestone": { "number": 1, "title": "2025h1", "description": "", "dueOn": null },
+                })
+            })
+            .collect();
+        serde_json::to_string(&issues).unwrap()
+    }
+
+    /// Paging happens inside `gh`; all we see is one array, which may be longer than an API page.
+    #[test]
+    fn issue_list_longer_than_an_api_page_is_kept_whole() {
+        let issues = parse_issue_list(issue_list_json(250).as_bytes(), ISSUE_LIST_LIMIT).unwrap();
+        assert_eq!(issues.len(), 250);
+        assert_eq!(
+            issues.iter().map(|i| i.number).collect::<Vec<_>>(),
+            (1..=250).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn issue_list_at_limit_is_an_error() {
+        assert!(parse_issue_list(issue_list_json(150).as_bytes(), 150).is_err());
+        assert!(parse_issue_list(issue_list_json(149).as_bytes(), 150).is_ok());
+    }
+}
@@ -1,5 +1,7 @@
 use std::process::Command;
 
+use super::LoggedOutput;
+
 use serde::{Deserialize, Serialize};
 
 use super::issue_id::Repository;
@@ -19,7 +21,7 @@ impl GhLabel {
             .arg("list")
             .arg("--json")
             .arg("name,color")
-            .output()?;
+            .logged_output()?;
 
         let labels: Vec<GhLabel> = serde_json::from_slice(&output.stdout)?;
 
@@ -36,7 +38,7 @@ impl GhLabel {
             .arg("--color")
             .arg(&self.color)
             .arg("--force")
-            .output()?;
+            .logged_output()?;
 
         if !output.status.success() {
             Err(anyhow::anyhow!(
@@ -48,4 +50,27 @@ impl GhLabel {
             Ok(())
         }
     }
+
+    /// Change the color of an existing label to `self.color`.
+    pub fn update_color(&self, repository: &Repository) -> anyhow::Result<()> {
+        let output = Command::new("gh")
+            .arg("-R")
+            .arg(repository.to_string())
+            .arg("label")
+            .arg("edit")
+            .arg(&self.name)
+            .arg("--color")
+            .arg(&self.color)
+            .logged_output()?;
+
+        if !output.status.success() {
+            Err(anyhow::anyhow!(
+                "failed to update color of label `{}`: {}",
+                self.name,
+                String::from_utf8_lossy(&output.stderr)
+            ))
+        } else {
+            Ok(())
+        }
+    }
 }
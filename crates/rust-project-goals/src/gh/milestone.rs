@@ -1,5 +1,12 @@
+use std::process::Command;
+
+use super::LoggedOutput;
+
+use anyhow::Context;
 use serde::{Deserialize, Serialize};
 
+use super::issue_id::Repository;
+
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
 pub struct GhMilestone {
     pub number: u64,
@@ -8,3 +15,123 @@ pub struct GhMilestone {
     #[serde(rename = "dueOn")]
     pub due_on: Option<String>,
 }
+
+/// Returns the titles of all milestones (open or closed) in `repository`.
+pub fn list_milestone_titles(repository: &Repository) -> anyhow::Result<Vec<String>> {
+    let output = Command::new("gh")
+        .arg("api")
+        .arg("--paginate")
+        .arg(format!("repos/{repository}/milestones?state=all"))
+        .arg("--jq")
+        .arg(".[].title")
+        .logged_output()
+        .context("running github cli tool `gh`")?;
+
+    if !output.status.success() {
+        Err(anyhow::anyhow!(
+            "failed to list milestones in `{}`: {}",
+            repository,
+            String::from_utf8_lossy(&output.stderr)
+        ))
+    } else {
+        Ok(String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .map(|line| line.to_string())
+            .collect())
+    }
+}
+
+/// A milestone as returned by the REST API (which, unlike `gh issue list`, uses `due_on`).
+#[derive(Debug, Deserialize)]
+struct ApiMilestone {
+    number: u64,
+    title: String,
+    due_on: Option<String>,
+}
+
+/// The milestone titled `title` in `repository`, if there is one.
+fn find_milestone(repository: &Repository, title: &str) -> anyhow::Result<Option<ApiMilestone>> {
+    let output = Command::new("gh")
+        .arg("api")
+        .arg("--paginate")
+        .arg(format!("repos/{repository}/milestones?state=all"))
+        .arg("--jq")
+        .arg(".[]")
+        .logged_output()
+        .context("running github cli tool `gh`")?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "failed to list milestones in `{}`: {}",
+            repository,
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    for line in String::from_utf8_lossy(&output.stdout).lines() {
+        let milestone: ApiMilestone = serde_json::from_str(line)?;
+        if milestone.title == title {
+            return Ok(Some(milestone));
+        }
+    }
+    Ok(None)
+}
+
+/// The due date of the milestone titled `title` in `repository` (like `2025-06-30T00:00:00Z`),
+/// if the milestone exists and has one.
+pub fn milestone_due_on(repository: &Repository, title: &str) -> anyhow::Result<Option<String>> {
+    Ok(find_milestone(repository, title)?.and_then(|milestone| milestone.due_on))
+}
+
+/// Sets the due date of the existing milestone titled `title` in `repository` to `due_on`
+/// (a `YYYY-MM-DD` date, set as midnight UTC).
+pub fn set_milestone_due(repository: &Repository, title: &str, due_on: &str) -> anyhow::Result<()> {
+    let Some(milestone) = find_milestone(repository, title)? else {
+        anyhow::bail!("no milestone `{title}` in `{repository}`");
+    };
+
+    let output = Command::new("gh")
+        .arg("api")
+        .arg("--method")
+        .arg("PATCH")
+        .arg(format!(
+            "repos/{repository}/milestones/{}",
+            milestone.number
+        ))
+        .arg("-f")
+        .arg(format!("due_on={due_on}T00:00:00Z"))
+        .logged_output()
+        .context("running github cli tool `gh`")?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "failed to set the due date of milestone `{}` in `{}`: {}",
+            title,
+            repository,
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    Ok(())
+}
+
+/// Creates an (open) milestone titled `title` in `repository`.
+pub fn create_milestone(repository: &Repository, title: &str) -> anyhow::Result<()> {
+    let output = Command::new("gh")
+        .arg("api")
+        .arg("--method")
+        .arg("POST")
+        .arg(format!("repos/{repository}/milestones"))
+        .arg("-f")
+        .arg(format!("title={title}"))
+        .logged_output()
+        .context("running github cli tool `gh`")?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "failed to create milestone `{}` in `{}`: {}",
+            title,
+            repository,
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    Ok(())
+}
@@ -1,10 +1,13 @@
 use std::path::Path;
 use std::sync::Arc;
-use std::{collections::BTreeSet, path::PathBuf};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::PathBuf,
+};
 
 use anyhow::{bail, Context};
 use regex::Regex;
-use spanned::Spanned;
+use spanned::{Span, Spanned};
 
 use crate::config::{Configuration, TeamAskDetails};
 use crate::gh::issue_id::{IssueId, Repository};
@@ -46,13 +49,75 @@ pub struct Metadata {
     pub title: String,
     pub short_title: Spanned<String>,
     pub pocs: String,
+    /// Owners other than the point of contact (from the optional `Additional owners` row)
+    pub additional_owners: String,
     pub status: Status,
     pub tracking_issue: Option<IssueId>,
+    /// False if the goal opted out of having its tracking issue locked (via the optional `Lockable` row)
+    pub lockable: bool,
+    /// True if the goal should never get a tracking issue (via the optional `No tracking issue` row)
+    pub no_tracking_issue: bool,
+    /// True if the tasks of each subgoal are shown in a collapsed block in the tracking issue
+    /// (via the optional `Collapse subgoals` row)
+    pub collapse_subgoals: bool,
+    /// True if the goal has no tasks on purpose (via the optional `Tasks intentionally empty` row)
+    pub tasks_intentionally_empty: bool,
+    /// Slug (file name without `.md`) of the goal whose tracking issue aggregates this one
+    /// (via the optional `Umbrella` row)
+    pub umbrella: Option<String>,
+    /// Area of the goal (e.g., `Async`) under which goal lists group it
+    /// (via the optional `Category` row)
+    pub category: Option<String>,
+    /// Labels (e.g., `A-async`) applied to the tracking issue on top of the computed ones
+    /// (via the optional `Labels` row)
+    pub extra_labels: Vec<String>,
     pub table: Spanned<Table>,
 }
 
 pub const TRACKING_ISSUE_ROW: &str = "Tracking issue";
 
+pub const POINT_OF_CONTACT_ROW: &str = "Point of contact";
+
+pub const ADDITIONAL_OWNERS_ROW: &str = "Additional owners";
+
+pub const LOCKABLE_ROW: &str = "Lockable";
+
+pub const NO_TRACKING_ISSUE_ROW: &str = "No tracking issue";
+
+pub const COLLAPSE_SUBGOALS_ROW: &str = "Collapse subgoals";
+
+pub const TIMEFRAME_ROW: &str = "Timeframe";
+
+pub const TASKS_INTENTIONALLY_EMPTY_ROW: &str = "Tasks intentionally empty";
+
+pub const UMBRELLA_ROW: &str = "Umbrella";
+
+pub const CATEGORY_ROW: &str = "Category";
+
+pub const LABELS_ROW: &str = "Labels";
+
+/// The category of goals without a `Category` row, listed after all the others.
+pub const OTHER_CATEGORY: &str = "Other";
+
+/// True if `text` looks like the name of a goal file, without `.md` (e.g., `async-closures`).
+fn is_slug(text: &str) -> bool {
+    let text = text.trim();
+    !text.is_empty()
+        && !text.ends_with(".md")
+        && text
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '-' || c == '_')
+}
+
+/// Parse the value of a yes/no row like `Lockable` (`true`/`false`, or `yes`/`no`).
+fn parse_flag(text: &str) -> Option<bool> {
+    match text.trim().to_lowercase().as_str() {
+        "true" | "yes" => Some(true),
+        "false" | "no" => Some(false),
+        _ => None,
+    }
+}
+
 /// Items required to complete the goal.
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub struct GoalPlan {
@@ -66,9 +131,76 @@ pub struct GoalPlan {
 /// Identifies a particular ask for a set of Rust teams
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub struct PlanItem {
+    /// The task, without the `(stretch)` annotation (if any).
     pub text: String,
+    /// The owners, without the effort and blocked annotations (if any).
     pub owners: String,
     pub notes: String,
+    /// Effort annotation found in the owners, like `(est: 2w)`.
+    pub effort: Option<Effort>,
+    /// The reason given by a blocked annotation in the owners, like
+    /// `(blocked: waiting on RFC 1234)`.
+    pub blocked: Option<String>,
+    /// True if the task is annotated `(stretch)`: it is tracked like any other,
+    /// but does not count towards the completion of the goal.
+    pub stretch: bool,
+}
+
+/// The estimated (and, once known, actual) effort of a plan item,
+/// written `(est: 2w)` or `(est: 2w, actual: 3w)` in its owners.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Effort {
+    pub estimated: WorkDays,
+    pub actual: Option<WorkDays>,
+}
+
+impl std::fmt::Display for Effort {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "est: {}", self.estimated)?;
+        if let Some(actual) = self.actual {
+            write!(f, ", actual: {actual}")?;
+        }
+        Ok(())
+    }
+}
+
+/// An amount of work in working days. Annotations can also be in weeks (`w`, 5 days)
+/// or months (`m`, 20 days), and are displayed in the largest unit that divides them.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct WorkDays(pub u32);
+
+impl WorkDays {
+    fn parse(amount: &str, unit: &str) -> anyhow::Result<Self> {
+        let amount: u32 = amount.parse()?;
+        let days = match unit {
+            "d" => 1,
+            "w" => 5,
+            "m" => 20,
+            _ => bail!("unknown effort unit `{unit}` (expected `d`, `w` or `m`)"),
+        };
+        match amount.checked_mul(days) {
+            Some(days) => Ok(WorkDays(days)),
+            None => bail!("effort `{amount}{unit}` is too large"),
+        }
+    }
+
+    /// The sum of `self` and `other`, or an error if it does not fit.
+    pub fn checked_add(self, other: WorkDays) -> anyhow::Result<WorkDays> {
+        match self.0.checked_add(other.0) {
+            Some(days) => Ok(WorkDays(days)),
+            None => bail!("total effort of {self} and {other} is too large"),
+        }
+    }
+}
+
+impl std::fmt::Display for WorkDays {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.0 {
+            days if days > 0 && days % 20 == 0 => write!(f, "{}m", days / 20),
+            days if days > 0 && days % 5 == 0 => write!(f, "{}w", days / 5),
+            days => write!(f, "{days}d"),
+        }
+    }
 }
 
 /// Returns the "owner(s)" of a plan-item, which can be
@@ -101,13 +233,95 @@ pub struct TeamAsk {
 
     /// Any notes
     pub notes: String,
+
+    /// True if the plan item for this ask is marked as complete
+    pub is_complete: bool,
+}
+
+/// A single team's part in a [`TeamAsk`], flattened so that tools can reason about
+/// the asks of a goal without re-parsing the markdown. See [`GoalDocument::team_ask_entries`].
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct TeamAskEntry {
+    /// The team being asked
+    pub team: &'static TeamName,
+
+    /// What the team is being asked for (e.g., RFC decision); this is the text of the plan item
+    pub ask_description: String,
+
+    /// Title of the subgoal containing the ask, if any
+    pub subgoal: Option<String>,
+
+    /// Any notes
+    pub notes: String,
+
+    /// True if the plan item for this ask is marked as complete
+    pub is_complete: bool,
+}
+
+/// The timeframe (e.g., `2024h2`) declared by the goals in `directory_path` with a
+/// `Timeframe` row, if any. It is an error for goals in the same directory to disagree.
+pub fn declared_timeframe(directory_path: &Path) -> anyhow::Result<Option<String>> {
+    let mut documents = vec![];
+    for (path, _) in markdown_files(directory_path)? {
+        let sections =
+            markwaydown::parse(&path).with_context(|| format!("parsing `{}`", path.display()))?;
+        documents.push((path, sections));
+    }
+    unique_timeframe(&documents)
+}
+
+fn unique_timeframe(documents: &[(PathBuf, Vec<Section>)]) -> anyhow::Result<Option<String>> {
+    let mut declared: BTreeMap<&str, Vec<&Path>> = BTreeMap::new();
+    for (path, sections) in documents {
+        let row = sections
+            .first()
+            .and_then(|section| section.tables.first())
+            .filter(|table| table.header == ["Metadata", ""])
+            .and_then(|table| table.rows.iter().find(|row| row[0] == TIMEFRAME_ROW));
+        let Some(row) = row else {
+            continue;
+        };
+
+        let timeframe = row[1].trim();
+        if !re::TIMEFRAME.is_match(timeframe) {
+            bail!(
+                "`{}`: timeframe must look like `2024h2` (found `{timeframe}`)",
+                path.display()
+            );
+        }
+        declared.entry(timeframe).or_default().push(path);
+    }
+
+    if declared.len() > 1 {
+        bail!(
+            "goals declare conflicting timeframes: {}",
+            commas(declared.iter().map(|(timeframe, paths)| {
+                format!(
+                    "`{timeframe}` ({})",
+                    commas(paths.iter().map(|path| path.display()))
+                )
+            }))
+        );
+    }
+
+    Ok(declared.into_keys().next().map(|t| t.to_string()))
 }
 
-/// Load all the goals from a given directory
-pub fn goals_in_dir(directory_path: &Path) -> anyhow::Result<Vec<GoalDocument>> {
+/// True if `sections` start with a goal's metadata table. Other markdown files
+/// (e.g., an index or README) are not goals.
+fn is_goal(sections: &[Section]) -> bool {
+    sections
+        .first()
+        .and_then(|section| section.tables.first())
+        .is_some_and(|table| table.header == ["Metadata", ""])
+}
+
+/// Load all the goals from a given directory, skipping the markdown files that are
+/// not goals. If `strict` is set (by `--strict`), such files are errors instead.
+pub fn goals_in_dir(directory_path: &Path, strict: bool) -> anyhow::Result<Vec<GoalDocument>> {
     let mut goal_documents = vec![];
     for (path, link_path) in markdown_files(&directory_path)? {
-        if let Some(goal_document) = GoalDocument::load(&path, &link_path)
+        if let Some(goal_document) = GoalDocument::load(&path, &link_path, strict)
             .with_context(|| format!("loading goal from `{}`", path.display()))?
         {
             goal_documents.push(goal_document);
@@ -116,20 +330,54 @@ pub fn goals_in_dir(directory_path: &Path) -> anyhow::Result<Vec<GoalDocument>>
     Ok(goal_documents)
 }
 
+/// Split `goal_documents` into flagship goals and all other goals, preserving their order.
+/// Output that lists goals should show the flagship goals first.
+pub fn flagship_first(goal_documents: &[GoalDocument]) -> (Vec<&GoalDocument>, Vec<&GoalDocument>) {
+    goal_documents
+        .iter()
+        .partition(|doc| doc.metadata.status.is_flagship)
+}
+
 impl GoalDocument {
-    fn load(path: &Path, link_path: &Path) -> anyhow::Result<Option<Self>> {
+    /// Returns `None` if the file is not a goal, unless `strict` is set.
+    fn load(path: &Path, link_path: &Path, strict: bool) -> anyhow::Result<Option<Self>> {
         let sections = markwaydown::parse(path)?;
+        if !is_goal(&sections) {
+            if strict {
+                bail!("not a goal document (its first section has no `Metadata` table)");
+            }
+            log::debug!("skipping `{}`, which is not a goal", path.display());
+            return Ok(None);
+        }
+        Self::from_sections(path, link_path, &sections)
+    }
 
-        let Some(metadata) = extract_metadata(&sections)? else {
+    /// Parse a goal document from its markdown `text` rather than reading it from `path`.
+    /// Returns `None` if the text has no metadata table (i.e., it is not a goal).
+    pub fn from_text(path: &Path, link_path: &Path, text: &str) -> anyhow::Result<Option<Self>> {
+        let span = Span {
+            file: path.into(),
+            bytes: 0..text.len(),
+        };
+        let sections = markwaydown::parse_text(Spanned::new(text, span))?;
+        Self::from_sections(path, link_path, &sections)
+    }
+
+    fn from_sections(
+        path: &Path,
+        link_path: &Path,
+        sections: &[Section],
+    ) -> anyhow::Result<Option<Self>> {
+        let Some(metadata) = extract_metadata(sections)? else {
             return Ok(None);
         };
 
-        let summary = extract_summary(&sections)?;
+        let summary = extract_summary(sections)?;
 
         let link_path = Arc::new(link_path.to_path_buf());
 
         let goal_plans = if metadata.status.is_not_not_accepted() {
-            extract_plan_items(&sections)?
+            extract_plan_items(sections)?
         } else {
             vec![]
         };
@@ -145,10 +393,7 @@ impl GoalDocument {
             }
         }
 
-        // Enforce that every goal has some team asks (unless it is not accepted)
-        if metadata.status.is_not_not_accepted() && team_asks.is_empty() {
-            anyhow::bail!("no team asks in goal; did you include `![Team]` in the table?");
-        }
+        // Note that a goal may have no team asks at all, if the owners can do all the work themselves.
 
         let task_owners = goal_plans
             .iter()
@@ -167,6 +412,22 @@ impl GoalDocument {
         }))
     }
 
+    /// Returns one entry per team for each team ask in this goal, in the order they appear in the document.
+    pub fn team_ask_entries(&self) -> Vec<TeamAskEntry> {
+        self.team_asks
+            .iter()
+            .flat_map(|ask| {
+                ask.teams.iter().map(|&team| TeamAskEntry {
+                    team,
+                    ask_description: ask.ask_description.clone(),
+                    subgoal: ask.goal_titles.get(1).map(|title| title.content.clone()),
+                    notes: ask.notes.clone(),
+                    is_complete: ask.is_complete,
+                })
+            })
+            .collect()
+    }
+
     pub fn teams_with_asks(&self) -> BTreeSet<&'static TeamName> {
         self.team_asks
             .iter()
@@ -180,18 +441,196 @@ impl GoalDocument {
         self.metadata.status.is_not_not_accepted()
     }
 
+    /// The file name of the goal without `.md`, which other goals use to name it
+    /// as their umbrella.
+    pub fn slug(&self) -> &str {
+        self.link_path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .unwrap_or_default()
+    }
+
+    /// True if the goal has no tasks at all, and did not say that this is on purpose.
+    /// Its "Tasks and status" section is then empty, which is almost always a mistake.
+    pub fn lacks_tasks(&self) -> bool {
+        !self.metadata.tasks_intentionally_empty
+            && self
+                .goal_plans
+                .iter()
+                .all(|goal_plan| goal_plan.plan_items.is_empty())
+    }
+
+    /// True if a tracking issue should be created for this goal.
+    pub fn wants_tracking_issue(&self) -> bool {
+        self.is_not_not_accepted() && !self.metadata.no_tracking_issue
+    }
+
     /// Modify the goal document on disk to link to the given issue number in the metadata.
     pub fn link_issue(&self, number: IssueId) -> anyhow::Result<()> {
-        let mut metadata_table = self.metadata.table.clone();
+        let metadata_table = self.linked_metadata_table(&number);
+        self.metadata
+            .table
+            .overwrite_in_path(&self.path, &metadata_table)?;
+        Ok(())
+    }
+
+    /// Describe the edit that [`Self::link_issue`] would make without writing anything.
+    /// Returns the removed lines (prefixed with `-`) followed by the added lines (prefixed with `+`).
+    pub fn link_issue_preview(&self, number: &IssueId) -> anyhow::Result<Vec<String>> {
+        self.metadata_table_preview(&self.linked_metadata_table(number))
+    }
+
+    fn linked_metadata_table(&self, number: &IssueId) -> Table {
+        let mut metadata_table = self.metadata.table.content.clone();
+        metadata_table.add_key_value_row(TRACKING_ISSUE_ROW, number);
         metadata_table
-            .content
-            .add_key_value_row(TRACKING_ISSUE_ROW, &number);
+    }
+
+    /// Modify the goal document on disk, replacing the owner `from` with `to`
+    /// in the `Point of contact` or `Additional owners` row.
+    pub fn replace_owner(&self, from: &str, to: &str) -> anyhow::Result<()> {
+        let metadata_table = self.owner_replaced_metadata_table(from, to)?;
         self.metadata
             .table
             .overwrite_in_path(&self.path, &metadata_table)?;
         Ok(())
     }
 
+    /// Describe the edit that [`Self::replace_owner`] would make without writing anything.
+    pub fn replace_owner_preview(&self, from: &str, to: &str) -> anyhow::Result<Vec<String>> {
+        self.metadata_table_preview(&self.owner_replaced_metadata_table(from, to)?)
+    }
+
+    fn owner_replaced_metadata_table(&self, from: &str, to: &str) -> anyhow::Result<Table> {
+        let mut metadata_table = self.metadata.table.content.clone();
+        let mut found = false;
+
+        for row in &mut metadata_table.rows {
+            if row[0] != POINT_OF_CONTACT_ROW && row[0] != ADDITIONAL_OWNERS_ROW {
+                continue;
+            }
+
+            let replaced = re::USERNAME.replace_all(&row[1], |c: &regex::Captures<'_>| {
+                if c[0].eq_ignore_ascii_case(from) {
+                    found = true;
+                    to.to_string()
+                } else {
+                    c[0].to_string()
+                }
+            });
+            row[1] = Spanned::here(replaced.to_string());
+        }
+
+        if !found {
+            anyhow::bail!(
+                "`{from}` is not an owner of `{}` (owners are {})",
+                self.path.display(),
+                self.metadata.owner_usernames().join(", ")
+            );
+        }
+
+        Ok(metadata_table)
+    }
+
+    /// Modify the goal document on disk to follow the assignees of its tracking issue:
+    /// the usernames (without `@`) in `remove` are dropped from the `Additional owners` row,
+    /// and those in `add` are appended to it. The point of contact is left alone.
+    pub fn sync_owners(
+        &self,
+        remove: &BTreeSet<String>,
+        add: &BTreeSet<String>,
+    ) -> anyhow::Result<()> {
+        let metadata_table = self.owners_synced_metadata_table(remove, add);
+        self.metadata
+            .table
+            .overwrite_in_path(&self.path, &metadata_table)?;
+        Ok(())
+    }
+
+    /// Describe the edit that [`Self::sync_owners`] would make without writing anything.
+    pub fn sync_owners_preview(
+        &self,
+        remove: &BTreeSet<String>,
+        add: &BTreeSet<String>,
+    ) -> anyhow::Result<Vec<String>> {
+        self.metadata_table_preview(&self.owners_synced_metadata_table(remove, add))
+    }
+
+    fn owners_synced_metadata_table(
+        &self,
+        remove: &BTreeSet<String>,
+        add: &BTreeSet<String>,
+    ) -> Table {
+        let is_removed = |owner: &str| {
+            remove
+                .iter()
+                .any(|username| owner.trim_start_matches('@').eq_ignore_ascii_case(username))
+        };
+        let mut owners: Vec<String> = self
+            .metadata
+            .additional_owners
+            .split(',')
+            .map(str::trim)
+            .filter(|owner| !owner.is_empty() && !is_removed(owner))
+            .map(str::to_string)
+            .collect();
+        owners.extend(add.iter().map(|username| format!("@{username}")));
+
+        let mut metadata_table = self.metadata.table.content.clone();
+        metadata_table.add_key_value_row(ADDITIONAL_OWNERS_ROW, &owners.join(", "));
+        metadata_table
+    }
+
+    /// Modify the goal document on disk, replacing the title in its `# ...` heading with `title`.
+    pub fn retitle(&self, title: &str) -> anyhow::Result<()> {
+        let full_text = std::fs::read_to_string(&self.path)
+            .with_context(|| format!("reading `{}`", self.path.display()))?;
+        let new_text = self.retitled_text(&full_text, title)?;
+        std::fs::write(&self.path, new_text)
+            .with_context(|| format!("writing `{}`", self.path.display()))
+    }
+
+    /// Describe the edit that [`Self::retitle`] would make without writing anything.
+    pub fn retitle_preview(&self, title: &str) -> anyhow::Result<Vec<String>> {
+        let full_text = std::fs::read_to_string(&self.path)
+            .with_context(|| format!("reading `{}`", self.path.display()))?;
+        let new_text = self.retitled_text(&full_text, title)?;
+        Ok(changed_lines(&full_text, &new_text))
+    }
+
+    fn retitled_text(&self, full_text: &str, title: &str) -> anyhow::Result<String> {
+        let mut new_text = String::new();
+        let mut found = false;
+        for line in full_text.split_inclusive('\n') {
+            let heading = line.trim_end().strip_prefix("# ").map(str::trim);
+            if !found && heading == Some(self.metadata.title.as_str()) {
+                found = true;
+                new_text.push_str(&format!("# {title}"));
+                new_text.push_str(&line[line.trim_end().len()..]);
+            } else {
+                new_text.push_str(line);
+            }
+        }
+        if !found {
+            bail!(
+                "no `# {}` heading found in `{}`",
+                self.metadata.title,
+                self.path.display()
+            );
+        }
+        Ok(new_text)
+    }
+
+    fn metadata_table_preview(&self, metadata_table: &Table) -> anyhow::Result<Vec<String>> {
+        let full_text = std::fs::read_to_string(&self.path)
+            .with_context(|| format!("reading `{}`", self.path.display()))?;
+        let new_text = self
+            .metadata
+            .table
+            .overwrite_in_text(&full_text, metadata_table);
+        Ok(changed_lines(&full_text, &new_text))
+    }
+
     /// In goal lists, we render our point-of-contact as "Help Wanted" if this is an invited goal.
     pub fn point_of_contact_for_goal_list(&self) -> String {
         if self.metadata.status.is_invited {
@@ -202,6 +641,77 @@ impl GoalDocument {
     }
 }
 
+/// Compare `old_text` and `new_text` line by line and return the lines that differ
+/// (after stripping the common prefix and suffix) in a diff-like format.
+fn changed_lines(old_text: &str, new_text: &str) -> Vec<String> {
+    let old_lines: Vec<&str> = old_text.lines().collect();
+    let new_lines: Vec<&str> = new_text.lines().collect();
+
+    let prefix = old_lines
+        .iter()
+        .zip(&new_lines)
+        .take_while(|(o, n)| o == n)
+        .count();
+    let suffix = old_lines[prefix..]
+        .iter()
+        .rev()
+        .zip(new_lines[prefix..].iter().rev())
+        .take_while(|(o, n)| o == n)
+        .count();
+
+    let removed = old_lines[prefix..old_lines.len() - suffix]
+        .iter()
+        .map(|line| format!("-{line}"));
+    let added = new_lines[prefix..new_lines.len() - suffix]
+        .iter()
+        .map(|line| format!("+{line}"));
+    removed.chain(added).collect()
+}
+
+/// Group `goals` by their category, in alphabetical order with [`OTHER_CATEGORY`] last,
+/// keeping the order of the goals within each group.
+///
+/// Returns `None` if none of the goals has a category, in which case they are listed
+/// without any grouping.
+pub fn by_category<'g>(
+    goals: &[&'g GoalDocument],
+) -> Option<Vec<(&'g str, Vec<&'g GoalDocument>)>> {
+    if goals.iter().all(|goal| goal.metadata.category.is_none()) {
+        return None;
+    }
+
+    let mut categories: BTreeMap<&str, Vec<&GoalDocument>> = BTreeMap::new();
+    let mut others = vec![];
+    for &goal in goals {
+        match &goal.metadata.category {
+            Some(category) => categories.entry(category).or_default().push(goal),
+            None => others.push(goal),
+        }
+    }
+
+    let mut groups: Vec<_> = categories.into_iter().collect();
+    if !others.is_empty() {
+        groups.push((OTHER_CATEGORY, others));
+    }
+    Some(groups)
+}
+
+/// Like [`format_goal_table`], but with a table per category (see [`by_category`]).
+pub fn format_goal_tables_by_category(goals: &[&GoalDocument]) -> anyhow::Result<String> {
+    let Some(groups) = by_category(goals) else {
+        return format_goal_table(goals);
+    };
+
+    let mut text = String::new();
+    for (category, goals) in groups {
+        text.push_str(&format!(
+            "#### {category}\n\n{}\n",
+            format_goal_table(&goals)?
+        ));
+    }
+    Ok(text)
+}
+
 pub fn format_goal_table(goals: &[&GoalDocument]) -> anyhow::Result<String> {
     // If any of the goals have tracking issues, include those in the table.
     let goals_are_proposed = goals
@@ -402,7 +912,7 @@ fn extract_metadata(sections: &[Section]) -> anyhow::Result<Option<Metadata>> {
     let Some(poc_row) = first_table
         .rows
         .iter()
-        .find(|row| row[0] == "Point of contact")
+        .find(|row| row[0] == POINT_OF_CONTACT_ROW)
     else {
         anyhow::bail!("metadata table has no `Point of contact` row")
     };
@@ -420,14 +930,125 @@ fn extract_metadata(sections: &[Section]) -> anyhow::Result<Option<Metadata>> {
 
     let status = Status::try_from(status_row[1].as_str())?;
 
+    let additional_owners = match first_table
+        .rows
+        .iter()
+        .find(|row| row[0] == ADDITIONAL_OWNERS_ROW)
+    {
+        Some(row) => {
+            if !only_owners(&row[1]) {
+                anyhow::bail!(
+                    "additional owners must be a comma-separated list of github usernames or `[team]` names (found {})",
+                    row[1].render()
+                )
+            }
+            row[1].to_string()
+        }
+        None => String::new(),
+    };
+
+    let lockable = match first_table.rows.iter().find(|row| row[0] == LOCKABLE_ROW) {
+        Some(row) => match parse_flag(&row[1]) {
+            Some(lockable) => lockable,
+            None => anyhow::bail!(
+                "lockable must be `true` or `false` (found {})",
+                row[1].render()
+            ),
+        },
+        None => true,
+    };
+
+    let no_tracking_issue = match first_table
+        .rows
+        .iter()
+        .find(|row| row[0] == NO_TRACKING_ISSUE_ROW)
+    {
+        Some(row) => match parse_flag(&row[1]) {
+            Some(no_tracking_issue) => no_tracking_issue,
+            None => anyhow::bail!(
+                "no tracking issue must be `true` or `false` (found {})",
+                row[1].render()
+            ),
+        },
+        None => false,
+    };
+
+    let collapse_subgoals = match first_table
+        .rows
+        .iter()
+        .find(|row| row[0] == COLLAPSE_SUBGOALS_ROW)
+    {
+        Some(row) => match parse_flag(&row[1]) {
+            Some(collapse_subgoals) => collapse_subgoals,
+            None => anyhow::bail!(
+                "collapse subgoals must be `true` or `false` (found {})",
+                row[1].render()
+            ),
+        },
+        None => false,
+    };
+
+    let tasks_intentionally_empty = match first_table
+        .rows
+        .iter()
+        .find(|row| row[0] == TASKS_INTENTIONALLY_EMPTY_ROW)
+    {
+        Some(row) => match parse_flag(&row[1]) {
+            Some(tasks_intentionally_empty) => tasks_intentionally_empty,
+            None => anyhow::bail!(
+                "tasks intentionally empty must be `true` or `false` (found {})",
+                row[1].render()
+            ),
+        },
+        None => false,
+    };
+
+    let umbrella = match first_table.rows.iter().find(|row| row[0] == UMBRELLA_ROW) {
+        Some(row) if !row[1].is_empty() => {
+            if !is_slug(&row[1]) {
+                anyhow::bail!(
+                    "umbrella must be the file name of another goal, without `.md` (found {})",
+                    row[1].render()
+                );
+            }
+            Some(row[1].trim().to_string())
+        }
+        _ => None,
+    };
+
+    let category = first_table
+        .rows
+        .iter()
+        .find(|row| row[0] == CATEGORY_ROW)
+        .map(|row| row[1].trim().to_string())
+        .filter(|category| !category.is_empty());
+
+    let extra_labels = first_table
+        .rows
+        .iter()
+        .find(|row| row[0] == LABELS_ROW)
+        .map(|row| {
+            row[1]
+                .split(',')
+                .map(|label| label.trim().trim_matches('`').to_string())
+                .filter(|label| !label.is_empty())
+                .collect()
+        })
+        .unwrap_or_default();
+
     let issue = if let Some(r) = first_table
         .rows
         .iter()
         .find(|row| row[0] == TRACKING_ISSUE_ROW)
     {
-        // Accepted goals must have a tracking issue.
+        // Accepted goals must have a tracking issue, unless they opted out of having one.
         let has_tracking_issue = !r[1].is_empty();
-        if status.acceptance == AcceptanceStatus::Accepted {
+        if no_tracking_issue {
+            anyhow::ensure!(
+                !has_tracking_issue,
+                "goals marked `{NO_TRACKING_ISSUE_ROW}` cannot declare a tracking issue"
+            );
+        } else if status.acceptance == AcceptanceStatus::Accepted {
             anyhow::ensure!(
                 has_tracking_issue,
                 "accepted goals cannot have an empty tracking issue"
@@ -455,8 +1076,16 @@ fn extract_metadata(sections: &[Section]) -> anyhow::Result<Option<Metadata>> {
             title.clone()
         },
         pocs: poc_row[1].to_string(),
+        additional_owners,
         status,
         tracking_issue: issue,
+        lockable,
+        no_tracking_issue,
+        collapse_subgoals,
+        tasks_intentionally_empty,
+        umbrella,
+        category,
+        extra_labels,
         table: first_table.clone(),
     }))
 }
@@ -477,6 +1106,235 @@ fn verify_row(rows: &[Vec<Spanned<String>>], key: &str, value: &str) -> anyhow::
     Ok(())
 }
 
+/// A problem found while validating the metadata table of a goal document.
+#[derive(Debug)]
+pub struct MetadataProblem {
+    /// Path to the goal document
+    pub path: PathBuf,
+
+    /// Name of the metadata row with the problem (e.g., `Status`)
+    pub field: String,
+
+    /// Description of what is wrong
+    pub message: String,
+}
+
+impl std::fmt::Display for MetadataProblem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}: `{}`: {}",
+            self.path.display(),
+            self.field,
+            self.message
+        )
+    }
+}
+
+/// Validate the metadata table of every goal document in `directory_path`.
+/// Unlike [`goals_in_dir`], this does not stop at the first error but reports
+/// every problem it finds, naming the file and the metadata row.
+///
+/// If `repository` is given, declared tracking issues must belong to it.
+/// If `strict` is set, markdown files that are not goals are reported too.
+pub fn validate_metadata_in_dir(
+    directory_path: &Path,
+    repository: Option<&Repository>,
+    strict: bool,
+) -> anyhow::Result<Vec<MetadataProblem>> {
+    let mut problems = vec![];
+    for (path, _) in markdown_files(directory_path)? {
+        let sections =
+            markwaydown::parse(&path).with_context(|| format!("parsing `{}`", path.display()))?;
+        problems.extend(validate_metadata(&path, &sections, repository, strict));
+    }
+    Ok(problems)
+}
+
+/// Like [`validate_metadata_in_dir`], for the single document `text` (read from `path`).
+/// A document that is not a goal has no problems; callers decide whether that is an error.
+pub fn validate_metadata_in_text(
+    path: &Path,
+    text: &str,
+    repository: Option<&Repository>,
+) -> anyhow::Result<Vec<MetadataProblem>> {
+    let span = Span {
+        file: path.into(),
+        bytes: 0..text.len(),
+    };
+    let sections = markwaydown::parse_text(Spanned::new(text, span))
+        .with_context(|| format!("parsing `{}`", path.display()))?;
+    Ok(validate_metadata(path, &sections, repository, false))
+}
+
+fn validate_metadata(
+    path: &Path,
+    sections: &[Section],
+    repository: Option<&Repository>,
+    strict: bool,
+) -> Vec<MetadataProblem> {
+    let mut problems = vec![];
+    let mut problem = |field: &str, message: String| {
+        problems.push(MetadataProblem {
+            path: path.to_path_buf(),
+            field: field.to_string(),
+            message,
+        })
+    };
+
+    // Documents without a metadata table in their first section are not goals (see `is_goal`).
+    if !is_goal(sections) {
+        if strict {
+            problem(
+                "Metadata",
+                "not a goal document (its first section has no `Metadata` table)".to_string(),
+            );
+        }
+        return problems;
+    }
+    let first_section = &sections[0];
+    let table = &first_section.tables[0];
+
+    if first_section.title.is_empty() {
+        problem("Title", "first section has no title".to_string());
+    }
+
+    let row = |key: &str| table.rows.iter().find(|row| row[0] == key);
+
+    match row(POINT_OF_CONTACT_ROW) {
+        None => problem(POINT_OF_CONTACT_ROW, "missing row".to_string()),
+        Some(r) if !re::is_just(&re::USERNAME, r[1].trim()) => problem(
+            POINT_OF_CONTACT_ROW,
+            format!("must be a single github username, found `{}`", *r[1]),
+        ),
+        Some(_) => {}
+    }
+
+    if let Some(r) = row(ADDITIONAL_OWNERS_ROW) {
+        if !only_owners(&r[1]) {
+            problem(
+                ADDITIONAL_OWNERS_ROW,
+                format!(
+                    "must be a comma-separated list of github usernames or `[team]` names, found `{}`",
+                    *r[1]
+                ),
+            );
+        }
+    }
+
+    for flag_row in [
+        LOCKABLE_ROW,
+        COLLAPSE_SUBGOALS_ROW,
+        TASKS_INTENTIONALLY_EMPTY_ROW,
+    ] {
+        if let Some(r) = row(flag_row) {
+            if parse_flag(&r[1]).is_none() {
+                problem(
+                    flag_row,
+                    format!("must be `true` or `false`, found `{}`", *r[1]),
+                );
+            }
+        }
+    }
+
+    if let Some(r) = row(UMBRELLA_ROW) {
+        if !r[1].is_empty() && !is_slug(&r[1]) {
+            problem(
+                UMBRELLA_ROW,
+                format!(
+                    "must be the file name of another goal, without `.md`, found `{}`",
+                    *r[1]
+                ),
+            );
+        }
+    }
+
+    if let Some(r) = row(TIMEFRAME_ROW) {
+        if !re::TIMEFRAME.is_match(r[1].trim()) {
+            problem(
+                TIMEFRAME_ROW,
+                format!("must look like `2024h2`, found `{}`", *r[1]),
+            );
+        }
+    }
+
+    let no_tracking_issue = match row(NO_TRACKING_ISSUE_ROW) {
+        None => false,
+        Some(r) => parse_flag(&r[1]).unwrap_or_else(|| {
+            problem(
+                NO_TRACKING_ISSUE_ROW,
+                format!("must be `true` or `false`, found `{}`", *r[1]),
+            );
+            false
+        }),
+    };
+
+    let status = match row("Status") {
+        None => {
+            problem("Status", "missing row".to_string());
+            None
+        }
+        Some(r) => match Status::try_from(r[1].as_str()) {
+            Ok(status) => Some(status),
+            Err(e) => {
+                problem("Status", e.to_string());
+                None
+            }
+        },
+    };
+
+    match row(TRACKING_ISSUE_ROW).filter(|r| !r[1].is_empty()) {
+        None if no_tracking_issue => {}
+        Some(r) if no_tracking_issue => problem(
+            TRACKING_ISSUE_ROW,
+            format!(
+                "goal is marked `{NO_TRACKING_ISSUE_ROW}` but declares tracking issue `{}`",
+                *r[1]
+            ),
+        ),
+        None => {
+            if let Some(Status {
+                acceptance: AcceptanceStatus::Accepted,
+                ..
+            }) = status
+            {
+                problem(
+                    TRACKING_ISSUE_ROW,
+                    "accepted goals must have a tracking issue".to_string(),
+                );
+            }
+        }
+        Some(r) => match r[1].content.parse::<IssueId>() {
+            Ok(issue_id) => {
+                if let Some(repository) = repository {
+                    if issue_id.repository != *repository {
+                        problem(
+                            TRACKING_ISSUE_ROW,
+                            format!(
+                                "tracking issue {issue_id} is not in the repository `{repository}`"
+                            ),
+                        );
+                    }
+                }
+            }
+            Err(e) => problem(TRACKING_ISSUE_ROW, format!("`{}`: {e}", *r[1])),
+        },
+    }
+
+    for (key, value) in [
+        ("Teams", TEAMS_WITH_ASKS_STR),
+        ("Task owners", TASK_OWNERS_STR),
+    ] {
+        match row(key) {
+            None => problem(key, "missing row".to_string()),
+            Some(r) if r[1] != value => problem(key, format!("expected `{value}`")),
+            Some(_) => {}
+        }
+    }
+
+    problems
+}
+
 fn extract_summary(sections: &[Section]) -> anyhow::Result<Option<String>> {
     let Some(ownership_section) = sections.iter().find(|section| section.title == "Summary") else {
         return Ok(None);
@@ -551,13 +1409,59 @@ fn extract_plan_item(
         anyhow::bail!("unexpected end of table");
     };
 
+    let (text, stretch) = split_stretch(&row[0]);
+    let (owners, blocked) = split_blocked(&row[1]);
+    let (owners, effort) = split_effort(&owners)?;
     Ok(PlanItem {
-        text: row[0].to_string(),
-        owners: row[1].to_string(),
+        text,
+        owners,
         notes: row[2].to_string(),
+        effort,
+        blocked,
+        stretch,
     })
 }
 
+/// Separate the blocked annotation (if any) from the owners of a plan item, so that the
+/// reason is not mistaken for owners or teams.
+fn split_blocked(owners: &str) -> (String, Option<String>) {
+    match re::BLOCKED.captures(owners) {
+        Some(captures) => (
+            re::BLOCKED.replace(owners, "").trim().to_string(),
+            Some(captures[1].to_string()),
+        ),
+        None => (owners.to_string(), None),
+    }
+}
+
+/// Separate the `(stretch)` annotation (if any) from the text of a plan item.
+fn split_stretch(text: &str) -> (String, bool) {
+    if re::STRETCH.is_match(text) {
+        (re::STRETCH.replace(text, "").trim().to_string(), true)
+    } else {
+        (text.to_string(), false)
+    }
+}
+
+/// Separate the effort annotation (if any) from the owners of a plan item,
+/// so that it is not mistaken for an owner or team.
+fn split_effort(owners: &str) -> anyhow::Result<(String, Option<Effort>)> {
+    let Some(captures) = re::EFFORT.captures(owners) else {
+        if owners.contains("(est:") {
+            bail!("invalid effort annotation in `{owners}` (expected e.g. `(est: 2w)` or `(est: 2w, actual: 3w)`)");
+        }
+        return Ok((owners.to_string(), None));
+    };
+
+    let estimated = WorkDays::parse(&captures[1], &captures[2])?;
+    let actual = match (captures.get(3), captures.get(4)) {
+        (Some(amount), Some(unit)) => Some(WorkDays::parse(amount.as_str(), unit.as_str())?),
+        _ => None,
+    };
+    let owners = re::EFFORT.replace(owners, "").trim().to_string();
+    Ok((owners, Some(Effort { estimated, actual })))
+}
+
 impl PlanItem {
     /// Parses the owners of this plan item.
     pub fn parse_owners(&self) -> anyhow::Result<Option<ParsedOwners>> {
@@ -580,6 +1484,12 @@ impl PlanItem {
         self.notes.contains("![Complete]")
     }
 
+    /// True if the plan item is annotated as blocked and not completed yet.
+    /// Blocked items still count as incomplete.
+    pub fn is_blocked(&self) -> bool {
+        self.blocked.is_some() && !self.is_complete()
+    }
+
     /// If true, this item is something being asked of a team.
     /// If false, it's something the goal owner(s) are proposing to do.
     pub fn is_team_ask(&self) -> bool {
@@ -667,6 +1577,7 @@ impl PlanItem {
                 teams,
                 owners: goal_owners.to_string(),
                 notes: self.notes.clone(),
+                is_complete: self.is_complete(),
             });
         }
 
@@ -702,15 +1613,366 @@ fn extract_identifiers(s: &str) -> Vec<&str> {
 }
 
 impl Metadata {
-    /// Extracts the `@abc` usernames found in the owner listing.
+    /// Extracts the `@abc` usernames of all owners: the point of contact first,
+    /// followed by any additional owners.
     pub fn owner_usernames(&self) -> Vec<&str> {
+        let mut usernames = self.primary_owner_usernames();
+        usernames.extend(self.additional_owner_usernames());
+        usernames
+    }
+
+    /// Extracts the `@abc` username(s) of the point of contact.
+    pub fn primary_owner_usernames(&self) -> Vec<&str> {
         owner_usernames(&self.pocs)
     }
+
+    /// Extracts the `@abc` usernames from the `Additional owners` row (if any).
+    pub fn additional_owner_usernames(&self) -> Vec<&str> {
+        owner_usernames(&self.additional_owners)
+    }
+
+    /// Teams listed as owners (like `[lang]`) in the `Additional owners` row.
+    ///
+    /// Teams cannot be assigned to a GitHub issue, so team ownership is reflected
+    /// by the team's `T-<team>` label and a note next to the point of contact instead.
+    pub fn owner_teams(&self) -> anyhow::Result<Vec<&'static TeamName>> {
+        let mut teams = vec![];
+        for team_name in owner_team_names(&self.additional_owners) {
+            let Some(team) = team::get_team_name(team_name)? else {
+                anyhow::bail!(
+                    "no Rust team named `{}` found (valid names are {})",
+                    team_name,
+                    commas(team::get_team_names()?),
+                );
+            };
+            teams.push(team);
+        }
+        Ok(teams)
+    }
+}
+
+/// True if `text` is a (possibly empty) comma-separated list of github usernames
+/// and `[team]` names.
+fn only_owners(text: &str) -> bool {
+    text.split(',')
+        .map(|s| s.trim())
+        .filter(|s| !s.is_empty())
+        .all(|s| re::is_just(&re::USERNAME, s) || re::is_just(&re::TEAM_OWNER, s))
+}
+
+fn owner_team_names(text: &str) -> Vec<&str> {
+    re::TEAM_OWNER
+        .captures_iter(text)
+        .map(|captures| captures.get(1).unwrap().as_str())
+        .collect()
 }
 
 fn owner_usernames(text: &str) -> Vec<&str> {
-    text.split(char::is_whitespace)
+    text.split(|c: char| c.is_whitespace() || c == ',')
         .filter_map(|owner| re::USERNAME.captures(owner))
         .map(|captures| captures.get(0).unwrap().as_str())
         .collect()
 }
+
+/// Goal documents for tests, so that each test only spells out the rows and tasks it is about.
+#[cfg(any(test, feature = "mock"))]
+pub mod test_support {
+    use std::path::Path;
+
+    use super::GoalDocument;
+
+    /// A row of an ownership table: the task, its owners and its notes.
+    type Task = (String, String, String);
+
+    /// The markdown of a goal, starting out with the rows every goal needs and a
+    /// single task owned by the point of contact.
+    pub struct GoalText {
+        title: String,
+        rows: Vec<(String, String)>,
+        tasks: Vec<Task>,
+        subgoals: Vec<(String, Vec<Task>)>,
+        default_task: bool,
+    }
+
+    impl GoalText {
+        pub fn new(title: &str) -> Self {
+            GoalText {
+                title: title.to_string(),
+                rows: vec![
+                    ("Point of contact".to_string(), "@owner".to_string()),
+                    ("Teams".to_string(), "<!-- TEAMS WITH ASKS -->".to_string()),
+                    (
+                        "Task owners".to_string(),
+                        "<!-- TASK OWNERS -->".to_string(),
+                    ),
+                    ("Status".to_string(), "Proposed".to_string()),
+                ],
+                tasks: vec![],
+                subgoals: vec![],
+                default_task: true,
+            }
+        }
+
+        /// Set the metadata row `name`, replacing it if it is already there.
+        pub fn row(mut self, name: &str, value: &str) -> Self {
+            match self.rows.iter_mut().find(|(n, _)| n == name) {
+                Some(row) => row.1 = value.to_string(),
+                None => self.rows.push((name.to_string(), value.to_string())),
+            }
+            self
+        }
+
+        /// Add a row to the ownership table (or to that of the last subgoal), replacing
+        /// the default task.
+        pub fn task(mut self, task: &str, owners: &str, notes: &str) -> Self {
+            let task = (task.to_string(), owners.to_string(), notes.to_string());
+            match self.subgoals.last_mut() {
+                Some((_, tasks)) => tasks.push(task),
+                None => self.tasks.push(task),
+            }
+            self.default_task = false;
+            self
+        }
+
+        /// Start a `### title` subgoal with an ownership table of its own, which the
+        /// following calls to [`task`](Self::task) add to.
+        pub fn subgoal(mut self, title: &str) -> Self {
+            self.subgoals.push((title.to_string(), vec![]));
+            self.default_task = false;
+            self
+        }
+
+        /// Leave the ownership table empty rather than starting it with the default task.
+        pub fn without_tasks(mut self) -> Self {
+            self.default_task = false;
+            self
+        }
+
+        pub fn text(&self) -> String {
+            let mut text = format!("# {}\n\n| Metadata | |\n| --- | --- |\n", self.title);
+            for (name, value) in &self.rows {
+                text.push_str(&format!("| {name} | {value} |\n"));
+            }
+            text.push_str(
+                "\n## Summary\n\nSummary.\n\n\
+                 ## Ownership and team asks\n\n\
+                 | Task | Owner(s) or team(s) | Notes |\n\
+                 | --- | --- | --- |\n",
+            );
+            if self.default_task {
+                let (_, point_of_contact) = &self.rows[0];
+                text.push_str(&format!("| Implementation | {point_of_contact} | |\n"));
+            }
+            for (task, owners, notes) in &self.tasks {
+                text.push_str(&format!("| {task} | {owners} | {notes} |\n"));
+            }
+            for (title, tasks) in &self.subgoals {
+                text.push_str(&format!(
+                    "\n### {title}\n\n\
+                     | Task | Owner(s) or team(s) | Notes |\n\
+                     | --- | --- | --- |\n"
+                ));
+                for (task, owners, notes) in tasks {
+                    text.push_str(&format!("| {task} | {owners} | {notes} |\n"));
+                }
+            }
+            text
+        }
+
+        /// Parse the goal as if it were the file at `path`.
+        pub fn document(&self, path: &str) -> GoalDocument {
+            GoalDocument::from_text(Path::new(path), Path::new(path), &self.text())
+                .unwrap()
+                .unwrap()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::test_support::GoalText;
+    use super::*;
+
+    fn document(path: &str, rows: &str) -> (PathBuf, Vec<Section>) {
+        let text =
+            format!("# Goal\n\n| Metadata | |\n| --- | --- |\n| Point of contact | @me |\n{rows}");
+        let span = Span {
+            file: path.into(),
+            bytes: 0..text.len(),
+        };
+        let sections = markwaydown::parse_text(Spanned::new(&text[..], span)).unwrap();
+        (PathBuf::from(path), sections)
+    }
+
+    #[test]
+    fn owners_are_synced_in_the_additional_owners_row() {
+        let document = GoalText::new("Goal")
+            .row(POINT_OF_CONTACT_ROW, "@alice")
+            .row(ADDITIONAL_OWNERS_ROW, "@bob, [lang], @Carol")
+            .document("src/2025h1/goal.md");
+        let logins = |logins: &[&str]| -> BTreeSet<String> {
+            logins.iter().map(|l| l.to_string()).collect()
+        };
+
+        let table = document.owners_synced_metadata_table(&logins(&["carol"]), &logins(&["dave"]));
+        let row = table
+            .rows
+            .iter()
+            .find(|row| row[0] == ADDITIONAL_OWNERS_ROW)
+            .unwrap();
+        assert_eq!(*row[1], "@bob, [lang], @dave");
+    }
+
+    #[test]
+    fn retitling_replaces_the_heading_only() {
+        let goal = GoalText::new("Goal");
+        let text = &goal.text();
+        let document = goal.document("src/2025h1/goal.md");
+        let retitled = document.retitled_text(text, "Better goal").unwrap();
+        assert_eq!(retitled, text.replacen("# Goal\n", "# Better goal\n", 1));
+        assert!(document.retitled_text("# Other\n", "Better goal").is_err());
+    }
+
+    #[test]
+    fn effort_is_split_from_owners() {
+        assert_eq!(
+            split_effort("@alice, @bob (est: 2w)").unwrap(),
+            (
+                "@alice, @bob".to_string(),
+                Some(Effort {
+                    estimated: WorkDays(10),
+                    actual: None
+                })
+            )
+        );
+        let (owners, effort) = split_effort("![Team] [lang] (est: 3d, actual: 1m)").unwrap();
+        assert_eq!(owners, "![Team] [lang]");
+        assert_eq!(effort.unwrap().to_string(), "est: 3d, actual: 1m");
+        assert_eq!(
+            split_effort("@alice").unwrap(),
+            ("@alice".to_string(), None)
+        );
+        assert!(split_effort("@alice (est: 2 years)").is_err());
+        assert!(split_effort("@alice (est: 4294967295m)").is_err());
+    }
+
+    #[test]
+    fn stretch_is_split_from_the_task() {
+        assert_eq!(
+            split_stretch("Write a blog post (stretch)"),
+            ("Write a blog post".to_string(), true)
+        );
+        assert_eq!(
+            split_stretch("Blog post ( Stretch )"),
+            ("Blog post".to_string(), true)
+        );
+        assert_eq!(split_stretch("Implement"), ("Implement".to_string(), false));
+    }
+
+    #[test]
+    fn timeframe_can_be_declared_by_goals() {
+        let documents = [
+            document("src/next/a.md", "| Timeframe | 2025h1 |\n"),
+            document("src/next/b.md", ""),
+        ];
+        assert_eq!(
+            unique_timeframe(&documents).unwrap().as_deref(),
+            Some("2025h1")
+        );
+        assert_eq!(unique_timeframe(&documents[1..]).unwrap(), None);
+
+        let documents = [
+            document("src/next/a.md", "| Timeframe | 2025h1 |\n"),
+            document("src/next/b.md", "| Timeframe | 2025h2 |\n"),
+        ];
+        let error = unique_timeframe(&documents).unwrap_err().to_string();
+        assert_eq!(
+            error,
+            "goals declare conflicting timeframes: `2025h1` (src/next/a.md), `2025h2` (src/next/b.md)"
+        );
+    }
+
+    #[test]
+    fn only_documents_with_a_metadata_table_are_goals() {
+        assert!(is_goal(&document("src/2025h1/a.md", "").1));
+
+        let text = "# Goals\n\n| Goal | Owner |\n| --- | --- |\n| A | @me |\n";
+        let span = Span {
+            file: "src/2025h1/README.md".into(),
+            bytes: 0..text.len(),
+        };
+        let sections = markwaydown::parse_text(Spanned::new(text, span)).unwrap();
+        assert!(!is_goal(&sections));
+        assert!(!is_goal(&[]));
+    }
+
+    #[test]
+    fn goals_are_grouped_by_category() {
+        let goal = |title: &str, category: &str| {
+            GoalText::new(title)
+                .row(CATEGORY_ROW, category)
+                .document(&format!("src/2025h1/{title}.md"))
+        };
+        let goals = [
+            goal("a", "Tooling"),
+            goal("b", ""),
+            goal("c", "Async"),
+            goal("d", "Tooling"),
+        ];
+        let goals: Vec<&GoalDocument> = goals.iter().collect();
+        let titles = |goals: &[&GoalDocument]| -> Vec<String> {
+            goals.iter().map(|g| g.metadata.title.clone()).collect()
+        };
+
+        let groups: Vec<(&str, Vec<String>)> = by_category(&goals)
+            .unwrap()
+            .into_iter()
+            .map(|(category, goals)| (category, titles(&goals)))
+            .collect();
+        assert_eq!(
+            groups,
+            [
+                ("Async", vec!["c".to_string()]),
+                ("Tooling", vec!["a".to_string(), "d".to_string()]),
+                (OTHER_CATEGORY, vec!["b".to_string()]),
+            ]
+        );
+
+        assert!(by_category(&goals[1..2]).is_none());
+    }
+
+    #[test]
+    fn blocked_is_split_from_owners() {
+        assert_eq!(
+            split_blocked("@alice (blocked: waiting on RFC 1234)"),
+            (
+                "@alice".to_string(),
+                Some("waiting on RFC 1234".to_string())
+            )
+        );
+        assert_eq!(
+            split_blocked("@alice (blocked:)"),
+            ("@alice".to_string(), Some(String::new()))
+        );
+        assert_eq!(split_blocked("@alice"), ("@alice".to_string(), None));
+    }
+
+    #[test]
+    fn non_goals_are_skipped_unless_strict() {
+        let dir = std::env::temp_dir().join(format!(
+            "non_goals_are_skipped_unless_strict-{}",
+            std::process::id()
+        ));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("README.md"), "# Readme\n\nNot a goal.\n").unwrap();
+
+        assert!(goals_in_dir(&dir, false).unwrap().is_empty());
+        assert!(goals_in_dir(&dir, true).is_err());
+        assert!(validate_metadata_in_dir(&dir, None, false)
+            .unwrap()
+            .is_empty());
+        assert_eq!(validate_metadata_in_dir(&dir, None, true).unwrap().len(), 1);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -1,8 +1,10 @@
+pub mod action;
 pub mod config;
 pub mod format_team_ask;
 pub mod gh;
 pub mod goal;
 pub mod markwaydown;
 pub mod re;
+pub mod sync;
 pub mod team;
 pub mod util;
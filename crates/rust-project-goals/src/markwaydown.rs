@@ -199,7 +199,14 @@ impl Table {
     /// Modify `path` to replace the lines containing this table with `new_table`.
     pub fn overwrite_in_path(&self, path: &Path, new_table: &Table) -> anyhow::Result<()> {
         let full_text = std::fs::read_to_string(path)?;
+        let new_text = self.overwrite_in_text(&full_text, new_table);
+        std::fs::write(path, new_text)?;
+        Ok(())
+    }
 
+    /// Returns `full_text` (the text this table was parsed from)
+    /// with the lines containing this table replaced by `new_table`.
+    pub fn overwrite_in_text(&self, full_text: &str, new_table: &Table) -> String {
         let mut new_text = full_text[..self.header[0].span.bytes.start].to_string();
 
         let table_text = {
@@ -209,9 +216,6 @@ impl Table {
         };
         new_text.push_str(&table_text);
         new_text.push_str(&full_text[self.rows.last().unwrap().last().unwrap().span.bytes.end..]);
-
-        std::fs::write(path, new_text)?;
-
-        Ok(())
+        new_text
     }
 }
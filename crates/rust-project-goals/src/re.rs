@@ -46,6 +46,47 @@ lazy_static! {
     pub static ref VALID_TEAM_ASKS: Regex = Regex::new(r"<!-- VALID TEAM ASKS -->").unwrap();
 }
 
+lazy_static! {
+    /// A markdown link to another `.md` file, like `](./async.md#summary)`.
+    /// Captures the path without the extension and the (optional) anchor.
+    pub static ref MD_LINK: Regex = Regex::new(r"\]\(([^(]*)\.md(#[^)]*)?\)").unwrap();
+}
+
+lazy_static! {
+    /// A markdown link to an `.html` page, like `](../2024h2/async.html#summary)`.
+    /// Captures the path without the extension and the (optional) anchor.
+    pub static ref HTML_LINK: Regex = Regex::new(r"\]\(([^(]*)\.html(#[^)]*)?\)").unwrap();
+}
+
+lazy_static! {
+    /// A timeframe directory name, like `2024h2`.
+    pub static ref TIMEFRAME: Regex = Regex::new(r"^\d{4}h[12]$").unwrap();
+}
+
+lazy_static! {
+    /// An effort annotation in the owners of a plan item, like `(est: 2w)` or `(est: 3d, actual: 1w)`.
+    /// Captures the estimated amount and unit, and the actual amount and unit if present.
+    pub static ref EFFORT: Regex =
+        Regex::new(r"\(\s*est:\s*(\d+)\s*([dwm])\s*(?:,\s*actual:\s*(\d+)\s*([dwm])\s*)?\)").unwrap();
+}
+
+lazy_static! {
+    /// A blocked annotation in the owners of a plan item, like `(blocked: waiting on RFC 1234)`.
+    /// Captures the reason.
+    pub static ref BLOCKED: Regex = Regex::new(r"\(\s*blocked:\s*([^)]*?)\s*\)").unwrap();
+}
+
+lazy_static! {
+    /// The annotation marking a plan item (or a task in a tracking issue) as a stretch goal,
+    /// like `(stretch)`.
+    pub static ref STRETCH: Regex = Regex::new(r"(?i)\(\s*stretch\s*\)").unwrap();
+}
+
+lazy_static! {
+    /// A team listed as an owner, like `[lang]`.
+    pub static ref TEAM_OWNER: Regex = Regex::new(r"\[([-.A-Za-z]+)\]").unwrap();
+}
+
 lazy_static! {
     /// Github username.
     ///
@@ -65,6 +106,23 @@ lazy_static! {
     pub static ref TRACKING_ISSUE: Regex = Regex::new(r"\[([^#/]*)/([^#/]*)#([0-9]+)\]").unwrap();
 }
 
+lazy_static! {
+    /// Something that looks like a github token (classic, OAuth, app, or fine-grained).
+    pub static ref GITHUB_TOKEN: Regex =
+        Regex::new(r"\b(gh[pousr]_[A-Za-z0-9]{20,}|github_pat_[A-Za-z0-9_]{20,})").unwrap();
+}
+
+lazy_static! {
+    /// The id at the end of the URL of an issue comment.
+    pub static ref COMMENT_ID: Regex = Regex::new(r"#issuecomment-([0-9]+)$").unwrap();
+}
+
+lazy_static! {
+    /// A full github issue URL, as pasted from the browser (optionally in `<>`).
+    pub static ref ISSUE_URL: Regex =
+        Regex::new(r"^<?https://github\.com/([^/\s]+)/([^/\s]+)/issues/([0-9]+)/?>?$").unwrap();
+}
+
 lazy_static! {
     pub static ref CHECKBOX: Regex = Regex::new(r"\s*[-*] \[[ x]\] ").unwrap();
 }
@@ -110,3 +168,18 @@ lazy_static! {
 
 /// If a comment begins with this text, it will be considered a summary.
 pub const TLDR: &str = "TL;DR:";
+
+// Hidden marker at the end of a generated tracking issue body, recording when it was last synced
+// and a hash of the generated content (excluding the marker itself).
+lazy_static! {
+    pub static ref SYNC_MARKER: Regex =
+        Regex::new(r"<!-- synced: (\S+) timeframe=(\S+) hash=([0-9a-f]+) -->").unwrap();
+}
+
+// Hidden marker right before the sync marker, naming the goal (by the file name of its document)
+// that the issue tracks, so that the issue can be found again after the goal is renamed.
+// Also records a hash of the title at the last sync, to tell which side renamed the goal.
+lazy_static! {
+    pub static ref GOAL_MARKER: Regex =
+        Regex::new(r"<!-- rpg:goal=(\S+)(?: title=([0-9a-f]+))? -->").unwrap();
+}
@@ -0,0 +1,4167 @@
+//! Planning the sync of the tracking issues with the goal documents: the labels, issues,
+//! comments and assignees that have to change on github (and the goal documents that have
+//! to be linked to their issue), as [`GithubAction`]s to execute, or with [`plan_actions`],
+//! as owned [`Action`]s.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Display,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+use unicode_normalization::UnicodeNormalization;
+
+use crate::{
+    action::{Action, PeriodBy},
+    gh::{
+        backend::GithubBackend,
+        issue_id::{IssueId, Repository},
+        issues::{
+            ExistingGithubComment, ExistingGithubIssue, FLAGSHIP_LABEL, TASKS_COMMENT_MARKER,
+            TRACKING_ISSUE_LABEL,
+        },
+        labels::GhLabel,
+    },
+    goal::{self, AcceptanceStatus, GoalDocument, GoalPlan, ParsedOwners, PlanItem},
+    re,
+    team::{get_person_data, TeamName},
+    util::commas,
+};
+use rust_project_goals_json::GithubIssueState;
+
+mod body_template;
+mod comment_template;
+mod ignore;
+#[cfg(test)]
+mod snapshot_tests;
+
+pub use body_template::set_body_template;
+use body_template::{BodyTemplate, IssueBody};
+pub use comment_template::{set_comment_templates, CommentTemplates, GoalComment};
+pub use ignore::IgnoredActions;
+
+/// Checks that `path` is a relative directory and returns its timeframe (e.g., `2024h2`).
+///
+/// Unless `timeframe` is given explicitly, this is the last component of `path` that
+/// looks like a timeframe, so nested folders like `src/2024h2/flagship` work too.
+pub fn validate_path(path: &Path, timeframe: Option<&str>) -> anyhow::Result<String> {
+    if !path.is_dir() {
+        return Err(anyhow::anyhow!(
+            "RFC path should be a directory like src/2024h2"
+        ));
+    };
+
+    if path.is_absolute() {
+        return Err(anyhow::anyhow!("RFC path should be relative"));
+    }
+
+    if let Some(timeframe) = timeframe {
+        return Ok(timeframe.to_string());
+    }
+
+    // Goals can declare their timeframe, so that directories need not be named after it.
+    if let Some(timeframe) = goal::declared_timeframe(path)? {
+        return Ok(timeframe);
+    }
+
+    let timeframe = timeframe_in_path(path).ok_or_else(|| {
+        anyhow::anyhow!(
+            "no timeframe like `2024h2` found in path `{}`; pass `--timeframe` explicitly",
+            path.display()
+        )
+    })?;
+
+    Ok(timeframe.to_string())
+}
+
+/// The last component of `path` that looks like a timeframe (e.g., `2024h2`).
+fn timeframe_in_path(path: &Path) -> Option<&str> {
+    path.components()
+        .rev()
+        .filter_map(|component| component.as_os_str().to_str())
+        .find(|component| re::TIMEFRAME.is_match(component))
+}
+
+/// Options for the labels that the `issues` command creates and applies.
+#[derive(Debug)]
+#[cfg_attr(feature = "clap", derive(clap::Args))]
+pub struct LabelOptions {
+    /// Color (six hex digits) for the `T-<team>` labels. Existing labels
+    /// with a different color are updated.
+    #[cfg_attr(feature = "clap", arg(long, value_parser = parse_label_color, default_value = TEAM_LABEL_COLOR))]
+    pub team_label_color: String,
+
+    /// Prefix (e.g., `goals/`) prepended to every label created or applied by this tool,
+    /// so that it only ever touches its own labels on a shared repository.
+    #[cfg_attr(feature = "clap", arg(long, default_value = ""))]
+    pub label_prefix: String,
+
+    /// Name of the label that marks tracking issues, for repositories with their own
+    /// conventions (e.g., `tracking-issue`). The label prefix applies to it too.
+    #[cfg_attr(feature = "clap", arg(long, default_value = TRACKING_ISSUE_LABEL))]
+    pub tracking_label: String,
+}
+
+impl LabelOptions {
+    /// The name of the tool-owned label `name`, with the prefix applied.
+    fn label(&self, name: &str) -> String {
+        format!("{}{name}", self.label_prefix)
+    }
+
+    /// The name of the label that marks tracking issues, with the prefix applied.
+    pub fn tracking_issue_label(&self) -> String {
+        self.label(&self.tracking_label)
+    }
+}
+
+/// The accepted goals in each of `paths` (one timeframe folder per path), with their timeframe.
+pub fn load_timeframes(
+    paths: &[PathBuf],
+    timeframe: Option<&str>,
+    strict: bool,
+) -> anyhow::Result<Vec<(String, Vec<GoalDocument>)>> {
+    let mut timeframes = vec![];
+    for path in paths {
+        let timeframe = validate_path(path, timeframe)?;
+        let mut goal_documents = goal::goals_in_dir(path, strict)?;
+        goal_documents.retain(|gd| gd.is_not_not_accepted());
+        timeframes.push((timeframe, goal_documents));
+    }
+    Ok(timeframes)
+}
+
+/// Plan the sync of the tracking issues for the goals in each of `paths` (one timeframe folder
+/// per path), in the order the actions would be executed, as owned [`Action`]s that can be
+/// kept or serialized once the goal documents are gone.
+pub fn plan_actions(
+    github: &dyn GithubBackend,
+    repository: &Repository,
+    paths: &[PathBuf],
+    timeframe: Option<&str>,
+    label_options: &LabelOptions,
+    sync_options: SyncOptions<'_>,
+) -> anyhow::Result<Vec<Action>> {
+    let timeframes = load_timeframes(paths, timeframe, sync_options.strict)?;
+    let actions = plan_timeframes(
+        github,
+        repository,
+        &timeframes,
+        label_options,
+        sync_options,
+        &mut |_| Ok(()),
+    )?;
+    Ok(actions.iter().map(GithubAction::to_action).collect())
+}
+
+/// Plan the actions that bring the labels and tracking issues of the goals in `timeframes`
+/// up to date (one pass of the sync). Each action is passed to `on_planned` as soon as it is
+/// planned, so that a dry run can show them without waiting for all the issues to be fetched.
+pub fn plan_timeframes<'doc>(
+    github: &dyn GithubBackend,
+    repository: &Repository,
+    timeframes: &'doc [(String, Vec<GoalDocument>)],
+    label_options: &LabelOptions,
+    sync_options: SyncOptions<'_>,
+    on_planned: &mut dyn FnMut(&GithubAction<'_>) -> anyhow::Result<()>,
+) -> anyhow::Result<BTreeSet<GithubAction<'doc>>> {
+    // Labels are shared between timeframes, so only create each one once.
+    let mut labelled_teams = BTreeSet::new();
+    let mut extra_labels = BTreeSet::new();
+    for (_, goal_documents) in timeframes {
+        labelled_teams.extend(labelled_teams_in(goal_documents)?);
+        extra_labels.extend(extra_labels_in(goal_documents));
+    }
+
+    let mut actions = initialize_labels(
+        github,
+        repository,
+        &labelled_teams,
+        &extra_labels,
+        label_options,
+    )?;
+    if sync_options.period_by == PeriodBy::Label {
+        let periods: Vec<&str> = timeframes.iter().map(|(t, _)| t.as_str()).collect();
+        actions.extend(initialize_period_labels(github, repository, &periods)?);
+    }
+    for action in &actions {
+        on_planned(action)?;
+    }
+    for (timeframe, goal_documents) in timeframes {
+        actions.extend(initialize_issues(
+            github,
+            repository,
+            timeframe,
+            goal_documents,
+            label_options,
+            sync_options,
+            on_planned,
+        )?);
+    }
+    Ok(actions)
+}
+
+/// Number of completed and total plan items for a goal, as recorded in the progress state file.
+/// Stretch goals are counted separately, so that they do not hold back the completion of the goal.
+/// Blocked items (see [`PlanItem::is_blocked`]) are counted among the incomplete ones, and also
+/// on their own.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct TaskCounts {
+    pub completed: u32,
+    pub total: u32,
+    #[serde(default)]
+    pub stretch_completed: u32,
+    #[serde(default)]
+    pub stretch_total: u32,
+    #[serde(default)]
+    pub blocked: u32,
+}
+
+impl TaskCounts {
+    pub fn of(goal_document: &GoalDocument) -> Self {
+        Self::of_items(
+            goal_document
+                .goal_plans
+                .iter()
+                .flat_map(|plan| &plan.plan_items),
+        )
+    }
+
+    pub fn of_items<'a>(plan_items: impl IntoIterator<Item = &'a PlanItem>) -> Self {
+        let mut counts = TaskCounts::default();
+        for plan_item in plan_items {
+            let (completed, total) = if plan_item.stretch {
+                (&mut counts.stretch_completed, &mut counts.stretch_total)
+            } else {
+                (&mut counts.completed, &mut counts.total)
+            };
+            *total += 1;
+            if plan_item.is_complete() {
+                *completed += 1;
+            }
+            if plan_item.is_blocked() {
+                counts.blocked += 1;
+            }
+        }
+        counts
+    }
+
+    /// The progress on stretch goals, like `, plus 1/2 stretch`, or nothing if there are none.
+    pub fn stretch_suffix(&self) -> String {
+        if self.stretch_total == 0 {
+            String::new()
+        } else {
+            format!(
+                ", plus {}/{} stretch",
+                self.stretch_completed, self.stretch_total
+            )
+        }
+    }
+
+    /// The number of blocked tasks, like `, 1 blocked`, or nothing if there are none.
+    pub fn blocked_suffix(&self) -> String {
+        if self.blocked == 0 {
+            String::new()
+        } else {
+            format!(", {} blocked", self.blocked)
+        }
+    }
+}
+
+/// An [`UpdateIssueBody`](GithubAction::UpdateIssueBody) for the tracking issue of each of
+/// `goal_documents` (the goals of `milestone`) whose body differs from the one the current
+/// body template generates. Titles, labels and assignees are left alone.
+pub fn plan_bodies<'doc>(
+    github: &dyn GithubBackend,
+    repository: &Repository,
+    milestone: &str,
+    goal_documents: &'doc [GoalDocument],
+    period_by: PeriodBy,
+    assignee_limit: usize,
+    strict: bool,
+) -> anyhow::Result<BTreeSet<GithubAction<'doc>>> {
+    let umbrella_children = umbrella_children(goal_documents)?;
+
+    // Labels do not appear in the body, so the defaults will do.
+    let label_options = LabelOptions {
+        team_label_color: TEAM_LABEL_COLOR.to_string(),
+        label_prefix: String::new(),
+        tracking_label: TRACKING_ISSUE_LABEL.to_string(),
+    };
+    let sync_options = SyncOptions {
+        force_update_body: true,
+        assignee_limit,
+        period_by,
+        strict,
+        ..SyncOptions::default()
+    };
+    let mut lookup_failures = vec![];
+    let mut desired_issues = vec![];
+    for goal_document in goal_documents {
+        if goal_document.metadata.tracking_issue.is_none() {
+            continue;
+        }
+        let children = umbrella_children
+            .get(goal_document.slug())
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        desired_issues.push(issue(
+            milestone,
+            goal_document,
+            children,
+            &label_options,
+            sync_options,
+            &mut lookup_failures,
+        )?);
+    }
+
+    plan_body_regeneration(github, repository, milestone, period_by, &desired_issues)
+}
+
+/// An [`UpdateIssueBody`](GithubAction::UpdateIssueBody) for the tracking issue of each of
+/// `desired_issues` (which all have one), unless its body is already exactly as generated.
+fn plan_body_regeneration<'doc>(
+    github: &dyn GithubBackend,
+    repository: &Repository,
+    milestone: &str,
+    period_by: PeriodBy,
+    desired_issues: &[GithubIssue<'doc>],
+) -> anyhow::Result<BTreeSet<GithubAction<'doc>>> {
+    let milestone_issues = period_by.list_issues(github, repository, milestone)?;
+
+    let mut actions = BTreeSet::new();
+    for desired_issue in desired_issues {
+        let Some(tracking_issue) = desired_issue.tracking_issue else {
+            continue;
+        };
+        let existing_issue = match milestone_issues
+            .iter()
+            .find(|issue| issue.number == tracking_issue.number)
+        {
+            Some(issue) => issue.clone(),
+            None => github.fetch_issue(repository, tracking_issue.number)?,
+        };
+
+        let Some((body, last_synced)) =
+            updated_issue_body(milestone, desired_issue, &existing_issue.body, true)
+        else {
+            continue;
+        };
+        // The sync marker's timestamp differs on every run, so only compare what comes before it.
+        if strip_sync_marker(&body).0 == strip_sync_marker(&existing_issue.body).0 {
+            continue;
+        }
+        actions.insert(GithubAction::UpdateIssueBody {
+            number: existing_issue.number,
+            body,
+            last_synced,
+        });
+    }
+    Ok(actions)
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct GithubIssue<'doc> {
+    pub title: String,
+    pub assignees: BTreeSet<String>,
+    pub body: String,
+    pub labels: Vec<String>,
+    pub milestone: String,
+    /// The issue type (e.g., `Task`), on repositories that use issue types.
+    pub issue_type: Option<String>,
+    /// Hash of the generated body, excluding the sync marker (see `content_hash`).
+    pub content_hash: String,
+    /// The comment listing the tasks, if they did not fit in the body (see `--truncate-body`).
+    pub tasks_comment: Option<String>,
+    pub tracking_issue: Option<&'doc IssueId>,
+    pub goal_document: &'doc GoalDocument,
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum GithubAction<'doc> {
+    CreateMilestone {
+        title: String,
+    },
+
+    /// Comes after [`GithubAction::CreateMilestone`], so the milestone exists by then.
+    SetMilestoneDue {
+        title: String,
+        due_on: String,
+        previous: Option<String>,
+    },
+
+    CreateLabel {
+        label: GhLabel,
+    },
+
+    UpdateLabelColor {
+        label: GhLabel,
+        old_color: String,
+    },
+
+    CreateIssue {
+        issue: GithubIssue<'doc>,
+    },
+
+    ReopenIssue {
+        number: u64,
+    },
+
+    ChangeTitle {
+        number: u64,
+        title: String,
+    },
+
+    /// Move the issue to `milestone` and then post `comment` (if any) explaining the move.
+    /// The comment is only posted if the milestone change succeeded, so that we never
+    /// announce a continuation for an issue that did not actually move.
+    /// There is no comment when the issue had no milestone before, as it continues nothing.
+    ///
+    /// With [`PeriodBy::Label`], "moving" means adding the `milestone` label
+    /// and removing the `previous` ones.
+    ChangeMilestone {
+        number: u64,
+        milestone: String,
+        period_by: PeriodBy,
+        previous: Vec<String>,
+        comment: Option<String>,
+    },
+
+    AddLabel {
+        number: u64,
+        label: String,
+    },
+
+    SetIssueType {
+        number: u64,
+        issue_type: String,
+    },
+
+    Comment {
+        number: u64,
+        body: String,
+    },
+
+    UpdateComment {
+        number: u64,
+        comment: ExistingGithubComment,
+        body: String,
+    },
+
+    DeleteComment {
+        number: u64,
+        comment: ExistingGithubComment,
+    },
+
+    UpdateIssueBody {
+        number: u64,
+        body: String,
+        /// When the issue was last synced, if it has a sync marker.
+        last_synced: Option<String>,
+    },
+
+    // We intentionally do not sync the issue *text*, because it may have been edited.
+    SyncAssignees {
+        number: u64,
+        remove_owners: BTreeSet<String>,
+        add_owners: BTreeSet<String>,
+    },
+
+    LockIssue {
+        number: u64,
+    },
+
+    LinkToTrackingIssue {
+        goal_document: &'doc GoalDocument,
+        issue_id: IssueId,
+    },
+
+    /// With [`AssigneeSource::Github`], the reverse of [`GithubAction::SyncAssignees`]:
+    /// bring the owners in the goal document in line with the assignees of its issue.
+    SyncOwnersFromIssue {
+        goal_document: &'doc GoalDocument,
+        number: u64,
+        remove_owners: BTreeSet<String>,
+        add_owners: BTreeSet<String>,
+    },
+
+    /// With [`TitleSource::Github`], the reverse of [`GithubAction::ChangeTitle`]:
+    /// give the goal document the title that its issue was renamed to.
+    RetitleGoal {
+        goal_document: &'doc GoalDocument,
+        number: u64,
+        title: String,
+    },
+
+    CloseIssue {
+        number: u64,
+    },
+
+    AddToProject {
+        number: u64,
+        project: u64,
+        status: Option<String>,
+    },
+}
+
+/// Initializes the required `T-<team>` labels on the repository.
+/// Warns if the labels are found with wrong color.
+///
+/// The `extra_labels` that goals declare in their `Labels` row are created if missing, but
+/// their color is left alone if they exist, as they belong to the repository's own conventions.
+pub fn initialize_labels(
+    github: &dyn GithubBackend,
+    repository: &Repository,
+    teams_with_asks: &BTreeSet<&TeamName>,
+    extra_labels: &BTreeSet<String>,
+    label_options: &LabelOptions,
+) -> anyhow::Result<BTreeSet<GithubAction<'static>>> {
+    let mut desired_labels: BTreeSet<_> = teams_with_asks
+        .iter()
+        .map(|team| {
+            let label_name = label_options.label(&team.gh_label());
+
+            GhLabel {
+                name: label_name,
+                color: label_options.team_label_color.clone(),
+            }
+        })
+        .collect();
+
+    desired_labels.insert(GhLabel {
+        name: label_options.tracking_issue_label(),
+        color: "f5f1fd".to_string(),
+    });
+
+    desired_labels.insert(GhLabel {
+        name: label_options.label(FLAGSHIP_LABEL),
+        color: "5319E7".to_string(),
+    });
+
+    // Labels that already exist only need their color fixed, if it differs.
+    let existing_labels = github.list_labels(repository)?;
+    let mut actions = BTreeSet::new();
+    for extra_label in extra_labels {
+        let exists = existing_labels
+            .iter()
+            .any(|label| label.name == *extra_label)
+            || desired_labels
+                .iter()
+                .any(|label| label.name == *extra_label);
+        if !exists {
+            actions.insert(GithubAction::CreateLabel {
+                label: GhLabel {
+                    name: extra_label.clone(),
+                    color: "ededed".to_string(),
+                },
+            });
+        }
+    }
+    for existing_label in existing_labels {
+        let Some(desired_label) = desired_labels
+            .iter()
+            .find(|label| label.name == existing_label.name)
+            .cloned()
+        else {
+            continue;
+        };
+        desired_labels.remove(&desired_label);
+
+        if !desired_label
+            .color
+            .eq_ignore_ascii_case(&existing_label.color)
+        {
+            actions.insert(GithubAction::UpdateLabelColor {
+                label: desired_label,
+                old_color: existing_label.color,
+            });
+        }
+    }
+
+    actions.extend(
+        desired_labels
+            .into_iter()
+            .map(|label| GithubAction::CreateLabel { label }),
+    );
+    Ok(actions)
+}
+
+/// Default color for the `T-<team>` labels.
+pub const TEAM_LABEL_COLOR: &str = "bfd4f2";
+
+/// Parse a label color given on the command line: six hex digits, optionally preceded by `#`.
+pub fn parse_label_color(s: &str) -> Result<String, String> {
+    let color = s.strip_prefix('#').unwrap_or(s);
+    if color.len() == 6 && color.chars().all(|c| c.is_ascii_hexdigit()) {
+        Ok(color.to_lowercase())
+    } else {
+        Err(format!(
+            "expected a 6-digit hex color like `bfd4f2`, found `{s}`"
+        ))
+    }
+}
+
+/// Initializes the required `T-<team>` labels on the repository.
+/// Warns if the labels are found with wrong color.
+///
+/// See [`SyncOptions`] for how existing issues are updated.
+/// The actions for each issue are passed to `on_planned` as soon as they are planned.
+fn initialize_issues<'doc>(
+    github: &dyn GithubBackend,
+    repository: &Repository,
+    timeframe: &str,
+    goal_documents: &'doc [GoalDocument],
+    label_options: &LabelOptions,
+    sync_options: SyncOptions<'_>,
+    on_planned: &mut dyn FnMut(&GithubAction<'_>) -> anyhow::Result<()>,
+) -> anyhow::Result<BTreeSet<GithubAction<'doc>>> {
+    let umbrella_children = umbrella_children(goal_documents)?;
+
+    // the set of issues we want to exist (goals can opt out with `No tracking issue`)
+    let mut lookup_failures = vec![];
+    let desired_issues: BTreeSet<GithubIssue> = goal_documents
+        .iter()
+        .filter(|goal_document| goal_document.wants_tracking_issue())
+        .map(|goal_document| {
+            let children = umbrella_children
+                .get(goal_document.slug())
+                .map(Vec::as_slice)
+                .unwrap_or_default();
+            issue(
+                timeframe,
+                goal_document,
+                children,
+                label_options,
+                sync_options,
+                &mut lookup_failures,
+            )
+        })
+        .collect::<anyhow::Result<_>>()?;
+
+    if !lookup_failures.is_empty() {
+        for failure in &lookup_failures {
+            eprintln!("{failure}");
+        }
+        anyhow::bail!(
+            "failed to look up {} owner(s) in the Rust team data (without `--owners-strict`, their usernames would be used as is)",
+            lookup_failures.len()
+        );
+    }
+
+    plan_issues(
+        github,
+        repository,
+        timeframe,
+        desired_issues,
+        sync_options,
+        on_planned,
+    )
+}
+
+/// Compute the actions that make the issues in the `timeframe` milestone match `desired_issues`.
+fn plan_issues<'doc>(
+    github: &dyn GithubBackend,
+    repository: &Repository,
+    timeframe: &str,
+    desired_issues: BTreeSet<GithubIssue<'doc>>,
+    sync_options: SyncOptions<'_>,
+    on_planned: &mut dyn FnMut(&GithubAction<'_>) -> anyhow::Result<()>,
+) -> anyhow::Result<BTreeSet<GithubAction<'doc>>> {
+    // the list of existing issues in the target milestone (or with the period label)
+    let milestone_issues = sync_options
+        .period_by
+        .list_issues(github, repository, timeframe)?;
+
+    // the issues already on the project board, if any (new issues are added on the next pass)
+    let project_issues = match sync_options.project {
+        Some(project) => github.list_project_issues(repository, project.number)?,
+        None => BTreeSet::new(),
+    };
+
+    // whether to give tracking issues their type (only if the repository uses issue types at all)
+    let set_issue_types = match sync_options.issue_type {
+        Some(issue_type) => has_issue_type(github, repository, issue_type)?,
+        None => false,
+    };
+
+    // existing issues that some desired issue already matches exactly, which fuzzy matching must not offer
+    let mut claimed: BTreeSet<u64> = desired_issues
+        .iter()
+        .filter_map(|desired_issue| match desired_issue.tracking_issue {
+            Some(tracking_issue) => Some(tracking_issue.number),
+            None => find_goal_issue(&milestone_issues, desired_issue).map(|issue| issue.number),
+        })
+        .collect();
+
+    // Go through each of the issues we want to exist (derived from the goals defined in the target folder)
+    let mut matched_issues = vec![];
+    for desired_issue in desired_issues {
+        // Check if we already created a tracking issue...
+        //
+        let existing_issue = if let Some(tracking_issue) = desired_issue.tracking_issue {
+            // a. We first check if there is a declared tracking issue in the markdown file.
+            // If so, check if we've already loaded its data.
+            if let Some(issue) = milestone_issues
+                .iter()
+                .find(|issue| issue.number == tracking_issue.number)
+            {
+                // If so, reuse it to avoid latency.
+                log::debug!(
+                    "`{}`: declared tracking issue #{} is in the milestone",
+                    desired_issue.goal_document.path.display(),
+                    issue.number,
+                );
+                Some(issue.clone())
+            } else {
+                // If not, load its information from the repository by number.
+                log::debug!(
+                    "`{}`: declared tracking issue #{} is not in the milestone, fetching it",
+                    desired_issue.goal_document.path.display(),
+                    tracking_issue.number,
+                );
+                Some(github.fetch_issue(repository, tracking_issue.number)?)
+            }
+        } else {
+            // b. If the markdown does not have a declared tracking issue, then we can search through
+            // the issues in the milestone for one with the goal's marker or, failing that, the correct title.
+            // We could also do a fresh GH query for an issue with the desired title
+            // but that is slower.
+            //
+            // This addresses a kind of awkward gap in our handling-- when a new project goal
+            // is created, we first create an issue for it, then do a loop and execute again.
+            // This second time, we will find the issue with the known title, get its
+            // number, and put that number into the markdown.
+            match find_goal_issue(&milestone_issues, &desired_issue) {
+                Some(issue) => {
+                    log::debug!(
+                        "`{}`: issue #{} has the goal marker or the same title",
+                        desired_issue.goal_document.path.display(),
+                        issue.number,
+                    );
+                    Some(issue.clone())
+                }
+                None => match sync_options.fuzzy_match {
+                    Some(fuzzy_match) => {
+                        let candidates =
+                            fuzzy_candidates(&desired_issue.title, &milestone_issues, &claimed);
+                        let issue = fuzzy_match.pick(&desired_issue.title, &candidates)?;
+                        if let Some(issue) = issue {
+                            log::debug!(
+                                "`{}`: issue #{} has a close title ({:?}) and was chosen",
+                                desired_issue.goal_document.path.display(),
+                                issue.number,
+                                issue.title,
+                            );
+                            claimed.insert(issue.number);
+                        }
+                        issue.cloned()
+                    }
+                    None => None,
+                },
+            }
+        };
+        matched_issues.push((desired_issue, existing_issue));
+    }
+
+    // the types of the existing issues, fetched all at once rather than one issue at a time
+    let existing_types = if set_issue_types {
+        let numbers: Vec<u64> = matched_issues
+            .iter()
+            .filter_map(|(_, existing_issue)| existing_issue.as_ref())
+            .map(|existing_issue| existing_issue.number)
+            .collect();
+        github.fetch_issue_types(repository, &numbers)?
+    } else {
+        BTreeMap::new()
+    };
+
+    let mut actions = BTreeSet::new();
+    for (desired_issue, existing_issue) in matched_issues {
+        let mut issue_actions = BTreeSet::new();
+        if let (Some(project), Some(existing_issue)) = (sync_options.project, &existing_issue) {
+            if !project_issues.contains(&existing_issue.number) {
+                log::debug!(
+                    "issue #{}: not on project {} -> AddToProject",
+                    existing_issue.number,
+                    project.number,
+                );
+                issue_actions.insert(GithubAction::AddToProject {
+                    number: existing_issue.number,
+                    project: project.number,
+                    status: project.status.map(str::to_string),
+                });
+            }
+        }
+        if let (true, Some(issue_type), Some(existing_issue)) = (
+            set_issue_types,
+            desired_issue.issue_type.as_deref(),
+            &existing_issue,
+        ) {
+            let existing_type = existing_types.get(&existing_issue.number);
+            if existing_type.map(String::as_str) != Some(issue_type) {
+                log::debug!(
+                    "issue #{}: type is {existing_type:?}, not `{issue_type}` -> SetIssueType",
+                    existing_issue.number,
+                );
+                issue_actions.insert(GithubAction::SetIssueType {
+                    number: existing_issue.number,
+                    issue_type: issue_type.to_string(),
+                });
+            }
+        }
+
+        issue_actions.extend(plan_issue_actions(
+            repository,
+            timeframe,
+            desired_issue,
+            existing_issue,
+            sync_options,
+        )?);
+        if let Some(ignored) = sync_options.ignored {
+            issue_actions.retain(|action| {
+                let keep = !ignored.contains(action);
+                if !keep {
+                    eprintln!("Ignoring `{action}` (listed in the ignore file)");
+                }
+                keep
+            });
+        }
+
+        for action in &issue_actions {
+            on_planned(action)?;
+        }
+        actions.extend(issue_actions);
+    }
+
+    Ok(actions)
+}
+
+/// True if `repository` has `issue_type`, false (with a warning) if the repository does not
+/// use issue types, so that `--issue-type` can be passed for every repository.
+fn has_issue_type(
+    github: &dyn GithubBackend,
+    repository: &Repository,
+    issue_type: &str,
+) -> anyhow::Result<bool> {
+    let issue_types = github.list_issue_types(repository)?;
+    if issue_types.is_empty() {
+        eprintln!("warning: `{repository}` does not use issue types, not setting `{issue_type}`");
+        return Ok(false);
+    }
+    if !issue_types.iter().any(|name| name == issue_type) {
+        anyhow::bail!(
+            "`{repository}` has no issue type `{issue_type}` (expected one of {})",
+            commas(issue_types.iter().map(|name| format!("`{name}`")))
+        );
+    }
+    Ok(true)
+}
+
+/// The title in Unicode normal form C, without surrounding whitespace. Editors and github
+/// do not always agree on the form (e.g., composed `é` vs `e` plus a combining accent).
+pub fn normalize_title(title: &str) -> String {
+    title.trim().nfc().collect()
+}
+
+/// The issue among `issues` that tracks the goal of `desired_issue`: the one whose
+/// [`re::GOAL_MARKER`] names the goal or, failing that, one without a marker that has its title.
+/// Issues marked for another goal are never matched by title, even if the goals swapped titles.
+fn find_goal_issue<'i>(
+    issues: &'i [ExistingGithubIssue],
+    desired_issue: &GithubIssue<'_>,
+) -> Option<&'i ExistingGithubIssue> {
+    let slug = desired_issue.goal_document.slug();
+    issues
+        .iter()
+        .find(|issue| goal_marker(&issue.body) == Some(slug))
+        .or_else(|| {
+            issues.iter().find(|issue| {
+                goal_marker(&issue.body).is_none() && same_title(&issue.title, &desired_issue.title)
+            })
+        })
+}
+
+/// The goal named by the [`re::GOAL_MARKER`] in the generated part of an issue body, if any.
+fn goal_marker(body: &str) -> Option<&str> {
+    let c = goal_marker_captures(body)?;
+    Some(c.get(1).unwrap().as_str())
+}
+
+/// The hash of the title that the issue and its goal document had when they were last synced,
+/// as recorded in the [`re::GOAL_MARKER`] (issues synced before we recorded it have none).
+fn synced_title_hash(body: &str) -> Option<&str> {
+    let c = goal_marker_captures(body)?;
+    Some(c.get(2)?.as_str())
+}
+
+fn goal_marker_captures(body: &str) -> Option<regex::Captures<'_>> {
+    let head = match body.find(PRESERVED_TEXT_SEPARATOR) {
+        Some(index) => &body[..index],
+        None => body,
+    };
+    re::GOAL_MARKER.captures_iter(head).last()
+}
+
+/// True if the titles are the same once normalized (see [`normalize_title`]).
+fn same_title(a: &str, b: &str) -> bool {
+    normalize_title(a) == normalize_title(b)
+}
+
+/// Titles at least this similar (see [`title_similarity`]) are offered by `--fuzzy-match`.
+const FUZZY_MATCH_THRESHOLD: f64 = 0.8;
+
+/// The issues among `issues` (other than the `claimed` ones and those marked for a goal)
+/// whose title is close to `title`, most similar first.
+fn fuzzy_candidates<'i>(
+    title: &str,
+    issues: &'i [ExistingGithubIssue],
+    claimed: &BTreeSet<u64>,
+) -> Vec<(&'i ExistingGithubIssue, f64)> {
+    let mut candidates: Vec<_> = issues
+        .iter()
+        .filter(|issue| !claimed.contains(&issue.number) && goal_marker(&issue.body).is_none())
+        .map(|issue| (issue, title_similarity(title, &issue.title)))
+        .filter(|&(_, similarity)| similarity >= FUZZY_MATCH_THRESHOLD)
+        .collect();
+    candidates.sort_by(|(a, a_similarity), (b, b_similarity)| {
+        b_similarity
+            .total_cmp(a_similarity)
+            .then(a.number.cmp(&b.number))
+    });
+    candidates
+}
+
+/// The Levenshtein ratio of the normalized, lowercased titles: 1.0 if they are the same,
+/// down to 0.0 if no character is in the right place.
+fn title_similarity(a: &str, b: &str) -> f64 {
+    let a: Vec<char> = normalize_title(a).to_lowercase().chars().collect();
+    let b: Vec<char> = normalize_title(b).to_lowercase().chars().collect();
+    let longest = a.len().max(b.len());
+    if longest == 0 {
+        return 1.0;
+    }
+
+    // distances between the prefixes of `a` and `b`, one row per character of `a`
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, a_char) in a.iter().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(a_char != b_char);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+
+    1.0 - row[b.len()] as f64 / longest as f64
+}
+
+/// What `--fuzzy-match` does with the close matches of an issue that has no exact match.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum FuzzyMatch {
+    /// Ask on the terminal whether to use one of them.
+    Ask,
+
+    /// Only list them; a new issue is created.
+    Report,
+}
+
+impl FuzzyMatch {
+    /// The candidate to use as the tracking issue for `title`, if any.
+    fn pick<'i>(
+        self,
+        title: &str,
+        candidates: &[(&'i ExistingGithubIssue, f64)],
+    ) -> anyhow::Result<Option<&'i ExistingGithubIssue>> {
+        if candidates.is_empty() {
+            return Ok(None);
+        }
+
+        match self {
+            FuzzyMatch::Report => {
+                eprintln!("note: no issue is titled `{title}`, but these are close:");
+                for (issue, similarity) in candidates {
+                    eprintln!(
+                        "  #{} `{}` ({:.0}% similar)",
+                        issue.number,
+                        issue.title,
+                        similarity * 100.0
+                    );
+                }
+                Ok(None)
+            }
+            FuzzyMatch::Ask => {
+                for &(issue, similarity) in candidates {
+                    eprintln!(
+                        "No issue is titled `{title}`. Use #{} `{}` ({:.0}% similar) as its tracking issue? [y/N]",
+                        issue.number,
+                        issue.title,
+                        similarity * 100.0
+                    );
+
+                    let mut input = String::new();
+                    std::io::stdin().read_line(&mut input)?;
+                    if input.trim().eq_ignore_ascii_case("y") {
+                        return Ok(Some(issue));
+                    }
+                }
+                Ok(None)
+            }
+        }
+    }
+}
+
+/// The github logins of the owners of `document` found by `lookup` (owners that are not
+/// found are not assigned).
+///
+/// When `lookup` fails (e.g., the team data could not be fetched), the username is used as
+/// is with a warning, unless `strict` is set, in which case the failure is added to `failures`.
+fn owner_logins(
+    document: &GoalDocument,
+    lookup: impl Fn(&str) -> anyhow::Result<Option<String>>,
+    strict: bool,
+    failures: &mut Vec<String>,
+) -> Vec<String> {
+    let mut logins = vec![];
+    for username in document.metadata.owner_usernames() {
+        match lookup(username) {
+            Ok(Some(login)) => logins.push(login),
+            Ok(None) => {}
+            Err(e) if strict => failures.push(format!(
+                "`{}`: looking up owner `{username}` failed: {e:#}",
+                document.path.display()
+            )),
+            Err(e) => {
+                eprintln!(
+                    "warning: `{}`: looking up owner `{username}` failed, assigning it as is: {e:#}",
+                    document.path.display()
+                );
+                logins.push(username.trim_start_matches('@').to_string());
+            }
+        }
+    }
+    logins
+}
+
+/// The github username to assign in place of the owners of `document` if none of them
+/// can be assigned (`logins` is empty, e.g. because they were not found in the team data):
+/// `default_owner`, if any. Goals without owners are reported either way.
+fn fallback_owner<'a>(
+    document: &GoalDocument,
+    logins: &[String],
+    default_owner: Option<&'a str>,
+) -> Option<&'a str> {
+    if !logins.is_empty() {
+        return None;
+    }
+    match default_owner {
+        Some(default_owner) => {
+            eprintln!(
+                "warning: `{}` has no owners to assign; assigning the default owner @{default_owner}",
+                document.path.display()
+            );
+            Some(default_owner)
+        }
+        None => {
+            eprintln!(
+                "warning: `{}` has no owners to assign; its tracking issue will have no assignees (see `--default-owner`)",
+                document.path.display()
+            );
+            None
+        }
+    }
+}
+
+/// How the sync creates tracking issues and brings existing ones up to date.
+#[derive(Copy, Clone, Debug)]
+pub struct SyncOptions<'a> {
+    /// Lock tracking issues, unless the goal opted out.
+    pub lock: bool,
+
+    /// Regenerate the body of every existing issue, even if it looks up to date.
+    pub force_update_body: bool,
+
+    /// Maximum number of assignees per issue; further owners are only named in the body.
+    pub assignee_limit: usize,
+
+    /// How the period of a tracking issue is recorded on github.
+    pub period_by: PeriodBy,
+
+    /// Reopen the tracking issues of accepted goals that were closed (otherwise we only warn).
+    pub reopen_closed: bool,
+
+    /// Delete the lock and continuation comments posted by this account (without `@`),
+    /// except the most recent one of each kind.
+    pub prune_comments_by: Option<&'a str>,
+
+    /// Add every tracking issue to this project board of the repository's owner.
+    pub project: Option<Project<'a>>,
+
+    /// What to do when no existing issue has the exact title of a goal but some are close.
+    /// When unset, a new issue is created without looking for close titles.
+    pub fuzzy_match: Option<FuzzyMatch>,
+    /// Refuse to go on if an owner of a goal is not a member of any Rust team.
+    pub require_team_members: bool,
+
+    /// Fail (listing every failure) if an owner cannot be looked up in the team data,
+    /// rather than assigning their username as written.
+    pub owners_strict: bool,
+
+    /// Whether the goal documents or the tracking issues are the source of truth for owners.
+    pub assignee_source: AssigneeSource,
+
+    /// Whether the goal documents or the tracking issues are the source of truth for titles.
+    pub title_source: TitleSource,
+
+    /// Whether assignees that are not owners of the goal are removed from its tracking issue.
+    pub assignees: AssigneeMode,
+
+    /// Github username (without `@`) that owns the goals that list no owners.
+    pub default_owner: Option<&'a str>,
+
+    /// Give every tracking issue this issue type (e.g., `Task`), if the repository uses them.
+    pub issue_type: Option<&'a str>,
+
+    /// Actions that are never planned (see `--ignore-file`).
+    pub ignored: Option<&'a IgnoredActions>,
+
+    /// Move the tasks of goals whose issue body would be too long for github to a comment,
+    /// rather than failing (see `MAX_BODY_LEN`).
+    pub truncate_body: bool,
+
+    /// Markdown files in the goal directories that are not goal documents are errors
+    /// rather than being skipped (`--strict`).
+    pub strict: bool,
+}
+
+/// The goal documents are the source of truth and nothing optional is done: issues are not
+/// locked, bodies only change when they are out of date, and closed issues stay closed.
+impl Default for SyncOptions<'_> {
+    fn default() -> Self {
+        SyncOptions {
+            lock: false,
+            force_update_body: false,
+            assignee_limit: MAX_ASSIGNEES,
+            period_by: PeriodBy::default(),
+            reopen_closed: false,
+            prune_comments_by: None,
+            project: None,
+            fuzzy_match: None,
+            require_team_members: false,
+            owners_strict: false,
+            assignee_source: AssigneeSource::default(),
+            title_source: TitleSource::default(),
+            assignees: AssigneeMode::default(),
+            default_owner: None,
+            issue_type: None,
+            ignored: None,
+            truncate_body: false,
+            strict: false,
+        }
+    }
+}
+
+/// A project (board) that tracking issues are added to, see [`SyncOptions::project`].
+#[derive(Copy, Clone, Debug)]
+pub struct Project<'a> {
+    /// The project number, as in `https://github.com/orgs/<org>/projects/<number>`.
+    pub number: u64,
+
+    /// The value of the `Status` field for issues added to the board. Issues that are
+    /// already on the board keep theirs.
+    pub status: Option<&'a str>,
+}
+
+/// Where the owners of a goal are maintained, see [`SyncOptions::assignee_source`].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+pub enum AssigneeSource {
+    /// The goal document: the assignees of its tracking issue are set to its owners.
+    #[default]
+    Markdown,
+
+    /// The tracking issue: its assignees are written back to the `Additional owners` row of
+    /// the goal document. The point of contact is never changed this way.
+    Github,
+}
+
+/// How the assignees of a tracking issue are brought in line with the owners of its goal,
+/// see [`SyncOptions::assignees`].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+pub enum AssigneeMode {
+    /// The assignees are exactly the owners: anyone else is unassigned.
+    #[default]
+    Reconcile,
+
+    /// Owners are assigned, but nobody is ever unassigned, so that people who assigned
+    /// themselves on github to pitch in are kept.
+    Additive,
+}
+
+/// Where the title of a goal is maintained, see [`SyncOptions::title_source`].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+pub enum TitleSource {
+    /// The goal document: the title of its tracking issue is set to the goal's.
+    #[default]
+    Markdown,
+
+    /// Either: whichever side changed since the last sync wins, and the document is retitled
+    /// if the issue was renamed. If both changed, we only warn.
+    Github,
+}
+
+/// With [`PeriodBy::Label`], create the labels for `periods` that do not exist yet.
+/// Their color is left alone if they do, as they belong to the repository's own conventions.
+fn initialize_period_labels(
+    github: &dyn GithubBackend,
+    repository: &Repository,
+    periods: &[&str],
+) -> anyhow::Result<BTreeSet<GithubAction<'static>>> {
+    let existing_labels = github.list_labels(repository)?;
+    Ok(periods
+        .iter()
+        .filter(|period| !existing_labels.iter().any(|label| label.name == **period))
+        .map(|period| GithubAction::CreateLabel {
+            label: GhLabel {
+                name: period.to_string(),
+                color: "ededed".to_string(),
+            },
+        })
+        .collect())
+}
+
+/// Github does not allow more than this many assignees on an issue.
+pub const MAX_ASSIGNEES: usize = 10;
+
+/// Parse `--assignee-limit`: between 1 and [`MAX_ASSIGNEES`].
+pub fn parse_assignee_limit(s: &str) -> Result<usize, String> {
+    match s.parse::<usize>() {
+        Ok(limit) if (1..=MAX_ASSIGNEES).contains(&limit) => Ok(limit),
+        _ => Err(format!(
+            "expected a number between 1 and {MAX_ASSIGNEES}, found `{s}`"
+        )),
+    }
+}
+
+/// With [`AssigneeSource::Github`], the edit that brings the owners of `goal_document` in line
+/// with the assignees of `existing_issue`, if any. Owners are compared by username, ignoring
+/// case, so that owners we could not assign (e.g., unknown to the team data, or beyond the
+/// assignee limit) but who were assigned on github are not added a second time.
+/// A point of contact that is no longer assigned is only warned about.
+fn plan_owners_from_issue<'doc>(
+    goal_document: &'doc GoalDocument,
+    existing_issue: &ExistingGithubIssue,
+) -> Option<GithubAction<'doc>> {
+    let number = existing_issue.number;
+    let is_assigned = |username: &str| {
+        existing_issue
+            .assignees
+            .iter()
+            .any(|login| login.eq_ignore_ascii_case(username))
+    };
+    let is_owner = |login: &str| {
+        goal_document
+            .metadata
+            .owner_usernames()
+            .iter()
+            .any(|username| username.trim_start_matches('@').eq_ignore_ascii_case(login))
+    };
+
+    let mut remove_owners = BTreeSet::new();
+    for username in goal_document.metadata.owner_usernames() {
+        let owner = username.trim_start_matches('@');
+        if is_assigned(owner) {
+            continue;
+        }
+        if goal_document
+            .metadata
+            .primary_owner_usernames()
+            .contains(&username)
+        {
+            eprintln!(
+                "warning: `{}`: the point of contact {username} is not assigned to issue #{number}; \
+                     update the `Point of contact` row by hand",
+                goal_document.path.display(),
+            );
+        } else {
+            remove_owners.insert(owner.to_string());
+        }
+    }
+    let add_owners: BTreeSet<String> = existing_issue
+        .assignees
+        .iter()
+        .filter(|login| !is_owner(login))
+        .cloned()
+        .collect();
+
+    if remove_owners.is_empty() && add_owners.is_empty() {
+        return None;
+    }
+    log::debug!(
+        "issue #{number}: assignees differ ({:?} vs {:?}) -> SyncOwnersFromIssue",
+        existing_issue.assignees,
+        goal_document.metadata.owner_usernames(),
+    );
+    Some(GithubAction::SyncOwnersFromIssue {
+        goal_document,
+        number,
+        remove_owners,
+        add_owners,
+    })
+}
+
+/// With [`TitleSource::Github`], the action that brings the differing titles of `desired_issue`
+/// and `existing_issue` in line, if we can tell which one changed since the last sync (from the
+/// title recorded in the [`re::GOAL_MARKER`]). If both did, or nothing was recorded, we warn.
+fn plan_title_from_issue<'doc>(
+    desired_issue: &GithubIssue<'doc>,
+    existing_issue: &ExistingGithubIssue,
+) -> Option<GithubAction<'doc>> {
+    let number = existing_issue.number;
+    let goal_document = desired_issue.goal_document;
+    let recorded = synced_title_hash(&existing_issue.body);
+    let changed = |title: &str| recorded != Some(title_hash(title).as_str());
+    match (
+        recorded,
+        changed(&desired_issue.title),
+        changed(&existing_issue.title),
+    ) {
+        (Some(_), true, false) => {
+            log::debug!(
+                "issue #{number}: goal retitled to {:?} -> ChangeTitle",
+                desired_issue.title,
+            );
+            Some(GithubAction::ChangeTitle {
+                number,
+                title: desired_issue.title.clone(),
+            })
+        }
+        (Some(_), false, true) => {
+            log::debug!(
+                "issue #{number}: issue retitled to {:?} -> RetitleGoal",
+                existing_issue.title,
+            );
+            Some(GithubAction::RetitleGoal {
+                goal_document,
+                number,
+                title: existing_issue.title.clone(),
+            })
+        }
+        (recorded, ..) => {
+            eprintln!("warning: `{}`: the goal is titled {:?} but issue #{number} is titled {:?}, and {}; \
+                     make them the same by hand",
+                    goal_document.path.display(),
+                    desired_issue.title,
+                    existing_issue.title,
+                    if recorded.is_some() {
+                        "both changed since the last sync"
+                    } else {
+                        "no title was recorded at the last sync to tell which one changed"
+                    },);
+            None
+        }
+    }
+}
+
+/// Compute the actions that bring `existing_issue` (if any) in line with `desired_issue`.
+fn plan_issue_actions<'doc>(
+    repository: &Repository,
+    timeframe: &str,
+    desired_issue: GithubIssue<'doc>,
+    existing_issue: Option<ExistingGithubIssue>,
+    sync_options: SyncOptions<'_>,
+) -> anyhow::Result<BTreeSet<GithubAction<'doc>>> {
+    let mut issue_actions = BTreeSet::new();
+
+    match existing_issue {
+        Some(existing_issue) => {
+            let number = existing_issue.number;
+            let periods = sync_options.period_by.periods_of(&existing_issue);
+            let comment = GoalComment {
+                previous_milestone: periods.first().map(|p| p.as_str()).unwrap_or_default(),
+                milestone: timeframe,
+                title: &desired_issue.title,
+                owners: commas(desired_issue.goal_document.metadata.owner_usernames()),
+            };
+
+            let issue_id = IssueId::new(repository.clone(), existing_issue.number);
+
+            if existing_issue.assignees != desired_issue.assignees {
+                match sync_options.assignee_source {
+                    AssigneeSource::Markdown => {
+                        let remove_owners: BTreeSet<String> = match sync_options.assignees {
+                            AssigneeMode::Reconcile => existing_issue
+                                .assignees
+                                .difference(&desired_issue.assignees)
+                                .cloned()
+                                .collect(),
+                            AssigneeMode::Additive => BTreeSet::new(),
+                        };
+                        let add_owners: BTreeSet<String> = desired_issue
+                            .assignees
+                            .difference(&existing_issue.assignees)
+                            .cloned()
+                            .collect();
+                        if !remove_owners.is_empty() || !add_owners.is_empty() {
+                            log::debug!(
+                                "issue #{number}: assignees differ ({:?} vs {:?}) -> SyncAssignees",
+                                existing_issue.assignees,
+                                desired_issue.assignees,
+                            );
+                            issue_actions.insert(GithubAction::SyncAssignees {
+                                number: existing_issue.number,
+                                remove_owners,
+                                add_owners,
+                            });
+                        }
+                    }
+
+                    // Only write back the owners of documents that are already linked to
+                    // the issue, so that we know the issue is theirs (and so that linking
+                    // and writing back do not edit the metadata table in the same run).
+                    AssigneeSource::Github if desired_issue.tracking_issue == Some(&issue_id) => {
+                        issue_actions.extend(plan_owners_from_issue(
+                            desired_issue.goal_document,
+                            &existing_issue,
+                        ));
+                    }
+                    AssigneeSource::Github => {}
+                }
+            }
+
+            // Declared labels are added if missing, but never removed: the goal may have
+            // dropped one that was since applied to the issue by hand.
+            for label in &desired_issue.goal_document.metadata.extra_labels {
+                if !existing_issue.has_label(label) {
+                    log::debug!("issue #{number}: declared label `{label}` is missing -> AddLabel");
+                    issue_actions.insert(GithubAction::AddLabel {
+                        number: existing_issue.number,
+                        label: label.clone(),
+                    });
+                }
+            }
+
+            let accepted = desired_issue.goal_document.metadata.status.acceptance
+                == AcceptanceStatus::Accepted;
+            if accepted && existing_issue.state == GithubIssueState::Closed {
+                if sync_options.reopen_closed {
+                    log::debug!("issue #{number}: closed but the goal is accepted -> ReopenIssue");
+                    issue_actions.insert(GithubAction::ReopenIssue {
+                        number: existing_issue.number,
+                    });
+                } else {
+                    eprintln!(
+                        "warning: `{}`: the goal is accepted but its tracking issue #{} is closed (use `--reopen-closed` to reopen it)",
+                        desired_issue.goal_document.path.display(),
+                        existing_issue.number
+                    );
+                }
+            }
+
+            // With `--title-source github`, the title that the issue and the document share
+            // once the actions are done, to record in the goal marker (`None` on a conflict).
+            let mut synced_title = None;
+            if !same_title(&existing_issue.title, &desired_issue.title) {
+                match sync_options.title_source {
+                    TitleSource::Markdown => {
+                        log::debug!(
+                            "issue #{number}: title differs ({:?} vs {:?}) -> ChangeTitle",
+                            existing_issue.title,
+                            desired_issue.title,
+                        );
+                        issue_actions.insert(GithubAction::ChangeTitle {
+                            number: existing_issue.number,
+                            title: desired_issue.title.clone(),
+                        });
+                    }
+                    TitleSource::Github => {
+                        let action = plan_title_from_issue(&desired_issue, &existing_issue);
+                        synced_title = match &action {
+                            Some(
+                                GithubAction::ChangeTitle { title, .. }
+                                | GithubAction::RetitleGoal { title, .. },
+                            ) => Some(title.clone()),
+                            _ => None,
+                        };
+                        issue_actions.extend(action);
+                    }
+                }
+            } else if sync_options.title_source == TitleSource::Github {
+                synced_title = Some(existing_issue.title.clone());
+            }
+
+            if !periods.iter().any(|period| period == timeframe) {
+                log::debug!(
+                    "issue #{number}: period is {periods:?}, not {timeframe:?} -> ChangeMilestone"
+                );
+                issue_actions.insert(GithubAction::ChangeMilestone {
+                    number: existing_issue.number,
+                    milestone: timeframe.to_string(),
+                    period_by: sync_options.period_by,
+                    previous: periods.clone(),
+                    comment: if periods.is_empty() {
+                        None
+                    } else {
+                        Some(CommentTemplates::get()?.continuing_goal(&comment)?)
+                    },
+                });
+            }
+
+            let lockable = sync_options.lock && desired_issue.goal_document.metadata.lockable;
+            if lockable && !existing_issue.was_locked() {
+                log::debug!("issue #{number}: lockable but has no lock comment -> LockIssue");
+                issue_actions.insert(GithubAction::LockIssue {
+                    number: existing_issue.number,
+                });
+                issue_actions.insert(GithubAction::Comment {
+                    number: existing_issue.number,
+                    body: CommentTemplates::get()?.lock_issue(&comment)?,
+                });
+            }
+
+            issue_actions.extend(plan_tasks_comment(
+                &existing_issue,
+                desired_issue.tasks_comment.as_deref(),
+            ));
+
+            if let Some(bot_account) = sync_options.prune_comments_by {
+                for comment in outdated_comments(&existing_issue, bot_account) {
+                    log::debug!(
+                        "issue #{number}: comment {} by {bot_account} is outdated -> DeleteComment",
+                        comment.url,
+                    );
+                    issue_actions.insert(GithubAction::DeleteComment {
+                        number: existing_issue.number,
+                        comment: comment.clone(),
+                    });
+                }
+            }
+
+            let mut body_update = updated_issue_body(
+                timeframe,
+                &desired_issue,
+                &existing_issue.body,
+                sync_options.force_update_body,
+            );
+            if let Some(title) = synced_title {
+                if let Some((body, _)) = &mut body_update {
+                    *body = with_synced_title(body, &title);
+                } else if goal_marker(&existing_issue.body).is_some()
+                    && synced_title_hash(&existing_issue.body) != Some(title_hash(&title).as_str())
+                {
+                    log::debug!("issue #{number}: synced title not recorded -> UpdateIssueBody");
+                    let (_, marker) = strip_sync_marker(&existing_issue.body);
+                    let last_synced = marker.map(|c| c[1].to_string());
+                    issue_actions.insert(GithubAction::UpdateIssueBody {
+                        number: existing_issue.number,
+                        body: with_synced_title(&existing_issue.body, &title),
+                        last_synced,
+                    });
+                }
+            }
+            if let Some((body, last_synced)) = body_update {
+                log::debug!(
+                    "issue #{number}: {} (last synced {last_synced:?}) -> UpdateIssueBody",
+                    if sync_options.force_update_body {
+                        "body update forced"
+                    } else {
+                        "goal content changed"
+                    },
+                );
+                issue_actions.insert(GithubAction::UpdateIssueBody {
+                    number: existing_issue.number,
+                    body,
+                    last_synced,
+                });
+            }
+
+            if desired_issue.tracking_issue != Some(&issue_id) {
+                log::debug!(
+                    "issue #{number}: `{}` declares {:?} as its tracking issue -> LinkToTrackingIssue",
+                    desired_issue.goal_document.path.display(),
+                    desired_issue.tracking_issue.map(|issue_id| issue_id.number),
+                );
+                issue_actions.insert(GithubAction::LinkToTrackingIssue {
+                    goal_document: desired_issue.goal_document,
+                    issue_id,
+                });
+            }
+        }
+
+        None => {
+            log::debug!(
+                "`{}`: no existing issue titled {:?} -> CreateIssue",
+                desired_issue.goal_document.path.display(),
+                desired_issue.title,
+            );
+            issue_actions.insert(GithubAction::CreateIssue {
+                issue: desired_issue,
+            });
+        }
+    }
+
+    Ok(issue_actions)
+}
+
+/// Post, update or delete the comment listing the tasks of the goal on `issue`, so that it
+/// matches `tasks_comment` (`None` if the tasks fit in the body).
+fn plan_tasks_comment<'doc>(
+    issue: &ExistingGithubIssue,
+    tasks_comment: Option<&str>,
+) -> Vec<GithubAction<'doc>> {
+    let number = issue.number;
+    let mut existing = issue.comments.iter().filter(|c| c.is_tasks_comment());
+    match (tasks_comment, existing.next()) {
+        (Some(body), None) => {
+            log::debug!("issue #{number}: tasks do not fit in the body -> Comment");
+            vec![GithubAction::Comment {
+                number,
+                body: body.to_string(),
+            }]
+        }
+        (Some(body), Some(comment)) if comment.body.trim() != body.trim() => {
+            log::debug!("issue #{number}: tasks changed -> UpdateComment");
+            vec![GithubAction::UpdateComment {
+                number,
+                comment: comment.clone(),
+                body: body.to_string(),
+            }]
+        }
+        (Some(_), Some(_)) => vec![],
+        (None, first) => first
+            .into_iter()
+            .chain(existing)
+            .map(|comment| {
+                log::debug!("issue #{number}: tasks fit in the body again -> DeleteComment");
+                GithubAction::DeleteComment {
+                    number,
+                    comment: comment.clone(),
+                }
+            })
+            .collect(),
+    }
+}
+
+/// The lock and continuation comments on `issue` posted by `bot_account`, except the most
+/// recent one of each kind. Comments by anyone else are never included.
+fn outdated_comments<'i>(
+    issue: &'i ExistingGithubIssue,
+    bot_account: &str,
+) -> Vec<&'i ExistingGithubComment> {
+    let by_bot = |comment: &&ExistingGithubComment| {
+        comment.author.trim_start_matches('@') == bot_account.trim_start_matches('@')
+    };
+    let mut outdated = vec![];
+    for kind in [
+        ExistingGithubComment::is_lock_comment,
+        ExistingGithubComment::is_continuing_goal_comment,
+    ] {
+        let comments: Vec<_> = issue
+            .comments
+            .iter()
+            .filter(by_bot)
+            .filter(|comment| kind(comment))
+            .collect();
+        if let Some((_latest, earlier)) = comments.split_last() {
+            outdated.extend(earlier);
+        }
+    }
+    outdated
+}
+
+/// Separates the generated part of an issue body from the earlier text we preserved
+/// when we last replaced it.
+const PRESERVED_TEXT_SEPARATOR: &str = "\n---\nNote: we have updated the body to match the ";
+
+/// Decide whether the body of an existing issue must be regenerated. Returns the new body
+/// (and when the issue was last synced) if so, or `None` if the issue is already up to date.
+///
+/// The decision compares the generated content (metadata table, summary, and tasks) rather than
+/// the full body, so that once an issue is synced, re-running is a no-op. With `force`, the body
+/// is regenerated regardless.
+fn updated_issue_body(
+    timeframe: &str,
+    desired_issue: &GithubIssue<'_>,
+    existing_body: &str,
+    force: bool,
+) -> Option<(String, Option<String>)> {
+    let (head, preserved) = match existing_body.find(PRESERVED_TEXT_SEPARATOR) {
+        Some(index) => (&existing_body[..index], Some(&existing_body[index..])),
+        None => (existing_body, None),
+    };
+    let (content, marker) = strip_sync_marker(head);
+
+    // The goal document has not changed since the last sync (even if the issue was edited since),
+    // or the body already matches (e.g., it was generated before we had the sync marker).
+    let recorded_hash = marker.as_ref().map(|c| &c[3]);
+    let matches_goal = content_hash(content) == desired_issue.content_hash;
+    if !force && (recorded_hash == Some(desired_issue.content_hash.as_str()) || matches_goal) {
+        return None;
+    }
+
+    let last_synced = marker.as_ref().map(|c| c[1].to_string());
+    let unedited = matches_goal || recorded_hash == Some(content_hash(content).as_str());
+    let body = if unedited {
+        // We generated the current text ourselves, so there is nothing new to preserve;
+        // only carry over whatever was preserved before.
+        format!("{}{}", desired_issue.body, preserved.unwrap_or(""))
+    } else {
+        // Let's update the tracking issue to the new goal description, while keeping
+        // the old text in case we need it. It's surprisingly hard to get out of GH
+        // otherwise.
+        format!(
+            "{desired_body}{PRESERVED_TEXT_SEPARATOR}{timeframe} goal. \
+             Your original text is preserved below. \
+             <details>\n{existing_body}\n</details>",
+            desired_body = desired_issue.body,
+        )
+    };
+    Some((body, last_synced))
+}
+
+/// Split an issue body into the content before the [`re::GOAL_MARKER`] (if any) and
+/// [`re::SYNC_MARKER`], and the sync marker itself (if any).
+fn strip_sync_marker(body: &str) -> (&str, Option<regex::Captures<'_>>) {
+    match re::SYNC_MARKER.captures(body) {
+        Some(c) => {
+            let content = &body[..c.get(0).unwrap().start()];
+            let content = content.strip_suffix('\n').unwrap_or(content);
+            let content = match re::GOAL_MARKER.find_iter(content).last() {
+                Some(m) if m.end() == content.len() => &content[..m.start()],
+                _ => content,
+            };
+            (content.strip_suffix('\n').unwrap_or(content), Some(c))
+        }
+        None => (body, None),
+    }
+}
+
+fn issue<'doc>(
+    timeframe: &str,
+    document: &'doc GoalDocument,
+    children: &[&GoalDocument],
+    label_options: &LabelOptions,
+    sync_options: SyncOptions<'_>,
+    lookup_failures: &mut Vec<String>,
+) -> anyhow::Result<GithubIssue<'doc>> {
+    let assignee_limit = sync_options.assignee_limit;
+    let owners = owner_logins(
+        document,
+        |username| Ok(get_person_data(username)?.map(|data| data.github_username.clone())),
+        sync_options.owners_strict,
+        lookup_failures,
+    );
+
+    let default_owner = fallback_owner(document, &owners, sync_options.default_owner);
+    let owners = match default_owner {
+        Some(default_owner) => vec![default_owner.to_string()],
+        None => owners,
+    };
+
+    let (assignees, unassigned) = cap_assignees(owners, assignee_limit);
+    if !unassigned.is_empty() {
+        eprintln!(
+            "warning: `{}` has more than {assignee_limit} owners; not assigning {}",
+            document.path.display(),
+            unassigned.join(", ")
+        );
+    }
+
+    let mut labels = issue_labels(document, label_options)?;
+    let milestone = match sync_options.period_by {
+        PeriodBy::Milestone => timeframe.to_string(),
+        PeriodBy::Label => {
+            labels.push(timeframe.to_string());
+            String::new()
+        }
+    };
+
+    let (content, tasks_comment) = issue_content(document, sync_options.truncate_body, |tasks| {
+        render_issue_text(
+            timeframe,
+            document,
+            children,
+            &unassigned,
+            default_owner,
+            tasks,
+        )
+    })?;
+    let content_hash = content_hash(&content);
+    Ok(GithubIssue {
+        title: document.metadata.title.clone(),
+        assignees,
+        body: with_sync_marker(
+            timeframe,
+            document.slug(),
+            &document.metadata.title,
+            &content_hash,
+            content,
+        ),
+        content_hash,
+        tasks_comment,
+        labels,
+        milestone,
+        issue_type: sync_options.issue_type.map(str::to_string),
+        tracking_issue: document.metadata.tracking_issue.as_ref(),
+        goal_document: document,
+    })
+}
+
+/// Labels for the tracking issue of `document`, sorted so that they do not depend on the order
+/// of the goal document. Every goal gets the tracking issue label, even if it has no team asks.
+fn issue_labels(
+    document: &GoalDocument,
+    label_options: &LabelOptions,
+) -> anyhow::Result<Vec<String>> {
+    let mut labels = vec![label_options.tracking_issue_label()];
+    if document.metadata.status.is_flagship {
+        labels.push(label_options.label(FLAGSHIP_LABEL));
+    }
+    for team in labelled_teams_in(std::slice::from_ref(document))? {
+        labels.push(label_options.label(&team.gh_label()));
+    }
+    // Declared labels are the repository's own, so they do not get the prefix.
+    labels.extend(document.metadata.extra_labels.iter().cloned());
+    labels.sort();
+    labels.dedup();
+    Ok(labels)
+}
+
+/// The generated content of the tracking issue for `document`, as `render` renders it with the
+/// given tasks, and the comment listing the tasks if they had to be moved out of the body.
+///
+/// Fails if the body would be longer than github allows, unless `truncate_body` is set and the
+/// body fits once its tasks are replaced by a pointer to the comment.
+fn issue_content(
+    document: &GoalDocument,
+    truncate_body: bool,
+    render: impl Fn(&str) -> anyhow::Result<String>,
+) -> anyhow::Result<(String, Option<String>)> {
+    let tasks = tasks_text(document)?;
+    let content = render(&tasks)?;
+    if body_len(&content) <= MAX_BODY_LEN {
+        return Ok((content, None));
+    }
+    if !truncate_body {
+        anyhow::bail!(
+            "`{}`: the tracking issue body would be {} characters, more than github allows \
+             ({MAX_BODY_LEN}); shorten the goal or use `--truncate-body` to move its tasks \
+             to a comment",
+            document.path.display(),
+            body_len(&content),
+        );
+    }
+
+    let content = render(TASKS_POINTER)?;
+    let comment = format!("{TASKS_COMMENT_MARKER}\n## Tasks and status\n\n{tasks}\n");
+    if body_len(&content) > MAX_BODY_LEN || comment.chars().count() > MAX_BODY_LEN {
+        anyhow::bail!(
+            "`{}`: the tracking issue body is longer than github allows ({MAX_BODY_LEN} \
+             characters) even with its tasks moved to a comment",
+            document.path.display(),
+        );
+    }
+    eprintln!(
+        "warning: `{}`: the tasks do not fit in the tracking issue body; listing them in a comment",
+        document.path.display(),
+    );
+    Ok((content, Some(comment)))
+}
+
+/// A hash of the generated issue `content` that is stable across runs and Rust versions
+/// (64-bit FNV-1a), so that we can tell whether the issue body needs to be regenerated.
+fn content_hash(content: &str) -> String {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in content.bytes() {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    format!("{hash:016x}")
+}
+
+/// Append the hidden [`re::GOAL_MARKER`] and [`re::SYNC_MARKER`] to the generated issue `content`.
+fn with_sync_marker(
+    timeframe: &str,
+    goal_slug: &str,
+    title: &str,
+    content_hash: &str,
+    content: String,
+) -> String {
+    let now = chrono::Utc::now().format("%Y-%m-%dT%H:%MZ");
+    let title_hash = title_hash(title);
+    format!(
+        "{content}\n<!-- rpg:goal={goal_slug} title={title_hash} -->\n\
+         <!-- synced: {now} timeframe={timeframe} hash={content_hash} -->\n"
+    )
+}
+
+/// The hash of `title` recorded in the [`re::GOAL_MARKER`], see [`synced_title_hash`].
+fn title_hash(title: &str) -> String {
+    content_hash(&normalize_title(title))
+}
+
+/// Record in the [`re::GOAL_MARKER`] of the issue `body` that its title was synced as `title`.
+fn with_synced_title(body: &str, title: &str) -> String {
+    re::GOAL_MARKER
+        .replace(body, |c: &regex::Captures<'_>| {
+            format!("<!-- rpg:goal={} title={} -->", &c[1], title_hash(title))
+        })
+        .into_owned()
+}
+
+pub fn goal_document_link(timeframe: &str, document: &GoalDocument) -> String {
+    let goal_file = document.slug();
+    format!("[{timeframe}/{goal_file}](https://rust-lang.github.io/rust-project-goals/{timeframe}/{goal_file}.html)")
+}
+
+/// Split `owners` (primary owners first) into the first `limit` distinct ones, who get assigned,
+/// and the rest.
+fn cap_assignees(owners: Vec<String>, limit: usize) -> (BTreeSet<String>, Vec<String>) {
+    let mut assignees = BTreeSet::new();
+    let mut unassigned = vec![];
+    for owner in owners {
+        if assignees.contains(&owner) || unassigned.contains(&owner) {
+            continue;
+        }
+        if assignees.len() < limit {
+            assignees.insert(owner);
+        } else {
+            unassigned.push(owner);
+        }
+    }
+    (assignees, unassigned)
+}
+
+/// The body of the tracking issue for `document`. Owners in `unassigned` (github usernames
+/// that did not fit in the assignees) are listed next to the point of contact, and
+/// `default_owner` (a github username) is named as the owner that was assigned in their place,
+/// if any (see [`fallback_owner`]).
+/// If `document` is an umbrella goal, `children` are the goals under it.
+fn issue_text(
+    timeframe: &str,
+    document: &GoalDocument,
+    children: &[&GoalDocument],
+    unassigned: &[String],
+    default_owner: Option<&str>,
+) -> anyhow::Result<String> {
+    let tasks = tasks_text(document)?;
+    render_issue_text(
+        timeframe,
+        document,
+        children,
+        unassigned,
+        default_owner,
+        &tasks,
+    )
+}
+
+/// The "Tasks and status" section of the tracking issue for `document`.
+fn tasks_text(document: &GoalDocument) -> anyhow::Result<String> {
+    let mut tasks = vec![];
+    for goal_plan in &document.goal_plans {
+        tasks.extend(task_items(goal_plan, document.metadata.collapse_subgoals)?);
+    }
+    Ok(tasks.join("\n"))
+}
+
+/// GitHub rejects issue bodies and comments longer than this many characters.
+const MAX_BODY_LEN: usize = 65536;
+
+/// Room left in the issue body for the hidden markers appended to the generated content
+/// (see [`with_sync_marker`]).
+const MARKER_RESERVE: usize = 512;
+
+/// Stands in for the tasks in the body of an issue whose tasks are listed in a comment.
+const TASKS_POINTER: &str =
+    "The tasks of this goal are too many for the issue body; they are listed in a comment below.";
+
+/// The length of `content` as github counts it, once the markers are appended.
+fn body_len(content: &str) -> usize {
+    content.chars().count() + MARKER_RESERVE
+}
+
+/// [`issue_text`], with `tasks` as the "Tasks and status" section.
+fn render_issue_text(
+    timeframe: &str,
+    document: &GoalDocument,
+    children: &[&GoalDocument],
+    unassigned: &[String],
+    default_owner: Option<&str>,
+    tasks: &str,
+) -> anyhow::Result<String> {
+    let teams = document
+        .teams_with_asks()
+        .iter()
+        .map(|team| team.name_and_link())
+        .collect::<Vec<_>>();
+    let teams = if teams.is_empty() {
+        "None".to_string()
+    } else {
+        teams.join(", ")
+    };
+
+    // The point of contact is rendered in bold; any other owners get their own row.
+    // Owning teams cannot be assigned to the issue, so we note them next to the point of contact.
+    let owner_teams = document
+        .metadata
+        .owner_teams()?
+        .iter()
+        .map(|team| team.name_and_link())
+        .collect::<Vec<_>>();
+    let mut poc = document
+        .metadata
+        .primary_owner_usernames()
+        .iter()
+        .map(|username| format!("**{username}**"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    if let Some(default_owner) = default_owner {
+        poc = format!("{poc} (default owner: **@{default_owner}**)");
+    }
+    if !owner_teams.is_empty() {
+        poc = format!("{poc} (on behalf of {})", owner_teams.join(", "));
+    }
+    if !unassigned.is_empty() {
+        let unassigned = unassigned
+            .iter()
+            .map(|username| format!("@{username}"))
+            .collect::<Vec<_>>();
+        poc = format!("{poc} (not assigned: {})", unassigned.join(", "));
+    }
+    let mut additional_owners = document
+        .metadata
+        .additional_owner_usernames()
+        .iter()
+        .map(|username| username.to_string())
+        .collect::<Vec<_>>();
+    additional_owners.extend(owner_teams);
+
+    BodyTemplate::get()?.render(&IssueBody {
+        timeframe,
+        title: &document.metadata.title,
+        poc,
+        additional_owners: additional_owners.join(", "),
+        teams,
+        goal_document: goal_document_link(timeframe, document),
+        summary: &document.summary,
+        tasks: tasks.to_string(),
+        asks: ask_items(document).join("\n"),
+        children: child_items(children).join("\n"),
+    })
+}
+
+/// The body of the tracking issue of `document` as `issues` would create it, for previews.
+/// Goals under an umbrella and owners beyond the assignee limit are not taken into account.
+pub fn issue_preview(timeframe: &str, document: &GoalDocument) -> anyhow::Result<String> {
+    issue_text(timeframe, document, &[], &[], None)
+}
+
+/// The goals that name each umbrella goal (by its [slug](`GoalDocument::slug`)) in their
+/// `Umbrella` row.
+fn umbrella_children(
+    goal_documents: &[GoalDocument],
+) -> anyhow::Result<BTreeMap<&str, Vec<&GoalDocument>>> {
+    let mut children: BTreeMap<&str, Vec<&GoalDocument>> = BTreeMap::new();
+    for document in goal_documents {
+        let Some(umbrella) = &document.metadata.umbrella else {
+            continue;
+        };
+        if umbrella == document.slug() {
+            anyhow::bail!("`{}` cannot be its own umbrella", document.path.display());
+        }
+        if !goal_documents.iter().any(|d| d.slug() == umbrella) {
+            anyhow::bail!(
+                "`{}`: umbrella goal `{umbrella}` not found",
+                document.path.display()
+            );
+        }
+        children
+            .entry(umbrella.as_str())
+            .or_default()
+            .push(document);
+    }
+    Ok(children)
+}
+
+/// A checkbox per goal under an umbrella, linking to its tracking issue and showing how many
+/// of its tasks are done. Children get their tracking issues before the umbrella can link
+/// to them; until then, the umbrella body is regenerated on each pass.
+fn child_items(children: &[&GoalDocument]) -> Vec<String> {
+    children
+        .iter()
+        .map(|child| {
+            let counts = TaskCounts::of(child);
+            let TaskCounts {
+                completed, total, ..
+            } = counts;
+            let title = &child.metadata.title;
+            let title = match &child.metadata.tracking_issue {
+                Some(issue_id) => format!("[{title}]({})", issue_id.url()),
+                None => format!("{title} (no tracking issue yet)"),
+            };
+            format!(
+                "* {box} {title} ({completed}/{total} tasks{stretch}{blocked})",
+                box = if total > 0 && completed == total { "[x]" } else { "[ ]" },
+                stretch = counts.stretch_suffix(),
+                blocked = counts.blocked_suffix(),
+            )
+        })
+        .collect()
+}
+
+/// Checkbox items for the tasks of `goal_plan`, under a header for its subgoal (if any).
+/// If `collapse` is set, a subgoal's tasks are instead put in a `<details>` block whose
+/// summary shows how many of them are complete. Stretch goals are marked as such, and
+/// counted separately. Team asks are left to [`ask_items`].
+fn task_items(goal_plan: &GoalPlan, collapse: bool) -> anyhow::Result<Vec<String>> {
+    use std::fmt::Write;
+
+    let mut tasks = vec![];
+    if goal_plan.plan_items.iter().all(|item| item.is_team_ask()) {
+        return Ok(tasks);
+    }
+
+    let collapsed = match &goal_plan.subgoal {
+        Some(title) if collapse => {
+            let counts = TaskCounts::of_items(&goal_plan.plan_items);
+            tasks.push(format!(
+                "<details><summary>{} ({}/{}{}{})</summary>\n",
+                **title,
+                counts.completed,
+                counts.total,
+                counts.stretch_suffix(),
+                counts.blocked_suffix(),
+            ));
+            true
+        }
+        Some(title) => {
+            tasks.push(format!("### {}", **title));
+            false
+        }
+        None => false,
+    };
+
+    for plan_item in goal_plan
+        .plan_items
+        .iter()
+        .filter(|item| !item.is_team_ask())
+    {
+        let mut description = format!(
+            "* {box} {text}",
+            box = if plan_item.is_complete() { "[x]" } else { "[ ]" },
+            text = plan_item.text
+        );
+
+        if plan_item.stretch {
+            write!(description, " *(stretch)*")?;
+        }
+
+        if let Some(ParsedOwners::Usernames(usernames)) = plan_item.parse_owners()? {
+            write!(description, " ({})", usernames.join(", "))?;
+        }
+
+        if let Some(effort) = &plan_item.effort {
+            write!(description, " ({effort})")?;
+        }
+
+        if plan_item.is_blocked() {
+            match plan_item.blocked.as_deref() {
+                Some("") | None => write!(description, " 🚫 **blocked**")?,
+                Some(reason) => write!(description, " 🚫 **blocked:** {reason}")?,
+            }
+        }
+
+        tasks.push(description);
+    }
+
+    if collapsed {
+        tasks.push("\n</details>\n".to_string());
+    }
+
+    Ok(tasks)
+}
+
+/// Checkbox items for the team asks of `document`, apart from its tasks, so that the tracking
+/// issue shows what is asked of each team's time. Asks in a subgoal are prefixed with its title.
+fn ask_items(document: &GoalDocument) -> Vec<String> {
+    document
+        .team_asks
+        .iter()
+        .map(|ask| {
+            let teams: Vec<String> = ask.teams.iter().map(|team| team.name_and_link()).collect();
+            let subgoal = match ask.goal_titles.get(1) {
+                Some(subgoal) => format!("{}: ", **subgoal),
+                None => String::new(),
+            };
+            format!(
+                "* {box} {subgoal}{description} ({teams} ![Team][])",
+                box = if ask.is_complete { "[x]" } else { "[ ]" },
+                description = ask.ask_description,
+                teams = teams.join(", "),
+            )
+        })
+        .collect()
+}
+
+/// The labels declared by `goal_documents` in their `Labels` row.
+pub fn extra_labels_in(goal_documents: &[GoalDocument]) -> BTreeSet<String> {
+    goal_documents
+        .iter()
+        .flat_map(|goal_document| goal_document.metadata.extra_labels.iter().cloned())
+        .collect()
+}
+
+/// Teams that get a `T-<team>` label on the tracking issues: those with asks and those owning a goal.
+pub fn labelled_teams_in(
+    goal_documents: &[GoalDocument],
+) -> anyhow::Result<BTreeSet<&'static TeamName>> {
+    let mut teams = teams_with_asks(goal_documents);
+    for goal_document in goal_documents {
+        teams.extend(goal_document.metadata.owner_teams()?);
+    }
+    Ok(teams)
+}
+
+pub fn teams_with_asks(goal_documents: &[GoalDocument]) -> BTreeSet<&'static TeamName> {
+    goal_documents
+        .iter()
+        .flat_map(|g| &g.team_asks)
+        .flat_map(|ask| &ask.teams)
+        .copied()
+        .collect()
+}
+
+impl Display for GithubAction<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_action())
+    }
+}
+
+/// The number of the open issue titled `title` in `milestone` (or, if the issue is to be
+/// created without a milestone, with the period label among `labels`; see [`PeriodBy`]).
+fn find_issue(
+    github: &dyn GithubBackend,
+    repository: &Repository,
+    title: &str,
+    labels: &[String],
+    milestone: &str,
+) -> anyhow::Result<Option<u64>> {
+    let issues = if !milestone.is_empty() {
+        github.list_issues_in_milestone(repository, milestone)?
+    } else if let Some(period) = labels.iter().find(|label| re::TIMEFRAME.is_match(label)) {
+        github.list_issues_with_label(repository, period)?
+    } else {
+        return Ok(None);
+    };
+    Ok(issues
+        .iter()
+        .find(|issue| same_title(&issue.title, title) && issue.state == GithubIssueState::Open)
+        .map(|issue| issue.number))
+}
+
+impl GithubAction<'_> {
+    /// Every [`kind`](GithubAction::kind) of action.
+    pub const KINDS: &'static [&'static str] = &[
+        "create_milestone",
+        "set_milestone_due",
+        "create_label",
+        "update_label_color",
+        "create_issue",
+        "reopen_issue",
+        "change_title",
+        "change_milestone",
+        "add_label",
+        "set_issue_type",
+        "comment",
+        "update_comment",
+        "delete_comment",
+        "update_issue_body",
+        "sync_assignees",
+        "lock_issue",
+        "link_to_tracking_issue",
+        "sync_owners_from_issue",
+        "retitle_goal",
+        "close_issue",
+        "add_to_project",
+    ];
+
+    /// The kind of the action in snake case (e.g., `change_title`), as in its JSON form.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            GithubAction::CreateMilestone { .. } => "create_milestone",
+            GithubAction::SetMilestoneDue { .. } => "set_milestone_due",
+            GithubAction::CreateLabel { .. } => "create_label",
+            GithubAction::UpdateLabelColor { .. } => "update_label_color",
+            GithubAction::CreateIssue { .. } => "create_issue",
+            GithubAction::ReopenIssue { .. } => "reopen_issue",
+            GithubAction::ChangeTitle { .. } => "change_title",
+            GithubAction::ChangeMilestone { .. } => "change_milestone",
+            GithubAction::AddLabel { .. } => "add_label",
+            GithubAction::SetIssueType { .. } => "set_issue_type",
+            GithubAction::Comment { .. } => "comment",
+            GithubAction::UpdateComment { .. } => "update_comment",
+            GithubAction::DeleteComment { .. } => "delete_comment",
+            GithubAction::UpdateIssueBody { .. } => "update_issue_body",
+            GithubAction::SyncAssignees { .. } => "sync_assignees",
+            GithubAction::LockIssue { .. } => "lock_issue",
+            GithubAction::LinkToTrackingIssue { .. } => "link_to_tracking_issue",
+            GithubAction::SyncOwnersFromIssue { .. } => "sync_owners_from_issue",
+            GithubAction::RetitleGoal { .. } => "retitle_goal",
+            GithubAction::CloseIssue { .. } => "close_issue",
+            GithubAction::AddToProject { .. } => "add_to_project",
+        }
+    }
+
+    /// The number of the issue this action modifies, if any.
+    pub fn issue_number(&self) -> Option<u64> {
+        match self {
+            GithubAction::CreateMilestone { .. }
+            | GithubAction::SetMilestoneDue { .. }
+            | GithubAction::CreateLabel { .. }
+            | GithubAction::UpdateLabelColor { .. }
+            | GithubAction::CreateIssue { .. } => None,
+            GithubAction::ReopenIssue { number }
+            | GithubAction::ChangeTitle { number, .. }
+            | GithubAction::ChangeMilestone { number, .. }
+            | GithubAction::AddLabel { number, .. }
+            | GithubAction::SetIssueType { number, .. }
+            | GithubAction::Comment { number, .. }
+            | GithubAction::UpdateComment { number, .. }
+            | GithubAction::DeleteComment { number, .. }
+            | GithubAction::UpdateIssueBody { number, .. }
+            | GithubAction::SyncAssignees { number, .. }
+            | GithubAction::SyncOwnersFromIssue { number, .. }
+            | GithubAction::RetitleGoal { number, .. }
+            | GithubAction::LockIssue { number }
+            | GithubAction::CloseIssue { number }
+            | GithubAction::AddToProject { number, .. } => Some(*number),
+            GithubAction::LinkToTrackingIssue { issue_id, .. } => Some(issue_id.number),
+        }
+    }
+
+    /// The action as JSON, for `--output-actions-file` and `export-matrix`.
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::to_value(self.to_action()).expect("actions can be serialized")
+    }
+
+    /// The action as owned data, which no longer refers to the goal documents.
+    pub fn to_action(&self) -> Action {
+        match self {
+            GithubAction::CreateMilestone { title } => Action::CreateMilestone {
+                title: title.clone(),
+            },
+            GithubAction::SetMilestoneDue {
+                title,
+                due_on,
+                previous,
+            } => Action::SetMilestoneDue {
+                title: title.clone(),
+                due_on: due_on.clone(),
+                previous: previous.clone(),
+            },
+            GithubAction::CreateLabel { label } => Action::CreateLabel {
+                label: label.clone(),
+            },
+            GithubAction::UpdateLabelColor { label, old_color } => Action::UpdateLabelColor {
+                label: label.clone(),
+                old_color: old_color.clone(),
+            },
+            GithubAction::CreateIssue { issue } => Action::CreateIssue {
+                title: issue.title.clone(),
+                assignees: issue.assignees.clone(),
+                labels: issue.labels.clone(),
+                milestone: issue.milestone.clone(),
+                body: issue.body.clone(),
+                issue_type: issue.issue_type.clone(),
+            },
+            GithubAction::ReopenIssue { number } => Action::ReopenIssue { number: *number },
+            GithubAction::ChangeTitle { number, title } => Action::ChangeTitle {
+                number: *number,
+                title: title.clone(),
+            },
+            GithubAction::ChangeMilestone {
+                number,
+                milestone,
+                period_by,
+                previous,
+                comment,
+            } => Action::ChangeMilestone {
+                number: *number,
+                milestone: milestone.clone(),
+                period_by: *period_by,
+                previous: previous.clone(),
+                comment: comment.clone(),
+            },
+            GithubAction::SetIssueType { number, issue_type } => Action::SetIssueType {
+                number: *number,
+                issue_type: issue_type.clone(),
+            },
+            GithubAction::AddLabel { number, label } => Action::AddLabel {
+                number: *number,
+                label: label.clone(),
+            },
+            GithubAction::Comment { number, body } => Action::Comment {
+                number: *number,
+                body: body.clone(),
+            },
+            GithubAction::UpdateComment {
+                number,
+                comment,
+                body,
+            } => Action::UpdateComment {
+                number: *number,
+                url: comment.url.clone(),
+                body: body.clone(),
+            },
+            GithubAction::DeleteComment { number, comment } => Action::DeleteComment {
+                number: *number,
+                url: comment.url.clone(),
+            },
+            GithubAction::UpdateIssueBody {
+                number,
+                body,
+                last_synced,
+            } => Action::UpdateIssueBody {
+                number: *number,
+                body: body.clone(),
+                last_synced: last_synced.clone(),
+            },
+            GithubAction::SyncAssignees {
+                number,
+                remove_owners,
+                add_owners,
+            } => Action::SyncAssignees {
+                number: *number,
+                remove_owners: remove_owners.clone(),
+                add_owners: add_owners.clone(),
+            },
+            GithubAction::LockIssue { number } => Action::LockIssue { number: *number },
+            GithubAction::LinkToTrackingIssue {
+                goal_document,
+                issue_id,
+            } => Action::LinkToTrackingIssue {
+                goal_document: goal_document.path.clone(),
+                issue: issue_id.to_string(),
+            },
+            GithubAction::SyncOwnersFromIssue {
+                goal_document,
+                number,
+                remove_owners,
+                add_owners,
+            } => Action::SyncOwnersFromIssue {
+                goal_document: goal_document.path.clone(),
+                number: *number,
+                remove_owners: remove_owners.clone(),
+                add_owners: add_owners.clone(),
+            },
+            GithubAction::RetitleGoal {
+                goal_document,
+                number,
+                title,
+            } => Action::RetitleGoal {
+                goal_document: goal_document.path.clone(),
+                number: *number,
+                title: title.clone(),
+            },
+            GithubAction::CloseIssue { number } => Action::CloseIssue { number: *number },
+            GithubAction::AddToProject {
+                number,
+                project,
+                status,
+            } => Action::AddToProject {
+                number: *number,
+                project: *project,
+                status: status.clone(),
+            },
+        }
+    }
+
+    /// The action to execute for `action`. Actions that refer to a goal document (creating
+    /// an issue, or editing the document) cannot be turned back into one.
+    pub fn from_action(action: Action) -> anyhow::Result<GithubAction<'static>> {
+        Ok(match action {
+            Action::CreateIssue { .. }
+            | Action::LinkToTrackingIssue { .. }
+            | Action::SyncOwnersFromIssue { .. }
+            | Action::RetitleGoal { .. } => {
+                anyhow::bail!(
+                    "`{action}`: the action refers to a goal document and cannot be read back"
+                )
+            }
+            Action::CreateMilestone { title } => GithubAction::CreateMilestone { title },
+            Action::SetMilestoneDue {
+                title,
+                due_on,
+                previous,
+            } => GithubAction::SetMilestoneDue {
+                title,
+                due_on,
+                previous,
+            },
+            Action::CreateLabel { label } => GithubAction::CreateLabel { label },
+            Action::UpdateLabelColor { label, old_color } => {
+                GithubAction::UpdateLabelColor { label, old_color }
+            }
+            Action::ReopenIssue { number } => GithubAction::ReopenIssue { number },
+            Action::ChangeTitle { number, title } => GithubAction::ChangeTitle { number, title },
+            Action::ChangeMilestone {
+                number,
+                milestone,
+                period_by,
+                previous,
+                comment,
+            } => GithubAction::ChangeMilestone {
+                number,
+                milestone,
+                period_by,
+                previous,
+                comment,
+            },
+            Action::AddLabel { number, label } => GithubAction::AddLabel { number, label },
+            Action::SetIssueType { number, issue_type } => {
+                GithubAction::SetIssueType { number, issue_type }
+            }
+            Action::Comment { number, body } => GithubAction::Comment { number, body },
+            // Updating or deleting a comment only needs its url.
+            Action::UpdateComment { number, url, body } => GithubAction::UpdateComment {
+                number,
+                comment: ExistingGithubComment {
+                    author: String::new(),
+                    body: String::new(),
+                    created_at: String::new(),
+                    url,
+                },
+                body,
+            },
+            Action::DeleteComment { number, url } => GithubAction::DeleteComment {
+                number,
+                comment: ExistingGithubComment {
+                    author: String::new(),
+                    body: String::new(),
+                    created_at: String::new(),
+                    url,
+                },
+            },
+            Action::UpdateIssueBody {
+                number,
+                body,
+                last_synced,
+            } => GithubAction::UpdateIssueBody {
+                number,
+                body,
+                last_synced,
+            },
+            Action::SyncAssignees {
+                number,
+                remove_owners,
+                add_owners,
+            } => GithubAction::SyncAssignees {
+                number,
+                remove_owners,
+                add_owners,
+            },
+            Action::LockIssue { number } => GithubAction::LockIssue { number },
+            Action::CloseIssue { number } => GithubAction::CloseIssue { number },
+            Action::AddToProject {
+                number,
+                project,
+                status,
+            } => GithubAction::AddToProject {
+                number,
+                project,
+                status,
+            },
+        })
+    }
+
+    pub fn execute(
+        self,
+        github: &dyn GithubBackend,
+        repository: &Repository,
+    ) -> anyhow::Result<()> {
+        match self {
+            GithubAction::CreateMilestone { title } => {
+                github.create_milestone(repository, &title)?;
+                Ok(())
+            }
+
+            GithubAction::SetMilestoneDue {
+                title,
+                due_on,
+                previous: _,
+            } => {
+                github.set_milestone_due(repository, &title, &due_on)?;
+                Ok(())
+            }
+
+            GithubAction::CreateLabel { label } => {
+                github.create_label(repository, &label)?;
+                Ok(())
+            }
+
+            GithubAction::UpdateLabelColor {
+                label,
+                old_color: _,
+            } => {
+                github.update_label_color(repository, &label)?;
+                Ok(())
+            }
+
+            GithubAction::CreateIssue {
+                issue:
+                    GithubIssue {
+                        title,
+                        assignees,
+                        body,
+                        labels,
+                        milestone,
+                        issue_type: _,
+                        content_hash: _,
+                        tasks_comment: _,
+                        tracking_issue: _,
+                        goal_document: _,
+                    },
+            } => {
+                // If an earlier attempt timed out after github created the issue, the list of
+                // issues we planned against is stale: check again so as not to create a duplicate.
+                if let Some(number) = find_issue(github, repository, &title, &labels, &milestone)? {
+                    eprintln!(
+                        "issue #{number} titled {title:?} already exists, not creating another one"
+                    );
+                    return Ok(());
+                }
+
+                let result =
+                    github.create_issue(repository, &body, &title, &labels, &assignees, &milestone);
+                if let Err(e) = result {
+                    // The issue may have been created after all. If not, report the error and
+                    // leave it to the next pass to plan the issue again.
+                    if find_issue(github, repository, &title, &labels, &milestone)?.is_none() {
+                        return Err(e);
+                    }
+                }
+
+                // Note: the issue is not locked and has no tasks comment yet, but we will
+                // reloop around later.
+
+                Ok(())
+            }
+
+            GithubAction::ChangeMilestone {
+                number,
+                milestone,
+                period_by,
+                previous,
+                comment,
+            } => {
+                let moved = match period_by {
+                    PeriodBy::Milestone => github.change_milestone(repository, number, &milestone),
+                    PeriodBy::Label => {
+                        github
+                            .add_label(repository, number, &milestone)
+                            .and_then(|()| {
+                                previous
+                                    .iter()
+                                    .try_for_each(|p| github.remove_label(repository, number, p))
+                            })
+                    }
+                };
+                moved.map_err(|e| {
+                    anyhow::anyhow!("{e:#} (so the continuation comment was not posted)")
+                })?;
+                if let Some(comment) = comment {
+                    github.create_comment(repository, number, &comment)?;
+                }
+                Ok(())
+            }
+
+            GithubAction::ChangeTitle { number, title } => {
+                github.change_title(repository, number, &title)?;
+                Ok(())
+            }
+
+            GithubAction::AddLabel { number, label } => {
+                github.add_label(repository, number, &label)?;
+                Ok(())
+            }
+
+            GithubAction::Comment { number, body } => {
+                github.create_comment(repository, number, &body)?;
+                Ok(())
+            }
+
+            GithubAction::UpdateIssueBody {
+                number,
+                body,
+                last_synced: _,
+            } => {
+                github.update_issue_body(repository, number, &body)?;
+                Ok(())
+            }
+
+            GithubAction::SyncAssignees {
+                number,
+                remove_owners,
+                add_owners,
+            } => {
+                // NOTE: Swallow errors here because sometimes people are not present in the org.
+                // We don't want to stop everything for that.
+                github.sync_assignees(repository, number, &remove_owners, &add_owners)?;
+                Ok(())
+            }
+
+            GithubAction::LockIssue { number } => github.lock_issue(repository, number),
+            GithubAction::SetIssueType { number, issue_type } => {
+                github.set_issue_type(repository, number, &issue_type)
+            }
+
+            GithubAction::LinkToTrackingIssue {
+                goal_document,
+                issue_id: number,
+            } => goal_document.link_issue(number),
+
+            GithubAction::SyncOwnersFromIssue {
+                goal_document,
+                number: _,
+                remove_owners,
+                add_owners,
+            } => goal_document.sync_owners(&remove_owners, &add_owners),
+
+            GithubAction::RetitleGoal {
+                goal_document,
+                number: _,
+                title,
+            } => goal_document.retitle(&title),
+
+            GithubAction::CloseIssue { number } => github.close_issue(repository, number),
+            GithubAction::AddToProject {
+                number,
+                project,
+                status,
+            } => github.add_to_project(repository, project, number, status.as_deref()),
+            GithubAction::UpdateComment {
+                number: _,
+                comment,
+                body,
+            } => github.update_comment(repository, &comment, &body),
+            GithubAction::DeleteComment { number: _, comment } => {
+                github.delete_comment(repository, &comment)
+            }
+            GithubAction::ReopenIssue { number } => github.reopen_issue(repository, number),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use crate::{
+        gh::{
+            backend::MockGithub,
+            issues::{CONTINUING_GOAL_PREFIX, LOCK_TEXT},
+        },
+        goal::{test_support::GoalText, GoalDocument},
+    };
+
+    use super::*;
+
+    /// A goal whose owners do all the work themselves, so there are no `![Team]` rows.
+    const GOAL_WITHOUT_ASKS: &str = "\
+# Do it ourselves
+
+| Metadata         |                          |
+|:-----------------|--------------------------|
+| Point of contact | @owner                   |
+| Teams            | <!-- TEAMS WITH ASKS --> |
+| Task owners      | <!-- TASK OWNERS -->     |
+| Status           | Proposed                 |
+
+## Summary
+
+We can do everything ourselves.
+
+## Ownership and team asks
+
+| Task           | Owner(s) or team(s) | Notes |
+| -------------- | ------------------- | ----- |
+| Implementation | @owner              |       |
+";
+
+    /// The goal document `text`, as if it were `src/2025h1/ourselves.md`.
+    fn ourselves(text: &str) -> GoalDocument {
+        GoalDocument::from_text(
+            Path::new("src/2025h1/ourselves.md"),
+            Path::new("ourselves.md"),
+            text,
+        )
+        .unwrap()
+        .unwrap()
+    }
+
+    fn goal_without_asks() -> GoalDocument {
+        ourselves(GOAL_WITHOUT_ASKS)
+    }
+
+    #[test]
+    fn goal_without_asks_loads() {
+        let document = goal_without_asks();
+        assert!(document.team_asks.is_empty());
+        assert!(document.teams_with_asks().is_empty());
+        assert!(teams_with_asks(std::slice::from_ref(&document)).is_empty());
+    }
+
+    #[test]
+    fn owner_lookup_failures_fall_back_or_are_collected() {
+        let text = GOAL_WITHOUT_ASKS.replace(
+            "| Status           | Proposed                 |\n",
+            "| Status           | Proposed                 |\n\
+             | Additional owners | @Other, @unknown        |\n",
+        );
+        let document = ourselves(&text);
+        let lookup = |username: &str| match username {
+            "@owner" => Ok(Some("Owner".to_string())),
+            "@unknown" => Ok(None),
+            _ => Err(anyhow::anyhow!("team data unavailable")),
+        };
+
+        let mut failures = vec![];
+        let logins = owner_logins(&document, lookup, false, &mut failures);
+        assert_eq!(logins, ["Owner", "Other"]);
+        assert!(failures.is_empty());
+
+        let logins = owner_logins(&document, lookup, true, &mut failures);
+        assert_eq!(logins, ["Owner"]);
+        assert_eq!(
+            failures,
+            ["`src/2025h1/ourselves.md`: looking up owner `@Other` failed: team data unavailable"]
+        );
+    }
+
+    #[test]
+    fn goal_without_tasks_is_flagged() {
+        assert!(!goal_without_asks().lacks_tasks());
+
+        let text =
+            GOAL_WITHOUT_ASKS.replace("| Implementation | @owner              |       |\n", "");
+        let document = ourselves(&text);
+        assert!(document.lacks_tasks());
+
+        let text = text.replace(
+            "| Status           | Proposed                 |",
+            "| Status           | Proposed                 |\n| Tasks intentionally empty | true |",
+        );
+        let document = ourselves(&text);
+        assert!(!document.lacks_tasks());
+    }
+
+    #[test]
+    fn umbrella_issue_lists_its_children() {
+        let child_text = GOAL_WITHOUT_ASKS
+            .replace("# Do it ourselves", "# One part")
+            .replace(
+                "| Status           | Proposed                 |",
+                "| Status           | Proposed                 |\n\
+                 | Umbrella | ourselves |\n\
+                 | Tracking issue | [rust-lang/rust-project-goals#7] |",
+            );
+        let child = GoalDocument::from_text(
+            Path::new("src/2025h1/one-part.md"),
+            Path::new("one-part.md"),
+            &child_text,
+        )
+        .unwrap()
+        .unwrap();
+        let documents = vec![goal_without_asks(), child];
+
+        let children = umbrella_children(&documents).unwrap();
+        assert_eq!(Vec::from_iter(children.keys()), [&"ourselves"]);
+
+        let text = issue_text("2025h1", &documents[0], &children["ourselves"], &[], None).unwrap();
+        assert!(
+            text.contains(
+                "## Goals under this umbrella\n\n\
+                 * [ ] [One part](https://github.com/rust-lang/rust-project-goals/issues/7) (0/1 tasks)\n"
+            ),
+            "{text}"
+        );
+        let text = issue_text("2025h1", &documents[1], &[], &[], None).unwrap();
+        assert!(!text.contains("umbrella"), "{text}");
+
+        let error = umbrella_children(&documents[1..]).unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("umbrella goal `ourselves` not found"));
+    }
+
+    #[test]
+    fn timeframe_is_found_in_nested_paths() {
+        assert_eq!(timeframe_in_path(Path::new("src/2024h2")), Some("2024h2"));
+        assert_eq!(
+            timeframe_in_path(Path::new("src/2024h2/flagship")),
+            Some("2024h2")
+        );
+        assert_eq!(timeframe_in_path(Path::new("src/flagship")), None);
+        assert_eq!(timeframe_in_path(Path::new("src/2024h3")), None);
+    }
+
+    #[test]
+    fn goal_can_opt_out_of_tracking_issue() {
+        assert!(goal_without_asks().wants_tracking_issue());
+
+        let text = GOAL_WITHOUT_ASKS.replace(
+            "| Status           | Proposed                 |",
+            "| Status           | Proposed                 |\n| No tracking issue | true |",
+        );
+        let document = ourselves(&text);
+        assert!(document.metadata.no_tracking_issue);
+        assert!(!document.wants_tracking_issue());
+    }
+
+    fn label_options(label_prefix: &str) -> LabelOptions {
+        LabelOptions {
+            team_label_color: TEAM_LABEL_COLOR.to_string(),
+            label_prefix: label_prefix.to_string(),
+            tracking_label: TRACKING_ISSUE_LABEL.to_string(),
+        }
+    }
+
+    #[test]
+    fn goal_without_asks_gets_tracking_label() {
+        let document = goal_without_asks();
+        assert_eq!(
+            issue_labels(&document, &label_options("")).unwrap(),
+            vec![TRACKING_ISSUE_LABEL.to_string()]
+        );
+    }
+
+    #[test]
+    fn label_prefix_applies_to_issue_labels() {
+        let document = goal_without_asks();
+        assert_eq!(
+            issue_labels(&document, &label_options("goals/")).unwrap(),
+            vec![format!("goals/{TRACKING_ISSUE_LABEL}")]
+        );
+    }
+
+    #[test]
+    fn tracking_label_can_be_renamed() {
+        let document = goal_without_asks();
+        let label_options = LabelOptions {
+            tracking_label: "type: tracking".to_string(),
+            ..label_options("goals/")
+        };
+        assert_eq!(
+            issue_labels(&document, &label_options).unwrap(),
+            vec!["goals/type: tracking".to_string()]
+        );
+    }
+
+    #[test]
+    fn generated_issues_are_stable() {
+        let goal = |labels: &str| {
+            let text = GOAL_WITHOUT_ASKS.replace(
+                "| Status           | Proposed                 |\n",
+                &format!(
+                    "| Status           | Proposed                 |\n| Labels | {labels} |\n"
+                ),
+            );
+            ourselves(&text)
+        };
+        let (a, b) = (
+            goal("WG-async, A-async"),
+            goal("A-async, WG-async, A-async"),
+        );
+
+        let body = |document| issue_text("2025h1", document, &[], &[], None).unwrap();
+        assert_eq!(body(&a), body(&a));
+        assert_eq!(body(&a), body(&b));
+        assert_eq!(content_hash(&body(&a)), content_hash(&body(&b)));
+
+        let labels = |document| issue_labels(document, &label_options("")).unwrap();
+        assert_eq!(labels(&a), ["A-async", "C-tracking-issue", "WG-async"]);
+        assert_eq!(labels(&a), labels(&b));
+    }
+
+    #[test]
+    fn goal_without_asks_renders_no_teams() {
+        let document = goal_without_asks();
+        let text = issue_text("2025h1", &document, &[], &[], None).unwrap();
+        assert!(text.contains("| Team(s)          | None |"), "{text}");
+        assert!(text.contains("@owner"), "{text}");
+        assert!(!text.contains("## Asks"), "{text}");
+    }
+
+    #[test]
+    fn default_owner_stands_in_for_missing_owners() {
+        let document = goal_without_asks();
+        let owners = ["owner".to_string()];
+        assert_eq!(fallback_owner(&document, &owners, Some("steward")), None);
+        assert_eq!(fallback_owner(&document, &[], None), None);
+        assert_eq!(
+            fallback_owner(&document, &[], Some("steward")),
+            Some("steward")
+        );
+
+        let text = issue_text("2025h1", &document, &[], &[], Some("steward")).unwrap();
+        assert!(
+            text.contains("**@owner** (default owner: **@steward**)"),
+            "{text}"
+        );
+    }
+
+    #[test]
+    fn label_colors() {
+        assert_eq!(parse_label_color("bfd4f2"), Ok("bfd4f2".to_string()));
+        assert_eq!(parse_label_color("#5319E7"), Ok("5319e7".to_string()));
+        assert!(parse_label_color("fff").is_err());
+        assert!(parse_label_color("bfd4fg").is_err());
+        assert!(parse_label_color("##bfd4f2").is_err());
+    }
+
+    /// A tracking issue for `document` as `issue` would generate it, without looking up the owners.
+    fn desired_issue<'doc>(document: &'doc GoalDocument, content: &str) -> GithubIssue<'doc> {
+        let content_hash = content_hash(content);
+        GithubIssue {
+            title: document.metadata.title.clone(),
+            assignees: BTreeSet::new(),
+            body: with_sync_marker(
+                "2025h1",
+                document.slug(),
+                &document.metadata.title,
+                &content_hash,
+                content.to_string(),
+            ),
+            labels: issue_labels(document, &label_options("")).unwrap(),
+            milestone: "2025h1".to_string(),
+            issue_type: None,
+            content_hash,
+            tasks_comment: None,
+            tracking_issue: None,
+            goal_document: document,
+        }
+    }
+
+    fn existing_issue(body: &str) -> ExistingGithubIssue {
+        ExistingGithubIssue {
+            number: 22,
+            title: "Do it ourselves".to_string(),
+            assignees: BTreeSet::new(),
+            comments: vec![],
+            body: body.to_string(),
+            state: GithubIssueState::Open,
+            labels: vec![],
+            milestone: Some(crate::gh::milestone::GhMilestone {
+                number: 1,
+                title: "2025h1".to_string(),
+                description: String::new(),
+                due_on: None,
+            }),
+        }
+    }
+
+    #[test]
+    fn titles_follow_the_side_that_changed() {
+        let document = goal_without_asks();
+        let mut desired = desired_issue(&document, "content");
+        let mut issue = existing_issue(&desired.body);
+        issue.title = "Renamed on github".to_string();
+        assert_eq!(
+            plan_title_from_issue(&desired, &issue),
+            Some(GithubAction::RetitleGoal {
+                goal_document: &document,
+                number: 22,
+                title: "Renamed on github".to_string(),
+            })
+        );
+
+        issue.title = desired.title.clone();
+        desired.title = "Renamed in markdown".to_string();
+        assert_eq!(
+            plan_title_from_issue(&desired, &issue),
+            Some(GithubAction::ChangeTitle {
+                number: 22,
+                title: "Renamed in markdown".to_string(),
+            })
+        );
+
+        // Both sides changed, or we cannot tell which one did.
+        issue.title = "Renamed on github".to_string();
+        assert_eq!(plan_title_from_issue(&desired, &issue), None);
+        issue.body = strip_sync_marker(&desired.body).0.to_string();
+        assert_eq!(plan_title_from_issue(&desired, &issue), None);
+
+        let body = with_synced_title(&desired.body, "Renamed on github");
+        assert_eq!(goal_marker(&body), Some("ourselves"));
+        assert_eq!(
+            synced_title_hash(&body),
+            Some(title_hash("Renamed on github").as_str())
+        );
+    }
+
+    #[test]
+    fn owners_follow_the_issue_except_for_the_point_of_contact() {
+        let text = GOAL_WITHOUT_ASKS.replace(
+            "| Status ",
+            "| Additional owners | @helper, @leaver |\n| Status ",
+        );
+        let document = ourselves(&text);
+        let logins = |logins: &[&str]| -> BTreeSet<String> {
+            logins.iter().map(|l| l.to_string()).collect()
+        };
+        let assignees = logins(&["owner", "helper", "leaver"]);
+
+        let mut issue = existing_issue("");
+        issue.assignees = assignees.clone();
+        assert_eq!(plan_owners_from_issue(&document, &issue), None);
+
+        issue.assignees = logins(&["helper", "newcomer"]);
+        assert_eq!(
+            plan_owners_from_issue(&document, &issue),
+            Some(GithubAction::SyncOwnersFromIssue {
+                goal_document: &document,
+                number: 22,
+                remove_owners: logins(&["leaver"]),
+                add_owners: logins(&["newcomer"]),
+            })
+        );
+    }
+
+    #[test]
+    fn owners_without_a_login_are_not_added_twice() {
+        let document = GoalText::new("Ourselves")
+            .row("Additional owners", "@Stranger")
+            .document("src/2025h1/ourselves.md");
+
+        // `@Stranger` is not in the team data, so we do not assign them...
+        let lookup = |username: &str| match username {
+            "@owner" => Ok(Some("owner".to_string())),
+            _ => Ok(None),
+        };
+        assert_eq!(
+            owner_logins(&document, lookup, true, &mut vec![]),
+            ["owner"]
+        );
+
+        // ...but someone did on github.
+        let mut issue = existing_issue("");
+        issue.assignees = BTreeSet::from(["owner".to_string(), "stranger".to_string()]);
+        assert_eq!(plan_owners_from_issue(&document, &issue), None);
+
+        issue.assignees = BTreeSet::from(["owner".to_string()]);
+        assert_eq!(
+            plan_owners_from_issue(&document, &issue),
+            Some(GithubAction::SyncOwnersFromIssue {
+                goal_document: &document,
+                number: 22,
+                remove_owners: BTreeSet::from(["Stranger".to_string()]),
+                add_owners: BTreeSet::new(),
+            })
+        );
+    }
+
+    /// Plan the actions for `existing`, returning the new body if the plan updates it.
+    fn plan_body_update(document: &GoalDocument, content: &str, existing: &str) -> Option<String> {
+        plan_forced_body_update(document, content, existing, false)
+    }
+
+    #[test]
+    fn only_changed_bodies_are_regenerated() {
+        let document = goal_without_asks();
+        let issue_id = IssueId::new(repository(), 22);
+        let content = issue_text("2025h1", &document, &[], &[], None).unwrap();
+        let desired_issues = [GithubIssue {
+            tracking_issue: Some(&issue_id),
+            ..desired_issue(&document, &content)
+        }];
+        let github = MockGithub::new().with_issue(existing_issue("Original text"));
+        let regenerate = || {
+            plan_body_regeneration(
+                &github,
+                &repository(),
+                "2025h1",
+                PeriodBy::Milestone,
+                &desired_issues,
+            )
+            .unwrap()
+        };
+
+        let actions = regenerate();
+        let [GithubAction::UpdateIssueBody {
+            number: 22, body, ..
+        }] = &Vec::from_iter(actions)[..]
+        else {
+            panic!("expected a single body update");
+        };
+        assert!(body.starts_with(&content), "{body}");
+        assert!(
+            body.contains("<details>\nOriginal text\n</details>"),
+            "{body}"
+        );
+
+        github
+            .update_issue_body(&repository(), 22, &desired_issues[0].body)
+            .unwrap();
+        assert!(regenerate().is_empty());
+    }
+
+    fn plan_forced_body_update(
+        document: &GoalDocument,
+        content: &str,
+        existing: &str,
+        force: bool,
+    ) -> Option<String> {
+        let repository = Repository::new("rust-lang", "rust-project-goals");
+        plan_issue_actions(
+            &repository,
+            "2025h1",
+            desired_issue(document, content),
+            Some(existing_issue(existing)),
+            SyncOptions {
+                force_update_body: force,
+                ..SyncOptions::default()
+            },
+        )
+        .unwrap()
+        .into_iter()
+        .find_map(|action| match action {
+            GithubAction::UpdateIssueBody { body, .. } => Some(body),
+            _ => None,
+        })
+    }
+
+    #[test]
+    fn only_older_bot_comments_are_pruned() {
+        let comment = |id: u64, author: &str, body: &str| ExistingGithubComment {
+            author: author.to_string(),
+            body: body.to_string(),
+            created_at: "2025-01-01T00:00:00Z".to_string(),
+            url: format!(
+                "https://github.com/rust-lang/rust-project-goals/issues/22#issuecomment-{id}"
+            ),
+        };
+        let continuing = format!("{CONTINUING_GOAL_PREFIX}.");
+        let issue = ExistingGithubIssue {
+            comments: vec![
+                comment(1, "@goals-bot", LOCK_TEXT),
+                comment(2, "@goals-bot", &continuing),
+                comment(3, "@someone", LOCK_TEXT),
+                comment(4, "@goals-bot", "An update."),
+                comment(5, "@goals-bot", &continuing),
+                comment(6, "@goals-bot", LOCK_TEXT),
+                comment(7, "@someone", &continuing),
+            ],
+            ..existing_issue("")
+        };
+
+        let ids = |bot_account| {
+            outdated_comments(&issue, bot_account)
+                .iter()
+                .map(|comment| comment.id().unwrap())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(ids("goals-bot"), [1, 2]);
+        assert_eq!(ids("@goals-bot"), [1, 2]);
+        assert!(ids("other-bot").is_empty());
+
+        let action = GithubAction::DeleteComment {
+            number: 22,
+            comment: outdated_comments(&issue, "goals-bot")[0].clone(),
+        };
+        assert_eq!(action.to_string(), "delete comment 1 on issue #22");
+    }
+
+    #[test]
+    fn titles_are_compared_normalized() {
+        assert!(same_title("Caf\u{e9} goal", "Cafe\u{301} goal"));
+        assert!(same_title("\u{1F980} Rust goal ", "\u{1F980} Rust goal"));
+        assert!(same_title(" Ferris \u{1F980}\n", "Ferris \u{1F980}"));
+        assert!(!same_title("\u{1F980} Rust goal", "Rust goal"));
+        assert!(!same_title("Cafe goal", "Cafe\u{301} goal"));
+    }
+
+    #[test]
+    fn close_titles_are_fuzzy_match_candidates() {
+        assert_eq!(title_similarity("Do it ourselves", " do it ourselves"), 1.0);
+        assert_eq!(title_similarity("kitten", "sitting"), 1.0 - 3.0 / 7.0);
+        assert!(title_similarity("Do it ourselves", "Do it ourselves!") >= FUZZY_MATCH_THRESHOLD);
+        assert!(title_similarity("Do it ourselves", "Parallel frontend") < FUZZY_MATCH_THRESHOLD);
+
+        let issue = |number, title: &str| ExistingGithubIssue {
+            number,
+            title: title.to_string(),
+            ..existing_issue("")
+        };
+        let issues = [
+            issue(1, "Do it ourselves!"),
+            issue(2, "Parallel frontend"),
+            issue(3, "Do it yourselves"),
+            issue(4, "Do it ourselves (again)"),
+        ];
+        let candidates = |claimed: &[u64]| {
+            fuzzy_candidates(
+                "Do it ourselves",
+                &issues,
+                &claimed.iter().copied().collect(),
+            )
+            .iter()
+            .map(|(issue, _)| issue.number)
+            .collect::<Vec<_>>()
+        };
+        assert_eq!(candidates(&[]), [1, 3]);
+        assert_eq!(candidates(&[1]), [3]);
+    }
+
+    #[test]
+    fn actions_can_be_read_back_from_json() {
+        let actions = [
+            GithubAction::ChangeMilestone {
+                number: 1,
+                milestone: "2025h1".to_string(),
+                period_by: PeriodBy::Label,
+                previous: vec!["2024h2".to_string()],
+                comment: Some("Continuing".to_string()),
+            },
+            GithubAction::SyncAssignees {
+                number: 2,
+                remove_owners: BTreeSet::from(["old".to_string()]),
+                add_owners: BTreeSet::from(["new".to_string()]),
+            },
+            GithubAction::UpdateIssueBody {
+                number: 3,
+                body: "Body".to_string(),
+                last_synced: None,
+            },
+            GithubAction::AddToProject {
+                number: 4,
+                project: 5,
+                status: Some("Todo".to_string()),
+            },
+        ];
+        assert_eq!(actions[0].to_json()["period_by"], "label");
+        for action in actions {
+            assert_eq!(action.to_json()["kind"], action.kind());
+            let read_back: Action = serde_json::from_value(action.to_json()).unwrap();
+            assert_eq!(read_back.to_string(), action.to_string());
+            assert_eq!(GithubAction::from_action(read_back).unwrap(), action);
+        }
+
+        let document = goal_without_asks();
+        let link = GithubAction::LinkToTrackingIssue {
+            goal_document: &document,
+            issue_id: IssueId::new(repository(), 22),
+        };
+        assert_eq!(link.to_action().issue_number(), Some(22));
+        assert!(GithubAction::from_action(link.to_action()).is_err());
+    }
+
+    #[test]
+    fn renamed_goal_is_found_by_its_marker() {
+        let document = goal_without_asks();
+        let content = issue_text("2025h1", &document, &[], &[], None).unwrap();
+        let desired = desired_issue(&document, &content);
+        assert_eq!(strip_sync_marker(&desired.body).0, content);
+        assert_eq!(goal_marker(&desired.body), Some("ourselves"));
+
+        let issue = |number, title: &str, body: &str| ExistingGithubIssue {
+            number,
+            title: title.to_string(),
+            ..existing_issue(body)
+        };
+        let marked_for_other = desired.body.replace("rpg:goal=ourselves", "rpg:goal=other");
+        let mut issues = vec![
+            issue(1, "Do it ourselves", &marked_for_other),
+            issue(2, "Do it ourselves", "Unmarked"),
+            issue(3, "Do it all by ourselves", &desired.body),
+        ];
+        let found =
+            |issues: &[ExistingGithubIssue]| find_goal_issue(issues, &desired).map(|i| i.number);
+        assert_eq!(found(&issues), Some(3));
+
+        // Without a marked issue, we fall back to the title, skipping issues of other goals.
+        issues.pop();
+        assert_eq!(found(&issues), Some(2));
+        issues.pop();
+        assert_eq!(found(&issues), None);
+    }
+
+    #[test]
+    fn fuzzy_matches_are_only_reported() {
+        let document = goal_without_asks();
+        let content = issue_text("2025h1", &document, &[], &[], None).unwrap();
+        let github = MockGithub::new().with_issue(ExistingGithubIssue {
+            number: 1,
+            title: format!("{}!", document.metadata.title),
+            ..existing_issue(&content)
+        });
+        let actions = plan_issues(
+            &github,
+            &repository(),
+            "2025h1",
+            BTreeSet::from([desired_issue(&document, &content)]),
+            SyncOptions {
+                fuzzy_match: Some(FuzzyMatch::Report),
+                ..SyncOptions::default()
+            },
+            &mut |_| Ok(()),
+        )
+        .unwrap();
+        assert!(
+            actions
+                .iter()
+                .any(|action| matches!(action, GithubAction::CreateIssue { .. })),
+            "{actions:?}"
+        );
+        assert!(
+            !actions
+                .iter()
+                .any(|action| matches!(action, GithubAction::ChangeTitle { .. })),
+            "{actions:?}"
+        );
+    }
+
+    #[test]
+    fn renormalized_title_is_not_changed() {
+        let text = GOAL_WITHOUT_ASKS.replace("# Do it ourselves", "# Caf\u{e9} \u{1F980}");
+        let document = ourselves(&text);
+        let existing = ExistingGithubIssue {
+            title: "Cafe\u{301} \u{1F980} ".to_string(),
+            ..existing_issue("")
+        };
+        let actions = plan_issue_actions(
+            &repository(),
+            "2025h1",
+            desired_issue(&document, ""),
+            Some(existing),
+            SyncOptions::default(),
+        )
+        .unwrap();
+        assert!(
+            !actions
+                .iter()
+                .any(|action| matches!(action, GithubAction::ChangeTitle { .. })),
+            "{actions:?}"
+        );
+    }
+
+    #[test]
+    fn additive_assignees_are_never_removed() {
+        let document = goal_without_asks();
+        let owners = |logins: &[&str]| -> BTreeSet<String> {
+            logins.iter().map(|l| l.to_string()).collect()
+        };
+        let existing = ExistingGithubIssue {
+            assignees: owners(&["volunteer"]),
+            ..existing_issue(&desired_issue(&document, "").body)
+        };
+        let sync = |owners: BTreeSet<String>, assignees| {
+            let actions = plan_issue_actions(
+                &repository(),
+                "2025h1",
+                GithubIssue {
+                    assignees: owners,
+                    ..desired_issue(&document, "")
+                },
+                Some(existing.clone()),
+                SyncOptions {
+                    assignees,
+                    ..SyncOptions::default()
+                },
+            )
+            .unwrap();
+            actions
+                .into_iter()
+                .find(|action| matches!(action, GithubAction::SyncAssignees { .. }))
+        };
+
+        assert_eq!(
+            sync(owners(&["owner"]), AssigneeMode::Reconcile),
+            Some(GithubAction::SyncAssignees {
+                number: 22,
+                remove_owners: owners(&["volunteer"]),
+                add_owners: owners(&["owner"]),
+            })
+        );
+        assert_eq!(
+            sync(owners(&["owner"]), AssigneeMode::Additive),
+            Some(GithubAction::SyncAssignees {
+                number: 22,
+                remove_owners: BTreeSet::new(),
+                add_owners: owners(&["owner"]),
+            })
+        );
+
+        // Once the owners are assigned, extra assignees are left alone.
+        assert_eq!(sync(owners(&[]), AssigneeMode::Additive), None);
+    }
+
+    #[test]
+    fn closed_tracking_issue_of_accepted_goal_is_reopened() {
+        let plan_reopen = |status: &str, reopen_closed: bool| {
+            let text = GOAL_WITHOUT_ASKS.replace(
+                "| Status           | Proposed                 |",
+                &format!("| Status           | {status} |"),
+            );
+            let document = ourselves(&text);
+            let existing = ExistingGithubIssue {
+                state: GithubIssueState::Closed,
+                ..existing_issue("")
+            };
+            plan_issue_actions(
+                &repository(),
+                "2025h1",
+                desired_issue(&document, ""),
+                Some(existing),
+                SyncOptions {
+                    reopen_closed,
+                    ..SyncOptions::default()
+                },
+            )
+            .unwrap()
+            .contains(&GithubAction::ReopenIssue { number: 22 })
+        };
+
+        assert!(plan_reopen("Accepted", true));
+        assert!(!plan_reopen("Accepted", false));
+        assert!(!plan_reopen("Proposed", true));
+    }
+
+    #[test]
+    fn second_sync_does_not_update_body() {
+        let document = goal_without_asks();
+        let content = issue_text("2025h1", &document, &[], &[], None).unwrap();
+
+        // The first pass replaces the hand-written body, preserving it.
+        let body = plan_body_update(&document, &content, "Original text").unwrap();
+        assert!(
+            body.contains("<details>\nOriginal text\n</details>"),
+            "{body}"
+        );
+
+        // Once that body is on the issue, planning again is a no-op.
+        assert_eq!(plan_body_update(&document, &content, &body), None);
+
+        // If the goal changes, the body is regenerated without nesting the old generated text.
+        let changed = content.replace("We can do everything ourselves.", "We changed our mind.");
+        let updated = plan_body_update(&document, &changed, &body).unwrap();
+        assert!(updated.contains("We changed our mind."), "{updated}");
+        assert!(
+            !updated.contains("We can do everything ourselves."),
+            "{updated}"
+        );
+        assert_eq!(updated.matches("<details>").count(), 1, "{updated}");
+        assert_eq!(plan_body_update(&document, &changed, &updated), None);
+    }
+
+    #[test]
+    fn body_without_marker_matching_goal_is_not_updated() {
+        let document = goal_without_asks();
+        let content = issue_text("2025h1", &document, &[], &[], None).unwrap();
+        assert_eq!(plan_body_update(&document, &content, &content), None);
+    }
+
+    fn subgoal_plan() -> GoalPlan {
+        let item = |text: &str, notes: &str| crate::goal::PlanItem {
+            text: text.to_string(),
+            owners: "@owner".to_string(),
+            notes: notes.to_string(),
+            effort: None,
+            blocked: None,
+            stretch: false,
+        };
+        GoalPlan {
+            subgoal: Some(spanned::Spanned::here("Stabilize".to_string())),
+            plan_items: vec![item("Write RFC", "![Complete][]"), item("Implement", "")],
+        }
+    }
+
+    #[test]
+    fn subgoal_tasks_are_flat_by_default() {
+        let tasks = task_items(&subgoal_plan(), false).unwrap();
+        assert_eq!(tasks[0], "### Stabilize");
+        assert!(!tasks.join("\n").contains("<details>"));
+    }
+
+    #[test]
+    fn team_asks_are_not_listed_as_tasks() {
+        let mut plan = subgoal_plan();
+        plan.plan_items.push(crate::goal::PlanItem {
+            text: "Design meeting".to_string(),
+            owners: "![Team][] [lang]".to_string(),
+            notes: String::new(),
+            effort: None,
+            blocked: None,
+            stretch: false,
+        });
+        let tasks = task_items(&plan, false).unwrap().join("\n");
+        assert!(tasks.contains("* [ ] Implement (@owner)"), "{tasks}");
+        assert!(!tasks.contains("Design meeting"), "{tasks}");
+
+        plan.plan_items.drain(..2);
+        assert!(task_items(&plan, false).unwrap().is_empty());
+    }
+
+    #[test]
+    fn subgoal_tasks_can_be_collapsed() {
+        let tasks = task_items(&subgoal_plan(), true).unwrap().join("\n");
+        assert!(
+            tasks.starts_with("<details><summary>Stabilize (1/2)</summary>\n"),
+            "{tasks}"
+        );
+        assert!(tasks.contains("* [x] Write RFC"), "{tasks}");
+        assert!(tasks.contains("* [ ] Implement"), "{tasks}");
+        assert!(tasks.ends_with("</details>\n"), "{tasks}");
+    }
+
+    #[test]
+    fn stretch_tasks_are_marked_and_counted_separately() {
+        let mut plan = subgoal_plan();
+        plan.plan_items.push(PlanItem {
+            text: "Write a blog post".to_string(),
+            owners: "@owner".to_string(),
+            notes: "![Complete][]".to_string(),
+            effort: None,
+            blocked: None,
+            stretch: true,
+        });
+
+        let counts = TaskCounts::of_items(&plan.plan_items);
+        assert_eq!((counts.completed, counts.total), (1, 2));
+        assert_eq!(counts.stretch_suffix(), ", plus 1/1 stretch");
+
+        let tasks = task_items(&plan, true).unwrap().join("\n");
+        assert!(
+            tasks.starts_with("<details><summary>Stabilize (1/2, plus 1/1 stretch)</summary>\n"),
+            "{tasks}"
+        );
+        assert!(
+            tasks.contains("* [x] Write a blog post *(stretch)* (@owner)"),
+            "{tasks}"
+        );
+    }
+
+    #[test]
+    fn blocked_tasks_are_marked_and_counted() {
+        let mut plan = subgoal_plan();
+        plan.plan_items[1].blocked = Some("waiting on the RFC".to_string());
+        // A completed task is no longer blocked, whatever its annotation.
+        plan.plan_items[0].blocked = Some(String::new());
+
+        let counts = TaskCounts::of_items(&plan.plan_items);
+        assert_eq!((counts.completed, counts.total, counts.blocked), (1, 2, 1));
+
+        let tasks = task_items(&plan, true).unwrap().join("\n");
+        assert!(
+            tasks.starts_with("<details><summary>Stabilize (1/2, 1 blocked)</summary>\n"),
+            "{tasks}"
+        );
+        assert!(
+            tasks.contains("* [ ] Implement (@owner) 🚫 **blocked:** waiting on the RFC"),
+            "{tasks}"
+        );
+        assert!(tasks.contains("* [x] Write RFC (@owner)\n"), "{tasks}");
+    }
+
+    #[test]
+    fn forced_body_update_preserves_original_text_once() {
+        let document = goal_without_asks();
+        let content = issue_text("2025h1", &document, &[], &[], None).unwrap();
+
+        let body = plan_forced_body_update(&document, &content, "Original text", true).unwrap();
+        assert!(
+            body.contains("<details>\nOriginal text\n</details>"),
+            "{body}"
+        );
+
+        // Forcing again regenerates the body, without preserving our own text a second time.
+        let again = plan_forced_body_update(&document, &content, &body, true).unwrap();
+        assert_eq!(again.matches("<details>").count(), 1, "{again}");
+        assert!(again.contains("Original text"), "{again}");
+    }
+
+    #[test]
+    fn assignees_are_capped_primary_owners_first() {
+        let owners = ["poc", "a", "poc", "b", "c"].map(String::from).to_vec();
+        let (assignees, unassigned) = cap_assignees(owners, 2);
+        assert_eq!(
+            assignees,
+            BTreeSet::from(["poc".to_string(), "a".to_string()])
+        );
+        assert_eq!(unassigned, ["b", "c"]);
+
+        let text = issue_text("2025h1", &goal_without_asks(), &[], &unassigned, None).unwrap();
+        assert!(
+            text.contains("| Point of contact | **@owner** (not assigned: @b, @c) |"),
+            "{text}"
+        );
+    }
+
+    fn repository() -> Repository {
+        Repository::new("rust-lang", "rust-project-goals")
+    }
+
+    /// Plan the issue actions for `document` against `github` without printing anything.
+    fn plan<'doc>(
+        github: &MockGithub,
+        document: &'doc GoalDocument,
+        lock: bool,
+    ) -> BTreeSet<GithubAction<'doc>> {
+        let content = issue_text("2025h1", document, &[], &[], None).unwrap();
+        let desired_issues = BTreeSet::from([desired_issue(document, &content)]);
+        plan_issues(
+            github,
+            &repository(),
+            "2025h1",
+            desired_issues,
+            SyncOptions {
+                lock,
+                ..SyncOptions::default()
+            },
+            &mut |_| Ok(()),
+        )
+        .unwrap()
+    }
+
+    /// Execute `actions` against `github`, except for linking (which would edit the goal file).
+    fn execute(github: &MockGithub, actions: BTreeSet<GithubAction<'_>>) {
+        for action in actions {
+            if !matches!(action, GithubAction::LinkToTrackingIssue { .. }) {
+                action.execute(github, &repository()).unwrap();
+            }
+        }
+    }
+
+    #[test]
+    fn new_goal_gets_an_issue_that_is_then_linked() {
+        let github = MockGithub::new();
+        let document = goal_without_asks();
+
+        let actions = plan(&github, &document, false);
+        assert!(
+            matches!(
+                Vec::from_iter(&actions)[..],
+                [GithubAction::CreateIssue { .. }]
+            ),
+            "{actions:?}"
+        );
+        execute(&github, actions);
+        assert_eq!(github.calls(), ["create issue #1"]);
+
+        // The created issue is found by its title, and only needs to be linked.
+        let actions = plan(&github, &document, false);
+        assert!(
+            matches!(
+                Vec::from_iter(&actions)[..],
+                [GithubAction::LinkToTrackingIssue { issue_id, .. }] if issue_id.number == 1
+            ),
+            "{actions:?}"
+        );
+    }
+
+    #[test]
+    fn issue_is_not_created_twice() {
+        let github = MockGithub::new();
+        let document = goal_without_asks();
+        let actions = plan(&github, &document, false);
+
+        // As if an earlier attempt timed out after github created the issue.
+        let Some(GithubAction::CreateIssue { issue }) = actions.first() else {
+            panic!("{actions:?}");
+        };
+        github
+            .create_issue(
+                &repository(),
+                &issue.body,
+                &issue.title,
+                &issue.labels,
+                &issue.assignees,
+                &issue.milestone,
+            )
+            .unwrap();
+
+        execute(&github, actions);
+        assert_eq!(github.calls(), ["create issue #1"]);
+    }
+
+    #[test]
+    fn issue_is_locked_only_once() {
+        let github = MockGithub::new();
+        let document = goal_without_asks();
+        execute(&github, plan(&github, &document, true));
+
+        let actions = plan(&github, &document, true);
+        assert!(
+            matches!(
+                Vec::from_iter(&actions)[..],
+                [
+                    GithubAction::Comment { number: 1, .. },
+                    GithubAction::LockIssue { number: 1 },
+                    GithubAction::LinkToTrackingIssue { .. },
+                ]
+            ),
+            "{actions:?}"
+        );
+        execute(&github, actions);
+        assert_eq!(github.calls(), ["create issue #1", "comment #1", "lock #1"]);
+
+        let actions = plan(&github, &document, true);
+        assert!(
+            matches!(
+                Vec::from_iter(&actions)[..],
+                [GithubAction::LinkToTrackingIssue { .. }]
+            ),
+            "{actions:?}"
+        );
+    }
+
+    #[test]
+    fn tracking_issues_are_added_to_the_project_once() {
+        let document = goal_without_asks();
+        let content = issue_text("2025h1", &document, &[], &[], None).unwrap();
+        let github = MockGithub::new().with_project_item(7, 3);
+        let plan = || {
+            let desired_issues = BTreeSet::from([desired_issue(&document, &content)]);
+            let sync_options = SyncOptions {
+                project: Some(Project {
+                    number: 7,
+                    status: Some("In progress"),
+                }),
+                ..SyncOptions::default()
+            };
+            plan_issues(
+                &github,
+                &repository(),
+                "2025h1",
+                desired_issues,
+                sync_options,
+                &mut |_| Ok(()),
+            )
+            .unwrap()
+        };
+
+        // A new issue is added to the board on the pass after it was created.
+        execute(&github, plan());
+        let actions = plan();
+        assert!(
+            actions.contains(&GithubAction::AddToProject {
+                number: 1,
+                project: 7,
+                status: Some("In progress".to_string()),
+            }),
+            "{actions:?}"
+        );
+        execute(&github, actions);
+        let actions = plan();
+        assert!(
+            !actions
+                .iter()
+                .any(|action| matches!(action, GithubAction::AddToProject { .. })),
+            "{actions:?}"
+        );
+        assert!(github
+            .calls()
+            .ends_with(&["add #1 to project 7".to_string()]));
+    }
+
+    #[test]
+    fn period_can_be_tracked_by_label() {
+        let document = goal_without_asks();
+        let content = issue_text("2025h1", &document, &[], &[], None).unwrap();
+        let desired = desired_issue(&document, &content);
+        let label = |name: &str| GhLabel {
+            name: name.to_string(),
+            color: String::new(),
+        };
+        let github = MockGithub::new().with_issue(ExistingGithubIssue {
+            number: 1,
+            labels: vec![label(TRACKING_ISSUE_LABEL), label("2024h2")],
+            milestone: None,
+            ..existing_issue(&desired.body)
+        });
+
+        let issue_id = IssueId::new(repository(), 1);
+        let plan = || {
+            let desired_issues = BTreeSet::from([GithubIssue {
+                tracking_issue: Some(&issue_id),
+                ..desired_issue(&document, &content)
+            }]);
+            let sync_options = SyncOptions {
+                period_by: PeriodBy::Label,
+                ..SyncOptions::default()
+            };
+            plan_issues(
+                &github,
+                &repository(),
+                "2025h1",
+                desired_issues,
+                sync_options,
+                &mut |_| Ok(()),
+            )
+            .unwrap()
+        };
+
+        let actions = plan();
+        assert!(
+            matches!(
+                Vec::from_iter(&actions)[..],
+                [GithubAction::ChangeMilestone { number: 1, period_by: PeriodBy::Label, previous, .. }]
+                    if previous == &["2024h2"]
+            ),
+            "{actions:?}"
+        );
+        execute(&github, actions);
+        assert_eq!(
+            github.calls(),
+            [
+                "add label 2025h1 #1",
+                "remove label 2024h2 #1",
+                "comment #1"
+            ]
+        );
+
+        let actions = plan();
+        assert!(actions.is_empty(), "{actions:?}");
+    }
+
+    #[test]
+    fn issue_types_are_set_where_the_repository_has_them() {
+        let document = goal_without_asks();
+        let content = issue_text("2025h1", &document, &[], &[], None).unwrap();
+        let desired = desired_issue(&document, &content);
+        let issue_id = IssueId::new(repository(), 22);
+        let plan = |github: &MockGithub| {
+            let desired_issues = BTreeSet::from([GithubIssue {
+                issue_type: Some("Task".to_string()),
+                tracking_issue: Some(&issue_id),
+                ..desired_issue(&document, &content)
+            }]);
+            let sync_options = SyncOptions {
+                issue_type: Some("Task"),
+                ..SyncOptions::default()
+            };
+            plan_issues(
+                github,
+                &repository(),
+                "2025h1",
+                desired_issues,
+                sync_options,
+                &mut |_| Ok(()),
+            )
+        };
+
+        // Without issue types, there is nothing to do.
+        let github = MockGithub::new().with_issue(existing_issue(&desired.body));
+        let actions = plan(&github).unwrap();
+        assert!(actions.is_empty(), "{actions:?}");
+
+        let github = MockGithub::new()
+            .with_issue(existing_issue(&desired.body))
+            .with_issue_types(&["Bug", "Task"]);
+        let actions = plan(&github).unwrap();
+        assert_eq!(
+            Vec::from_iter(&actions),
+            [&GithubAction::SetIssueType {
+                number: 22,
+                issue_type: "Task".to_string()
+            }]
+        );
+        execute(&github, actions);
+        assert!(plan(&github).unwrap().is_empty());
+
+        let github = MockGithub::new()
+            .with_issue(existing_issue(&desired.body))
+            .with_issue_types(&["Bug"]);
+        assert!(plan(&github).is_err());
+    }
+
+    #[test]
+    fn tasks_of_long_goals_move_to_a_comment() {
+        // A long summary and many tasks: each fits in an issue body, but not both.
+        let summary = "We can do everything ourselves. ".repeat(1200);
+        let rows: String = (0..600)
+            .map(|i| format!("| Write part {i} of the documentation | @owner | |\n"))
+            .collect();
+        let text = GOAL_WITHOUT_ASKS.replace("We can do everything ourselves.", &summary) + &rows;
+        let document = ourselves(&text);
+        let render = |tasks: &str| render_issue_text("2025h1", &document, &[], &[], None, tasks);
+
+        let error = issue_content(&document, false, render)
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("src/2025h1/ourselves.md"), "{error}");
+        assert!(error.contains("--truncate-body"), "{error}");
+
+        let (content, comment) = issue_content(&document, true, render).unwrap();
+        let comment = comment.unwrap();
+        assert!(content.contains(TASKS_POINTER), "{content}");
+        assert!(!content.contains("Write part"), "{content}");
+        assert!(body_len(&content) <= MAX_BODY_LEN);
+        assert!(comment.starts_with(TASKS_COMMENT_MARKER));
+        assert!(comment.contains("Write part 599 of the documentation"));
+
+        // The comment is posted once, then kept in line with the tasks.
+        let mut issue = existing_issue(&desired_issue(&document, &content).body);
+        assert_eq!(
+            plan_tasks_comment(&issue, Some(&comment)),
+            [GithubAction::Comment {
+                number: 22,
+                body: comment.clone(),
+            }]
+        );
+        issue.comments.push(ExistingGithubComment {
+            author: "@goals-bot".to_string(),
+            body: comment.clone(),
+            created_at: "2025-01-01T00:00:00Z".to_string(),
+            url: "https://github.com/rust-lang/rust-project-goals/issues/22#issuecomment-1"
+                .to_string(),
+        });
+        assert!(plan_tasks_comment(&issue, Some(&comment)).is_empty());
+        let changed = comment.replace("part 0 ", "part zero ");
+        assert!(matches!(
+            &plan_tasks_comment(&issue, Some(&changed))[..],
+            [GithubAction::UpdateComment { number: 22, body, .. }] if *body == changed
+        ));
+        assert!(matches!(
+            &plan_tasks_comment(&issue, None)[..],
+            [GithubAction::DeleteComment { number: 22, .. }]
+        ));
+    }
+
+    #[test]
+    fn continuation_comment_needs_a_previous_milestone() {
+        let document = goal_without_asks();
+        let content = issue_text("2025h1", &document, &[], &[], None).unwrap();
+        let issue_id = IssueId::new(repository(), 22);
+        let desired = || GithubIssue {
+            tracking_issue: Some(&issue_id),
+            ..desired_issue(&document, &content)
+        };
+        let calls = |milestone: Option<&str>| {
+            let existing = ExistingGithubIssue {
+                milestone: milestone.map(|title| crate::gh::milestone::GhMilestone {
+                    number: 1,
+                    title: title.to_string(),
+                    description: String::new(),
+                    due_on: None,
+                }),
+                ..existing_issue(&desired().body)
+            };
+            let github = MockGithub::new().with_issue(existing.clone());
+            let actions = plan_issue_actions(
+                &repository(),
+                "2025h1",
+                desired(),
+                Some(existing),
+                SyncOptions::default(),
+            )
+            .unwrap();
+            execute(&github, actions);
+            github.calls()
+        };
+
+        // Moving from an earlier milestone announces the continuation...
+        assert_eq!(
+            calls(Some("2024h2")),
+            ["change milestone #22", "comment #22"]
+        );
+
+        // ...but an issue that had no milestone continues nothing.
+        assert_eq!(calls(None), ["change milestone #22"]);
+    }
+
+    #[test]
+    fn labels_are_created_or_recolored() {
+        let github = MockGithub::new().with_label(TRACKING_ISSUE_LABEL, "000000");
+        let teams = BTreeSet::new();
+
+        let actions = initialize_labels(
+            &github,
+            &repository(),
+            &teams,
+            &BTreeSet::new(),
+            &label_options(""),
+        )
+        .unwrap();
+        execute(&github, actions);
+        assert_eq!(
+            github.calls(),
+            [
+                format!("create label {FLAGSHIP_LABEL}"),
+                format!("update label {TRACKING_ISSUE_LABEL}"),
+            ]
+        );
+
+        let actions = initialize_labels(
+            &github,
+            &repository(),
+            &teams,
+            &BTreeSet::new(),
+            &label_options(""),
+        )
+        .unwrap();
+        assert!(actions.is_empty(), "{actions:?}");
+    }
+
+    #[test]
+    fn declared_labels_are_created_and_added_but_not_recolored() {
+        let github = MockGithub::new()
+            .with_label(TRACKING_ISSUE_LABEL, "f5f1fd")
+            .with_label(FLAGSHIP_LABEL, "5319E7")
+            .with_label("A-async", "000000");
+        let extra_labels = BTreeSet::from(["A-async".to_string(), "WG-async".to_string()]);
+        let actions = initialize_labels(
+            &github,
+            &repository(),
+            &BTreeSet::new(),
+            &extra_labels,
+            &label_options(""),
+        )
+        .unwrap();
+        execute(&github, actions);
+        assert_eq!(github.calls(), ["create label WG-async"]);
+
+        let text = GOAL_WITHOUT_ASKS.replace(
+            "| Status           | Proposed                 |\n",
+            "| Status           | Proposed                 |\n\
+             | Labels           | `A-async`, WG-async      |\n",
+        );
+        let document = ourselves(&text);
+        assert_eq!(
+            issue_labels(&document, &label_options("goals/")).unwrap(),
+            [
+                "A-async".to_string(),
+                "WG-async".to_string(),
+                format!("goals/{TRACKING_ISSUE_LABEL}"),
+            ]
+        );
+
+        let content = issue_text("2025h1", &document, &[], &[], None).unwrap();
+        let existing_issue = ExistingGithubIssue {
+            labels: vec![GhLabel {
+                name: "A-async".to_string(),
+                color: "000000".to_string(),
+            }],
+            ..existing_issue(&content)
+        };
+        let actions = plan_issue_actions(
+            &repository(),
+            "2025h1",
+            desired_issue(&document, &content),
+            Some(existing_issue),
+            SyncOptions::default(),
+        )
+        .unwrap();
+        assert!(
+            actions.contains(&GithubAction::AddLabel {
+                number: 22,
+                label: "WG-async".to_string()
+            }),
+            "{actions:?}"
+        );
+        assert!(!actions.iter().any(
+            |action| matches!(action, GithubAction::AddLabel { label, .. } if label == "A-async")
+        ));
+    }
+}
@@ -0,0 +1,155 @@
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use anyhow::Context;
+use handlebars::Handlebars;
+use serde::Serialize;
+
+const TEMPLATE_NAME: &str = "issue_body";
+
+/// Set by `--body-template`; when unset, the built-in `templates/issue_body.hbs` is used.
+static BODY_TEMPLATE: OnceLock<BodyTemplate> = OnceLock::new();
+
+/// Use the template at `path` to render tracking issue bodies for the rest of this run.
+pub fn set_body_template(path: &Path) -> anyhow::Result<()> {
+    let template = BodyTemplate::from_file(path)?;
+    if BODY_TEMPLATE.set(template).is_err() {
+        anyhow::bail!("the issue body template was already loaded");
+    }
+    Ok(())
+}
+
+/// The template used to render the body of tracking issues.
+pub(crate) struct BodyTemplate {
+    reg: Handlebars<'static>,
+}
+
+impl BodyTemplate {
+    /// The template set with [`set_body_template`], or else the built-in one.
+    pub(crate) fn get() -> anyhow::Result<&'static BodyTemplate> {
+        if let Some(template) = BODY_TEMPLATE.get() {
+            return Ok(template);
+        }
+        let builtin = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("../../templates")
+            .join(format!("{TEMPLATE_NAME}.hbs"));
+        let template = BodyTemplate::from_file(&builtin)?;
+        Ok(BODY_TEMPLATE.get_or_init(|| template))
+    }
+
+    /// Load the template at `path`, checking that it only references known variables.
+    pub(crate) fn from_file(path: &Path) -> anyhow::Result<Self> {
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("reading issue body template `{}`", path.display()))?;
+        Self::from_text(&text)
+            .with_context(|| format!("loading issue body template `{}`", path.display()))
+    }
+
+    fn from_text(text: &str) -> anyhow::Result<Self> {
+        let mut reg = Handlebars::new();
+
+        // Unknown variables are errors, and the body is markdown, not HTML.
+        reg.set_strict_mode(true);
+        reg.register_escape_fn(handlebars::no_escape);
+        reg.register_template_string(TEMPLATE_NAME, text)?;
+
+        // In strict mode, rendering fails on any variable that `IssueBody` does not define.
+        let template = BodyTemplate { reg };
+        template.render(&IssueBody::sample())?;
+        Ok(template)
+    }
+
+    pub(crate) fn render(&self, body: &IssueBody<'_>) -> anyhow::Result<String> {
+        Ok(self.reg.render(TEMPLATE_NAME, body)?)
+    }
+}
+
+/// The variables available to the issue body template.
+#[derive(Serialize, Debug)]
+pub(crate) struct IssueBody<'a> {
+    /// Milestone of the goal (e.g., `2025h1`)
+    pub timeframe: &'a str,
+
+    /// Title of the goal
+    pub title: &'a str,
+
+    /// The point of contact, in bold (plus any teams the goal is owned on behalf of)
+    pub poc: String,
+
+    /// Comma-separated other owners, or empty if there are none
+    pub additional_owners: String,
+
+    /// Comma-separated teams with asks (as links), or `None`
+    pub teams: String,
+
+    /// Link to the goal document in the published book
+    pub goal_document: String,
+
+    /// Text of the summary section
+    pub summary: &'a str,
+
+    /// Checkbox list of the tasks, grouped by subgoal
+    pub tasks: String,
+
+    /// Checkbox list of the team asks (each with the `![Team][]` badge), or empty
+    pub asks: String,
+
+    /// Checkbox list of the goals naming this one as their umbrella, or empty
+    pub children: String,
+}
+
+impl IssueBody<'static> {
+    fn sample() -> Self {
+        IssueBody {
+            timeframe: "2025h1",
+            title: "Title",
+            poc: "**@owner**".to_string(),
+            additional_owners: "@other".to_string(),
+            teams: "None".to_string(),
+            goal_document: "[2025h1/goal](https://example.com)".to_string(),
+            summary: "Summary",
+            tasks: "* [ ] Task".to_string(),
+            asks: String::new(),
+            children: String::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builtin_template_renders_metadata_table() {
+        let text = BodyTemplate::get()
+            .unwrap()
+            .render(&IssueBody::sample())
+            .unwrap();
+        assert!(text.starts_with("\n| Metadata         | |\n"), "{text:?}");
+        assert!(
+            text.contains(
+                "| Point of contact | **@owner** |\n\
+                 | Additional owners | @other |\n\
+                 | Team(s)          | None |\n"
+            ),
+            "{text:?}"
+        );
+        assert!(text.ends_with("Team%20ask-red\n"), "{text:?}");
+
+        let text = BodyTemplate::get()
+            .unwrap()
+            .render(&IssueBody {
+                additional_owners: String::new(),
+                ..IssueBody::sample()
+            })
+            .unwrap();
+        assert!(!text.contains("Additional owners"), "{text:?}");
+    }
+
+    #[test]
+    fn unknown_variables_are_rejected() {
+        assert!(BodyTemplate::from_text("{{summary}}").is_ok());
+        let error = BodyTemplate::from_text("{{summery}}").err().unwrap();
+        assert!(format!("{error:#}").contains("summery"), "{error:#}");
+    }
+}
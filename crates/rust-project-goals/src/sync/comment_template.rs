@@ -0,0 +1,193 @@
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use anyhow::Context;
+use handlebars::Handlebars;
+use serde::Serialize;
+
+use crate::gh::issues::{CONTINUING_GOAL_MARKER, CONTINUING_GOAL_PREFIX, LOCK_MARKER, LOCK_TEXT};
+
+const CONTINUING_GOAL: &str = "continuing_goal";
+const LOCK_ISSUE: &str = "lock_issue";
+
+/// Set by `--comment-template`; when unset, the built-in templates are used.
+static COMMENT_TEMPLATES: OnceLock<CommentTemplates> = OnceLock::new();
+
+/// Use the `continuing_goal.hbs` and/or `lock_issue.hbs` templates found in `dir`
+/// for the comments we post on tracking issues, for the rest of this run.
+pub fn set_comment_templates(dir: &Path) -> anyhow::Result<()> {
+    let templates = CommentTemplates::load(Some(dir))?;
+    if COMMENT_TEMPLATES.set(templates).is_err() {
+        anyhow::bail!("the comment templates were already loaded");
+    }
+    Ok(())
+}
+
+/// The templates for the comments posted when a goal continues into a new milestone
+/// and when its tracking issue is locked.
+pub struct CommentTemplates {
+    reg: Handlebars<'static>,
+}
+
+impl CommentTemplates {
+    /// The templates set with [`set_comment_templates`], or else the built-in ones.
+    pub fn get() -> anyhow::Result<&'static CommentTemplates> {
+        if let Some(templates) = COMMENT_TEMPLATES.get() {
+            return Ok(templates);
+        }
+        let templates = CommentTemplates::load(None)?;
+        Ok(COMMENT_TEMPLATES.get_or_init(|| templates))
+    }
+
+    /// Load the templates in `dir`, falling back to the built-in ones for those it lacks.
+    fn load(dir: Option<&Path>) -> anyhow::Result<Self> {
+        let builtin = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../templates");
+        let mut texts = vec![];
+        for name in [CONTINUING_GOAL, LOCK_ISSUE] {
+            let file_name = format!("{name}.hbs");
+            let path = match dir.map(|dir| dir.join(&file_name)) {
+                Some(path) if path.exists() => path,
+                _ => builtin.join(&file_name),
+            };
+            let text = std::fs::read_to_string(&path)
+                .with_context(|| format!("reading comment template `{}`", path.display()))?;
+            texts.push((path, text));
+        }
+
+        if let Some(dir) = dir {
+            if texts.iter().all(|(path, _)| !path.starts_with(dir)) {
+                anyhow::bail!(
+                    "`{}` contains neither `{CONTINUING_GOAL}.hbs` nor `{LOCK_ISSUE}.hbs`",
+                    dir.display()
+                );
+            }
+        }
+
+        let [(continuing_path, continuing), (lock_path, lock)] = &texts[..] else {
+            unreachable!()
+        };
+        Self::from_texts(continuing, lock).with_context(|| {
+            format!(
+                "loading comment templates `{}` and `{}`",
+                continuing_path.display(),
+                lock_path.display()
+            )
+        })
+    }
+
+    fn from_texts(continuing_goal: &str, lock_issue: &str) -> anyhow::Result<Self> {
+        let mut reg = Handlebars::new();
+
+        // Unknown variables are errors, and comments are markdown, not HTML.
+        reg.set_strict_mode(true);
+        reg.register_escape_fn(handlebars::no_escape);
+        reg.register_template_string(CONTINUING_GOAL, continuing_goal)?;
+        reg.register_template_string(LOCK_ISSUE, lock_issue)?;
+
+        let templates = CommentTemplates { reg };
+        templates.continuing_goal(&GoalComment::sample())?;
+        templates.lock_issue(&GoalComment::sample())?;
+        Ok(templates)
+    }
+
+    /// The comment posted when a tracking issue moves to a new milestone.
+    pub fn continuing_goal(&self, comment: &GoalComment<'_>) -> anyhow::Result<String> {
+        let text = self.reg.render(CONTINUING_GOAL, comment)?;
+        let text = text.trim();
+
+        // We need to recognize our own comments later on (e.g., to leave them out of updates).
+        if text.starts_with(CONTINUING_GOAL_PREFIX) {
+            Ok(text.to_string())
+        } else {
+            Ok(format!("{text}\n\n{CONTINUING_GOAL_MARKER}"))
+        }
+    }
+
+    /// The comment posted when a tracking issue is locked.
+    pub fn lock_issue(&self, comment: &GoalComment<'_>) -> anyhow::Result<String> {
+        let text = self.reg.render(LOCK_ISSUE, comment)?;
+        let text = text.trim();
+
+        // This comment is how we know that the issue was locked, see `was_locked`.
+        if text == LOCK_TEXT {
+            Ok(text.to_string())
+        } else {
+            Ok(format!("{text}\n\n{LOCK_MARKER}"))
+        }
+    }
+}
+
+/// The variables available to the comment templates.
+#[derive(Serialize, Debug)]
+pub struct GoalComment<'a> {
+    /// Milestone the tracking issue was in before (e.g., `2024h2`), or empty
+    pub previous_milestone: &'a str,
+
+    /// Milestone of the goal (e.g., `2025h1`)
+    pub milestone: &'a str,
+
+    /// Title of the goal
+    pub title: &'a str,
+
+    /// Comma-separated owners of the goal, point of contact first
+    pub owners: String,
+}
+
+impl GoalComment<'static> {
+    fn sample() -> Self {
+        GoalComment {
+            previous_milestone: "2024h2",
+            milestone: "2025h1",
+            title: "Title",
+            owners: "@owner, @other".to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::gh::issues::ExistingGithubComment;
+
+    use super::*;
+
+    fn comment(body: String) -> ExistingGithubComment {
+        ExistingGithubComment {
+            author: "@bot".to_string(),
+            body,
+            created_at: "2025-01-01T00:00:00Z".to_string(),
+            url: String::new(),
+        }
+    }
+
+    #[test]
+    fn builtin_templates_keep_the_terse_form() {
+        let templates = CommentTemplates::get().unwrap();
+        assert_eq!(
+            templates.continuing_goal(&GoalComment::sample()).unwrap(),
+            format!("{CONTINUING_GOAL_PREFIX} 2025h1")
+        );
+        assert_eq!(
+            templates.lock_issue(&GoalComment::sample()).unwrap(),
+            LOCK_TEXT
+        );
+    }
+
+    #[test]
+    fn custom_comments_are_still_recognized() {
+        let templates = CommentTemplates::from_texts(
+            "{{title}} is continuing from {{previous_milestone}} into {{milestone}}.",
+            "Please contact {{owners}} with questions.",
+        )
+        .unwrap();
+
+        let continuing = templates.continuing_goal(&GoalComment::sample()).unwrap();
+        assert!(continuing.starts_with("Title is continuing from 2024h2 into 2025h1.\n"));
+        assert!(comment(continuing).is_automated_comment());
+
+        let lock = templates.lock_issue(&GoalComment::sample()).unwrap();
+        assert!(lock.starts_with("Please contact @owner, @other with questions.\n"));
+        assert!(comment(lock).is_automated_comment());
+
+        assert!(CommentTemplates::from_texts("{{milestones}}", "").is_err());
+    }
+}
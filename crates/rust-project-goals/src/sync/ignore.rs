@@ -0,0 +1,117 @@
+use std::collections::BTreeSet;
+use std::path::Path;
+
+use anyhow::Context;
+
+use super::GithubAction;
+use crate::util::commas;
+
+/// Actions that a sync must never execute, listed in the file given to `--ignore-file`.
+///
+/// Each line names the kind of an action and the issue it applies to, like
+/// `ChangeTitle #123` (or `change_title #123`, as in `--output-actions-file`).
+/// Blank lines and lines starting with `#` are skipped.
+#[derive(Debug, Default)]
+pub struct IgnoredActions {
+    /// The kind of action (in snake case) and the issue number.
+    entries: BTreeSet<(String, u64)>,
+}
+
+impl IgnoredActions {
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("reading ignore file `{}`", path.display()))?;
+        Self::parse(&text).with_context(|| format!("parsing ignore file `{}`", path.display()))
+    }
+
+    fn parse(text: &str) -> anyhow::Result<Self> {
+        let mut entries = BTreeSet::new();
+        for (index, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let entry = line
+                .split_once(char::is_whitespace)
+                .and_then(|(kind, number)| {
+                    let number: u64 = number.trim().strip_prefix('#')?.parse().ok()?;
+                    Some((snake_case(kind), number))
+                });
+            let Some((kind, number)) = entry else {
+                anyhow::bail!(
+                    "line {}: expected an action and an issue, like `ChangeTitle #123`, found `{line}`",
+                    index + 1
+                );
+            };
+            if !GithubAction::KINDS.contains(&kind.as_str()) {
+                anyhow::bail!(
+                    "line {}: unknown action `{kind}` (expected one of {})",
+                    index + 1,
+                    commas(GithubAction::KINDS.iter().map(|kind| format!("`{kind}`")))
+                );
+            }
+            entries.insert((kind, number));
+        }
+        Ok(Self { entries })
+    }
+
+    /// True if `action` is listed (actions that do not apply to an issue never are).
+    pub fn contains(&self, action: &GithubAction<'_>) -> bool {
+        let Some(number) = action.issue_number() else {
+            return false;
+        };
+        self.entries.contains(&(action.kind().to_string(), number))
+    }
+}
+
+/// `ChangeTitle` as `change_title`; names already in snake case are left alone.
+fn snake_case(name: &str) -> String {
+    let mut result = String::new();
+    for (index, c) in name.chars().enumerate() {
+        if c.is_ascii_uppercase() {
+            if index > 0 {
+                result.push('_');
+            }
+            result.push(c.to_ascii_lowercase());
+        } else {
+            result.push(c);
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn entries_match_on_kind_and_issue() {
+        let ignored = IgnoredActions::parse(
+            "# the title was shortened on purpose\nChangeTitle #123\n\nupdate_issue_body #456\n",
+        )
+        .unwrap();
+        let change_title = |number| GithubAction::ChangeTitle {
+            number,
+            title: "Do it".to_string(),
+        };
+        assert!(ignored.contains(&change_title(123)));
+        assert!(!ignored.contains(&change_title(456)));
+        assert!(ignored.contains(&GithubAction::UpdateIssueBody {
+            number: 456,
+            body: String::new(),
+            last_synced: None,
+        }));
+        assert!(!ignored.contains(&GithubAction::LockIssue { number: 123 }));
+
+        let error = IgnoredActions::parse("ChangeTitle 123").unwrap_err();
+        assert!(error.to_string().contains("line 1"), "{error}");
+
+        let error = IgnoredActions::parse("\nChangeTitel #123").unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("line 2: unknown action `change_titel`"),
+            "{error}"
+        );
+    }
+}
@@ -0,0 +1,78 @@
+//! Snapshot tests for the body of tracking issues: each fixture below is rendered with
+//! [`issue_text`] and compared with `insta` against its `.snap` file in `snapshots`. After
+//! an intended change of format, run `cargo insta test` and accept the new bodies with
+//! `cargo insta review`.
+//!
+//! The teams asked in the fixtures are looked up in the offline team data of
+//! `src/team/fixtures`.
+
+use crate::goal::{test_support::GoalText, GoalDocument};
+
+use super::issue_text;
+
+fn assert_issue_text(name: &str, document: &GoalDocument) {
+    let text = issue_text("2025h1", document, &[], &[], None).unwrap();
+    insta::assert_snapshot!(name, text);
+}
+
+#[test]
+fn flagship_goal() {
+    let document = GoalText::new("Ship the big thing")
+        .row("Status", "Flagship")
+        .task("Implementation", "@owner", "")
+        .task("Write RFC", "@owner", "![Complete][]")
+        .document("src/2025h1/flagship.md");
+    assert_issue_text("flagship", &document);
+}
+
+#[test]
+fn goal_with_subgoals() {
+    let document = GoalText::new("Design and stabilize")
+        .row("Status", "Accepted")
+        .subgoal("Design")
+        .task("Write RFC", "@owner", "![Complete][]")
+        .subgoal("Stabilize")
+        .task("Implement", "@owner", "")
+        .task("Write a blog post (stretch)", "@owner", "")
+        .document("src/2025h1/subgoals.md");
+    assert_issue_text("subgoals", &document);
+}
+
+#[test]
+fn goal_with_username_owners() {
+    let document = GoalText::new("Shared work")
+        .row("Status", "Accepted")
+        .row("Additional owners", "@alice, @bob")
+        .task("Implementation", "@alice, @bob", "")
+        .task("Documentation", "@carol", "")
+        .document("src/2025h1/usernames.md");
+    assert_issue_text("username_owners", &document);
+}
+
+#[test]
+fn goal_without_tasks() {
+    let document = GoalText::new("Nothing to do yet")
+        .row("Status", "Accepted")
+        .row("Tasks intentionally empty", "true")
+        .without_tasks()
+        .document("src/2025h1/empty.md");
+    assert_issue_text("empty_tasks", &document);
+}
+
+#[test]
+fn goal_with_team_asks() {
+    let document = GoalText::new("Get the teams on board")
+        .row("Status", "Accepted")
+        .task("Implementation", "@owner", "")
+        .task(
+            "Discussion and moral support",
+            "![Team][] [lang], [compiler]",
+            "",
+        )
+        .task("Standard reviews", "![Team][] [compiler]", "![Complete][]")
+        .subgoal("Stabilize")
+        .task("Write the stabilization report", "@owner", "")
+        .task("RFC decision", "![Team][] [lang]", "")
+        .document("src/2025h1/team-asks.md");
+    assert_issue_text("team_asks", &document);
+}
@@ -3,8 +3,6 @@ use std::{collections::BTreeMap, sync::OnceLock};
 use rust_team_data::v1;
 use serde::de::DeserializeOwned;
 
-use crate::util::in_thread;
-
 trait Load<T> {
     fn load(&self, op: impl FnOnce() -> anyhow::Result<T>) -> anyhow::Result<&T>;
 }
@@ -26,29 +24,49 @@ pub struct PersonData {
     pub data: v1::Person,
 }
 
+static PEOPLE: OnceLock<anyhow::Result<BTreeMap<String, PersonData>>> = OnceLock::new();
+static TEAMS: OnceLock<anyhow::Result<BTreeMap<TeamName, v1::Team>>> = OnceLock::new();
+
+/// Use `teams` and `people` rather than fetching them from the team API, e.g. so that
+/// tests can run offline. Has no effect on data that was already loaded.
+pub fn use_team_data(teams: v1::Teams, people: v1::People) {
+    TEAMS.get_or_init(|| Ok(teams_by_name(teams)));
+    PEOPLE.get_or_init(|| Ok(people_by_username(people)));
+}
+
 /// Given a username like `@foo` finds the corresponding person data (if any).
 pub fn get_person_data(username: &str) -> anyhow::Result<Option<&'static PersonData>> {
-    static DATA: OnceLock<anyhow::Result<BTreeMap<String, PersonData>>> = OnceLock::new();
-    let people = DATA.load(|| {
-        let data: v1::People = fetch("people.json")?;
-        Ok(data
-            .people
-            .into_iter()
-            .map(|(username, value)| {
-                (
-                    username.to_lowercase(),
-                    PersonData {
-                        github_username: username,
-                        data: value,
-                    },
-                )
-            })
-            .collect())
-    })?;
+    let people = PEOPLE.load(|| Ok(people_by_username(fetch("people.json")?)))?;
 
     Ok(people.get(&username[1..].to_lowercase()))
 }
 
+fn people_by_username(people: v1::People) -> BTreeMap<String, PersonData> {
+    people
+        .people
+        .into_iter()
+        .map(|(username, value)| {
+            (
+                username.to_lowercase(),
+                PersonData {
+                    github_username: username,
+                    data: value,
+                },
+            )
+        })
+        .collect()
+}
+
+/// True if the user `username` (like `@foo`) is a current member of some Rust team.
+pub fn is_team_member(username: &str) -> anyhow::Result<bool> {
+    let username = username.trim_start_matches('@');
+    Ok(get_teams()?.values().any(|team| {
+        team.members
+            .iter()
+            .any(|member| member.github.eq_ignore_ascii_case(username))
+    }))
+}
+
 #[derive(Debug, PartialOrd, Ord, PartialEq, Eq)]
 pub struct TeamName(String);
 
@@ -63,15 +81,15 @@ pub fn get_team_names() -> anyhow::Result<impl Iterator<Item = &'static TeamName
 }
 
 fn get_teams() -> anyhow::Result<&'static BTreeMap<TeamName, v1::Team>> {
-    static DATA: OnceLock<anyhow::Result<BTreeMap<TeamName, v1::Team>>> = OnceLock::new();
-    DATA.load(|| {
-        let teams: v1::Teams = fetch("teams.json")?;
-        Ok(teams
-            .teams
-            .into_iter()
-            .map(|(team_name, value)| (TeamName(team_name.to_lowercase()), value))
-            .collect())
-    })
+    TEAMS.load(|| Ok(teams_by_name(fetch("teams.json")?)))
+}
+
+fn teams_by_name(teams: v1::Teams) -> BTreeMap<TeamName, v1::Team> {
+    teams
+        .teams
+        .into_iter()
+        .map(|(team_name, value)| (TeamName(team_name.to_lowercase()), value))
+        .collect()
 }
 
 pub fn get_team_name(team_name: &str) -> anyhow::Result<Option<&'static TeamName>> {
@@ -80,6 +98,11 @@ pub fn get_team_name(team_name: &str) -> anyhow::Result<Option<&'static TeamName
 }
 
 impl TeamName {
+    /// The bare name of the team, like `"compiler"`
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
     /// Get the data for this team.
     pub fn data(&self) -> &'static v1::Team {
         get_teams().unwrap().get(self).unwrap()
@@ -112,6 +135,7 @@ impl TeamName {
     }
 }
 
+#[cfg(not(test))]
 fn fetch<T>(path: &str) -> anyhow::Result<T>
 where
     T: DeserializeOwned + Send,
@@ -119,8 +143,23 @@ where
     // Run this on another thread because it can create a tokio runtime
     // for the block reqwest API which makes tokio grouchy when that runtime is
     // dropped.
-    in_thread(|| {
+    crate::util::in_thread(|| {
         let url = format!("{}/{}", v1::BASE_URL, path);
         Ok(reqwest::blocking::get(&url)?.json()?)
     })
 }
+
+/// The tests of this crate read the team data from `team/fixtures` rather than the team
+/// API, so that they run offline and do not depend on who is on which team today.
+#[cfg(test)]
+fn fetch<T>(path: &str) -> anyhow::Result<T>
+where
+    T: DeserializeOwned + Send,
+{
+    let json = match path {
+        "teams.json" => include_str!("team/fixtures/teams.json"),
+        "people.json" => include_str!("team/fixtures/people.json"),
+        _ => anyhow::bail!("no team data fixture for `{path}`"),
+    };
+    Ok(serde_json::from_str(json)?)
+}
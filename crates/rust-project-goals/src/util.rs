@@ -1,5 +1,5 @@
 use std::{
-    collections::BTreeSet,
+    collections::{BTreeMap, BTreeSet},
     fmt::{Display, Write},
     path::{Path, PathBuf},
 };
@@ -9,6 +9,26 @@ use walkdir::WalkDir;
 
 pub const ARROW: &str = "↳";
 
+/// Warnings printed while running a command, counted by category
+/// (e.g., `goal without tasks`) so that the command can summarize them.
+#[derive(Debug, Default)]
+pub struct Warnings {
+    counts: BTreeMap<&'static str, usize>,
+}
+
+impl Warnings {
+    /// Print a warning, counting it under `category`.
+    pub fn warn(&mut self, category: &'static str, message: impl Display) {
+        eprintln!("warning: {message}");
+        *self.counts.entry(category).or_default() += 1;
+    }
+
+    /// Number of warnings printed so far, by category.
+    pub fn counts(&self) -> &BTreeMap<&'static str, usize> {
+        &self.counts
+    }
+}
+
 /// Formats a table as markdown. The input should be a series of rows
 /// where each row has the same number of columns.
 /// The first row is the headers.